@@ -0,0 +1,199 @@
+// Log of every applied switch, one JSON object per line under XDG_STATE_HOME,
+// for working out which of the daemon, a keybinding or a manual run did what
+
+use crate::effects;
+use crate::events;
+use crate::mqtt;
+use crate::state;
+use crate::tr;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Entry {
+    // Seconds since the Unix epoch
+    #[serde(default)]
+    pub time: u64,
+    // What started the switch, e.g. `switch (terminal)` or `watch: on lid closed`
+    #[serde(default)]
+    pub trigger: String,
+    pub profile: String,
+    // The profile that was active before, if known
+    pub previous: Option<String>,
+    // Whether the config file was rewritten or the switch went over IPC only
+    #[serde(default = "persistent")]
+    pub persistent: bool,
+    // None on success, otherwise what went wrong
+    pub error: Option<String>,
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+// Entries written before transient switches were logged are all persistent
+fn persistent() -> bool {
+    true
+}
+
+// The entry as one line of the log, with `result` spelled out for readers
+impl Serialize for Entry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entry = serializer.serialize_struct("Entry", 8)?;
+        entry.serialize_field("time", &self.time)?;
+        entry.serialize_field("trigger", &self.trigger)?;
+        entry.serialize_field("profile", &self.profile)?;
+        entry.serialize_field("previous", &self.previous)?;
+        entry.serialize_field("persistent", &self.persistent)?;
+        let result = if self.error.is_none() { "ok" } else { "failed" };
+        entry.serialize_field("result", result)?;
+        entry.serialize_field("error", &self.error)?;
+        entry.serialize_field("duration_ms", &self.duration_ms)?;
+        entry.end()
+    }
+}
+
+// An entry as a live event for `events --follow` subscribers
+#[derive(Serialize)]
+struct Event<'a> {
+    event: &'static str,
+    #[serde(flatten)]
+    entry: &'a Entry,
+}
+
+impl Entry {
+    // The entry as one line of JSON for the events socket
+    pub fn event(&self) -> String {
+        let event = Event {
+            event: "switch",
+            entry: self,
+        };
+        serde_json::to_string(&event).unwrap_or_default()
+    }
+}
+
+// A switch being timed; `finish` writes its entry
+pub struct Pending {
+    entry: Entry,
+    started: Instant,
+}
+
+// Start timing a switch to `profile`
+pub fn start(trigger: &str, profile: &str, previous: Option<&str>, persistent: bool) -> Pending {
+    Pending {
+        entry: Entry {
+            time: state::now(),
+            trigger: trigger.to_string(),
+            profile: profile.to_string(),
+            previous: previous.map(str::to_string),
+            persistent,
+            error: None,
+            duration_ms: 0,
+        },
+        started: Instant::now(),
+    }
+}
+
+impl Pending {
+    // Record the outcome, warning rather than failing if the log cannot be written
+    pub fn finish(mut self, error: Option<String>) {
+        self.entry.error = error;
+        self.entry.duration_ms = self.started.elapsed().as_millis() as u64;
+        if let Err(e) = append(&self.entry) {
+            eprintln!("{}", tr!("warning", tr!("log-write-failed", e)));
+        }
+        let event = self.entry.event();
+        events::publish(&event);
+        // Re-applying the profile that is already active is no change
+        if self.entry.error.is_none() && self.entry.previous.as_ref() != Some(&self.entry.profile) {
+            effects::post_webhooks(&event);
+        }
+        if self.entry.error.is_none() && self.entry.persistent {
+            mqtt::publish_state(&self.entry.profile);
+        }
+    }
+}
+
+pub fn log_path() -> PathBuf {
+    state::state_dir().join("switches.log")
+}
+
+fn append(entry: &Entry) -> io::Result<()> {
+    let path = log_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+// Every entry in the log, oldest first. Lines that cannot be parsed are skipped.
+pub fn load() -> Vec<Entry> {
+    fs::read_to_string(log_path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+pub fn clear() -> io::Result<()> {
+    match fs::remove_file(log_path()) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// One line for `log show`
+pub fn describe(entry: &Entry) -> String {
+    let previous = entry.previous.as_deref().unwrap_or("-");
+    let kind = if entry.persistent {
+        String::new()
+    } else {
+        tr!("log-transient")
+    };
+    let result = match &entry.error {
+        None => tr!("log-ok"),
+        Some(error) => tr!("log-failed", error),
+    };
+    tr!(
+        "log-line",
+        format_time(entry.time),
+        previous,
+        entry.profile,
+        kind,
+        entry.trigger,
+        result,
+        entry.duration_ms
+    )
+}
+
+// `YYYY-MM-DD HH:MM:SS` in UTC for seconds since the Unix epoch
+pub fn format_time(time: u64) -> String {
+    let (days, seconds) = ((time / 86400) as i64, time % 86400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
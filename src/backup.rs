@@ -0,0 +1,99 @@
+// Copies of the Sway config as it was before each write, kept under
+// XDG_STATE_HOME so a change can be looked at again or undone. Each config
+// file gets its own directory; a copy is named after the second it was taken.
+
+use crate::audit;
+use crate::state;
+use crate::tr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// How many copies are kept per config file; the oldest go first
+const KEEP: usize = 20;
+
+pub struct Backup {
+    // Seconds since the Unix epoch, which is also the file name
+    pub time: u64,
+    pub path: PathBuf,
+}
+
+impl Backup {
+    pub fn lines(&self) -> io::Result<Vec<String>> {
+        Ok(fs::read_to_string(&self.path)?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    // The time in UTC, as `log show` prints it
+    pub fn describe(&self) -> String {
+        format!("{}  {} UTC", self.time, audit::format_time(self.time))
+    }
+}
+
+// Where the copies of one config file go, e.g. `home_me_.config_sway_config`
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    let name = config_path
+        .to_string_lossy()
+        .trim_start_matches('/')
+        .replace('/', "_");
+    state::state_dir().join("backups").join(name)
+}
+
+// Copy the config as it is now, before it is overwritten. A second write in the
+// same second keeps the first copy, which is the older state.
+pub fn keep(config_path: &Path) -> io::Result<()> {
+    let contents = match fs::read(config_path) {
+        Ok(contents) => contents,
+        // Nothing to keep for a config that does not exist yet
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let dir = backup_dir(config_path);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(state::now().to_string());
+    if !path.exists() {
+        fs::write(path, contents)?;
+    }
+    for old in list(config_path).iter().rev().skip(KEEP) {
+        fs::remove_file(&old.path)?;
+    }
+    Ok(())
+}
+
+// The copies of a config file, oldest first
+pub fn list(config_path: &Path) -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(backup_dir(config_path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let time = entry.file_name().to_str()?.parse().ok()?;
+            Some(Backup {
+                time,
+                path: entry.path(),
+            })
+        })
+        .collect();
+    backups.sort_by_key(|backup| backup.time);
+    backups
+}
+
+// The copy named by `timestamp`, either as listed or `latest`
+pub fn find(config_path: &Path, timestamp: &str) -> Result<Backup, String> {
+    let mut backups = list(config_path);
+    if timestamp == "latest" {
+        return backups
+            .pop()
+            .ok_or_else(|| tr!("no-backups", config_path.display()));
+    }
+    let time: u64 = timestamp
+        .parse()
+        .map_err(|_| tr!("not-a-timestamp", timestamp))?;
+    backups
+        .into_iter()
+        .find(|backup| backup.time == time)
+        .ok_or_else(|| tr!("no-backup-from", timestamp))
+}
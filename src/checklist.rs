@@ -0,0 +1,124 @@
+// A checklist of the connected outputs: tick the ones to have on, apply them
+// straight away, and optionally keep the result as a new profile
+
+use crate::cli::Args;
+use crate::prompt;
+use sway_display_switcher::config::{self, DisplayConfig, SwayConfig};
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::sway::{self, Output};
+use sway_display_switcher::{generate, migrate, output, state, tr};
+
+pub fn run(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    if connected.is_empty() {
+        return Err(Error::Failed(tr!("no-outputs")));
+    }
+
+    let mut checked: Vec<bool> = connected.iter().map(|output| output.active).collect();
+    loop {
+        println!();
+        for (index, output) in connected.iter().enumerate() {
+            let mark = if checked[index] { 'x' } else { ' ' };
+            println!(
+                "{}. [{}] {} ({})",
+                index + 1,
+                mark,
+                output.name,
+                output.identifier()
+            );
+        }
+        let answer = prompt::ask(&tr!("checklist-toggle"), "")?;
+        if answer.is_empty() {
+            break;
+        }
+        if answer.eq_ignore_ascii_case("q") {
+            println!("{}", tr!("exiting"));
+            return Ok(());
+        }
+        match toggles(&answer, connected.len()) {
+            Some(numbers) => {
+                for number in numbers {
+                    checked[number] = !checked[number];
+                }
+            }
+            None => println!("{}", tr!("number-range", connected.len())),
+        }
+    }
+
+    if !checked.contains(&true) && !args.force {
+        return Err(Error::Failed(tr!("checklist-blackout")));
+    }
+    let changed: Vec<(&Output, bool)> = connected
+        .iter()
+        .zip(checked.iter().copied())
+        .filter(|(output, on)| output.active != *on)
+        .collect();
+    if changed.is_empty() {
+        println!("{}", tr!("checklist-unchanged"));
+        return Ok(());
+    }
+    let commands: Vec<String> = changed
+        .iter()
+        .map(|(output, on)| {
+            format!(
+                "output {} {}",
+                output::quote(&output.name),
+                if *on { "enable" } else { "disable" }
+            )
+        })
+        .collect();
+    state::record_live("Outputs checklist", &commands);
+    for command in commands {
+        if !sway::run_command(&command) {
+            return Err(Error::Sway(tr!("sway-rejected", command)));
+        }
+    }
+    println!("{}", tr!("checklist-applied"));
+
+    if !prompt::confirm(&tr!("checklist-save"), false)? {
+        return Ok(());
+    }
+    let description = loop {
+        let name = prompt::ask(&tr!("profile-name"), "")?;
+        // Commas would break the header line
+        if name.is_empty() || name.contains(',') {
+            println!("{}", tr!("name-invalid"));
+        } else if config::find_config(display_configs, &name).is_some() {
+            println!("{}", tr!("profile-exists", name));
+        } else {
+            break name;
+        }
+    };
+    // The outputs as Sway arranged them, with the modes it picked for the
+    // ones that were just enabled
+    let now = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    let mut profile = DisplayConfig {
+        description: description.clone(),
+        status: "Disabled".to_string(),
+        outputs: generate::snapshot(&now),
+        metadata: Vec::new(),
+    };
+    migrate::add_identities(&mut profile, &now);
+    let mut updated = display_configs.to_vec();
+    updated.push(profile);
+    config.save(&updated)?;
+    println!("{}", tr!("saved-profile", description));
+    crate::switch_to_saved(args, &config.path, &description)
+}
+
+// The 0-based indices named by an answer such as `1 3` or `2,4`, or None when
+// one of them is not a number between 1 and `count`
+fn toggles(answer: &str, count: usize) -> Option<Vec<usize>> {
+    answer
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| match word.parse::<usize>() {
+            Ok(number) if number > 0 && number <= count => Some(number - 1),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,357 @@
+use std::env;
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::tr;
+
+// Command line arguments: an optional subcommand, its operands, and flags
+#[derive(Debug, Default)]
+pub struct Args {
+    pub command: Option<String>,
+    pub positional: Vec<String>,
+    pub transient: bool,
+    pub watch: bool,
+    pub observe: bool,
+    pub from_udev: bool,
+    pub follow: bool,
+    pub lines: Option<usize>,
+    pub since: Option<u64>,
+    pub json: bool,
+    pub format: Option<String>,
+    pub sort: Option<String>,
+    pub favorites: bool,
+    pub long: bool,
+    pub output: Option<String>,
+    pub verbose: bool,
+    pub socket: Option<String>,
+    pub seat: Option<String>,
+    pub insert: bool,
+    pub check: bool,
+    pub apply: bool,
+    pub only: Vec<String>,
+    pub overrides: Vec<String>,
+    pub persist: bool,
+    pub timeout: Option<u64>,
+    pub revert_after: Option<u64>,
+    pub seconds: Option<u64>,
+    pub default: Option<String>,
+    pub headless: bool,
+    pub end: bool,
+    pub save: Option<String>,
+    pub relative_to: Option<String>,
+    pub auto_scale: bool,
+    pub system: bool,
+    pub force: bool,
+    pub strict: bool,
+    pub tag: Option<String>,
+    pub host: Option<String>,
+    pub help: bool,
+}
+
+impl Args {
+    pub fn parse() -> error::Result<Args> {
+        let mut args = Args::default();
+        let mut iter = env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            // Accept both `--flag value` and `--flag=value`
+            let (arg, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| iter.next())
+                    .ok_or_else(|| Error::Usage(tr!("option-needs-value", arg)))
+            };
+            match arg.as_str() {
+                // Apply via IPC only, leaving the config file untouched
+                "--transient" => args.transient = true,
+                // Keep re-asserting the Enabled profile
+                "--watch" => args.watch = true,
+                // Watch, but only log what would be applied
+                "--observe" => args.observe = true,
+                // Started by the generated udev helper: switch to the best match
+                "--from-udev" => args.from_udev = true,
+                // Keep printing events as they happen
+                "--follow" => args.follow = true,
+                // Only the last or the recent logged events
+                "-n" | "--lines" => args.lines = Some(count(value()?)?),
+                "--since" => args.since = Some(seconds(value()?)?),
+                // Machine-readable output
+                "--json" => args.json = true,
+                "--format" => args.format = Some(value()?),
+                "--sort" => args.sort = Some(value()?),
+                "--favorites" => args.favorites = true,
+                // Per-output details in list
+                "-l" | "--long" => args.long = true,
+                // Only profiles with a line for this output in list
+                "--output" => args.output = Some(value()?),
+                // Explain how a result was reached
+                "-v" | "--verbose" => args.verbose = true,
+                // Which Sway instance to talk to, and whose state to use
+                "--socket" => args.socket = Some(value()?),
+                "--seat" => args.seat = Some(value()?),
+                // Write generated snippets into the Sway config
+                "--insert" => args.insert = true,
+                // Report what would change without writing
+                "--check" => args.check = true,
+                // Apply a composed profile, or save it under a name
+                "--apply" => args.apply = true,
+                "--save" => args.save = Some(value()?),
+                // Mirror the captured output onto a headless one
+                "--headless" => args.headless = true,
+                // Stop presenting and restore the outputs
+                "--end" => args.end = true,
+                // Apply just these outputs of the profile (comma-separated)
+                "--only" => args.only.extend(
+                    value()?
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(String::from),
+                ),
+                // Change one setting of the profile for this switch
+                "--set" => args.overrides.push(value()?),
+                // Write --set changes back into the profile
+                "--persist" => args.persist = true,
+                // Pick a profile by itself when the picker gets no answer
+                "--timeout" => args.timeout = Some(seconds(value()?)?),
+                // How long `test` keeps the profile
+                "--seconds" => args.seconds = Some(seconds(value()?)?),
+                // Switch back unless `confirm` is run within this many seconds
+                "--revert-after" => args.revert_after = Some(seconds(value()?)?),
+                "--default" => args.default = Some(value()?),
+                "--relative-to" => args.relative_to = Some(value()?),
+                // Use DPI-based scale suggestions without asking
+                "--auto-scale" => args.auto_scale = true,
+                // Manage /etc/sway/config even when the user has a config
+                "--system" => args.system = true,
+                // Apply a profile even when it turns off every connected output
+                "--force" => args.force = true,
+                // Refuse a managed section with lines that are no Sway command
+                "--strict" => args.strict = true,
+                // Only cycle among profiles with this tag
+                "--tag" => args.tag = Some(value()?),
+                // Manage the Sway session of another machine over SSH
+                "--host" => args.host = Some(value()?),
+                "-h" | "--help" => args.help = true,
+                // A lone `-` names standard input, as for `import json -`
+                flag if flag.starts_with('-') && flag != "-" => {
+                    print_usage();
+                    return Err(Error::Usage(tr!("unknown-option", flag)));
+                }
+                _ if args.command.is_none() => args.command = Some(arg),
+                _ => args.positional.push(arg),
+            }
+        }
+        Ok(args)
+    }
+}
+
+// The feature a command needs, when this build was made without it
+pub fn feature_for(command: &str) -> Option<&'static str> {
+    match command {
+        "wizard" | "add-output" | "outputs" if !cfg!(feature = "interactive") => {
+            Some("interactive")
+        }
+        "visual-edit" if !cfg!(feature = "dialogs") => Some("dialogs"),
+        "window" if !cfg!(feature = "gtk") => Some("gtk"),
+        _ => None,
+    }
+}
+
+// The error for something this build was made without
+pub fn left_out(what: &str, feature: &str) -> Error {
+    Error::Usage(tr!("feature-left-out", what, feature))
+}
+
+// The optional features this build was made without
+fn left_out_features() -> Vec<&'static str> {
+    [
+        ("interactive", cfg!(feature = "interactive")),
+        ("menu", cfg!(feature = "menu")),
+        ("dialogs", cfg!(feature = "dialogs")),
+        ("daemon", cfg!(feature = "daemon")),
+        ("dbus", cfg!(feature = "dbus")),
+        ("gtk", cfg!(feature = "gtk")),
+    ]
+    .into_iter()
+    .filter(|(_, built)| !built)
+    .map(|(feature, _)| feature)
+    .collect()
+}
+
+fn seconds(value: String) -> error::Result<u64> {
+    value
+        .parse()
+        .map_err(|_| Error::Usage(tr!("not-seconds", value)))
+}
+
+fn count(value: String) -> error::Result<usize> {
+    value
+        .parse()
+        .map_err(|_| Error::Usage(tr!("not-count", value)))
+}
+
+// Each command and option with the message id of its description. A command
+// without one shares the description of the command after it.
+const COMMANDS: &[(&str, &str)] = &[
+    ("(none)", "help-pick"),
+    ("switch <profile>", "help-switch"),
+    ("list", "help-list"),
+    ("show <profile>", "help-show"),
+    ("diff <a> <b>", "help-diff"),
+    ("lint", "help-lint"),
+    ("format", "help-format"),
+    ("compose <a> <b>...", "help-compose"),
+    ("plan <profile>", "help-plan"),
+    ("apply", "help-apply"),
+    ("export <profile>", "help-export"),
+    ("migrate descriptions", "help-migrate-descriptions"),
+    ("migrate identities", "help-migrate-identities"),
+    ("migrate adopt", "help-migrate-adopt"),
+    ("visual-edit [<editor>]", "help-visual-edit"),
+    ("import json <file>", ""),
+    (
+        "import nwg-displays|way-displays|shikane [<profile>]",
+        "help-import",
+    ),
+    ("position <output> <x>,<y>", ""),
+    (
+        "position <output> --relative-to <other> left|right|above|below",
+        "help-position",
+    ),
+    ("refresh <output> [<hz>]", "help-refresh"),
+    ("tearing [<output>] [on|off|toggle]", "help-tearing"),
+    ("power <output> on|off|toggle", "help-power"),
+    ("blank", "help-blank"),
+    ("lock", "help-lock"),
+    ("icc set <output> <path>", "help-icc-set"),
+    ("icc show", "help-icc-show"),
+    ("generate bindings", "help-generate-bindings"),
+    ("generate matrix", "help-generate-matrix"),
+    (
+        "generate completions bash|zsh|fish",
+        "help-generate-completions",
+    ),
+    ("names", "help-names"),
+    ("add-output [<output>]", "help-add-output"),
+    ("wizard", "help-wizard"),
+    ("window", "help-window"),
+    ("outputs", "help-outputs"),
+    ("sockets", "help-sockets"),
+    ("recover finish|discard", "help-recover"),
+    ("backups [list]", "help-backups-list"),
+    ("backups diff [<timestamp>]", "help-backups-diff"),
+    ("backups restore <timestamp>", "help-backups-restore"),
+    ("detect", "help-detect"),
+    ("test <profile>", "help-test"),
+    ("confirm", "help-confirm"),
+    ("next", "help-next"),
+    ("which", "help-which"),
+    ("get <field>", "help-get"),
+    ("capture <output>", "help-capture"),
+    ("capture stop", "help-capture-stop"),
+    ("present [<output>]", "help-present"),
+    ("log show|clear", "help-log"),
+    ("events", "help-events"),
+    ("restore", "help-restore"),
+];
+
+const OPTIONS: &[(&str, &str)] = &[
+    ("--transient", "help-transient"),
+    ("--watch", "help-watch"),
+    ("--observe", "help-observe"),
+    ("--follow", "help-follow"),
+    ("-n, --lines <n>", "help-lines"),
+    ("--since <secs>", "help-since"),
+    ("--from-udev", "help-from-udev"),
+    ("--json", "help-json"),
+    ("--format <fmt>", "help-format-option"),
+    ("--sort <key>", "help-sort"),
+    ("--favorites", "help-favorites"),
+    ("-l, --long", "help-long"),
+    ("--output <o>", "help-output"),
+    ("-v, --verbose", "help-verbose"),
+    ("--socket <path>", "help-socket"),
+    ("--seat <name>", "help-seat"),
+    ("--host <dest>", "help-host"),
+    ("--insert", "help-insert"),
+    ("--check", "help-check"),
+    ("--only <o>,<o>", "help-only"),
+    ("--set <o>.<key>=<value>", "help-set"),
+    ("--persist", "help-persist"),
+    ("--revert-after <secs>", "help-revert-after"),
+    ("--timeout <secs>", "help-timeout"),
+    ("--default <p>", "help-default"),
+    ("--headless", "help-headless"),
+    ("--end", "help-end"),
+    ("--apply", "help-apply-option"),
+    ("--save <name>", "help-save"),
+    ("--relative-to <o>", "help-relative-to"),
+    ("--tag <tag>", "help-tag"),
+    ("--auto-scale", "help-auto-scale"),
+    ("--system", "help-system"),
+    ("--force", "help-force"),
+    ("--strict", "help-strict"),
+    ("-h, --help", "help-help"),
+];
+
+// Descriptions start in this column and are wrapped so lines stay within 78
+// columns
+const USAGE_WIDTH: usize = 20;
+const DESCRIPTION_WIDTH: usize = 58;
+
+pub fn print_usage() {
+    println!("{}", tr!("help-usage"));
+    println!();
+    println!("{}", tr!("help-commands"));
+    print_entries(COMMANDS);
+    println!();
+    println!("{}", tr!("help-options"));
+    print_entries(OPTIONS);
+    let left_out = left_out_features();
+    if !left_out.is_empty() {
+        println!();
+        println!("{}", tr!("built-without", left_out.join(", ")));
+    }
+}
+
+// A usage that does not fit its column goes on a line of its own
+fn print_entries(entries: &[(&str, &str)]) {
+    for (usage, id) in entries {
+        let description = if id.is_empty() {
+            Vec::new()
+        } else {
+            wrap(&tr!(id), DESCRIPTION_WIDTH)
+        };
+        let mut lines = description.iter();
+        if usage.len() < USAGE_WIDTH - 2 {
+            match lines.next() {
+                Some(line) => println!("  {:<width$} {}", usage, line, width = USAGE_WIDTH - 3),
+                None => println!("  {}", usage),
+            }
+        } else {
+            println!("  {}", usage);
+        }
+        for line in lines {
+            println!("{:width$}{}", "", line, width = USAGE_WIDTH);
+        }
+    }
+}
+
+// Break `text` into lines of at most `width` characters at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
@@ -0,0 +1,1354 @@
+use crate::backup;
+use crate::diff::{self, DiffLine};
+use crate::error::{self, Error};
+use crate::output::{self, OutputLine};
+use crate::profile::Profile;
+use crate::remote;
+use crate::section::Section;
+use crate::sway::Output;
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+// In JSON a profile is written and read as a `profile::Profile`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Profile", from = "Profile")]
+pub struct DisplayConfig {
+    pub description: String,
+    pub outputs: Vec<String>,
+    pub status: String,
+    // Extra `, Key = Value` pairs from the header line, in their original order
+    pub metadata: Vec<(String, String)>,
+}
+
+impl DisplayConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.status.eq_ignore_ascii_case("Enabled")
+    }
+
+    // Pinned with `Favorite = yes` (or true/on/1) in the header
+    pub fn is_favorite(&self) -> bool {
+        self.metadata_flag("Favorite")
+    }
+
+    // A free-form `Notes = ...` remark about when or how to use the profile
+    pub fn notes(&self) -> Option<&str> {
+        self.metadata("Notes").filter(|notes| !notes.is_empty())
+    }
+
+    // Everything the profile sets for the output `target` (a connector or
+    // description), merged the way Sway merges output configs: `output *` lines
+    // apply to every output, and a subcommand overrides an earlier one of its
+    // kind. None when no line applies to the output.
+    pub fn settings_for(&self, target: &str, connected: &[Output]) -> Option<OutputLine> {
+        let live = connected
+            .iter()
+            .find(|o| o.name == target || o.identifier() == target);
+        let mut merged: Option<OutputLine> = None;
+        for line in self
+            .outputs
+            .iter()
+            .filter_map(|line| OutputLine::parse(line))
+        {
+            let applies = line.is_wildcard()
+                || line.target == target
+                || live.is_some_and(|o| o.matches(&line.target));
+            if !applies {
+                continue;
+            }
+            let settings = merged.get_or_insert_with(|| OutputLine {
+                target: target.to_string(),
+                args: Vec::new(),
+                attributes: Vec::new(),
+            });
+            for attribute in line.attributes {
+                if attribute.is_known() {
+                    let group = output::key_group(&attribute.key);
+                    settings
+                        .attributes
+                        .retain(|earlier| output::key_group(&earlier.key) != group);
+                }
+                settings.attributes.push(attribute);
+            }
+        }
+        if let Some(settings) = &mut merged {
+            settings.args = settings
+                .attributes
+                .iter()
+                .flat_map(|a| std::iter::once(&a.key).chain(&a.flags).chain(&a.values))
+                .cloned()
+                .collect();
+        }
+        merged
+    }
+
+    // Index of the output line configuring `name` (a connector or description),
+    // resolving connector names through the connected outputs
+    pub fn find_output_line(&self, name: &str, connected: &[Output]) -> Option<usize> {
+        let live = connected
+            .iter()
+            .find(|o| o.name == name || o.identifier() == name);
+        self.outputs.iter().position(|line| {
+            OutputLine::parse(line).is_some_and(|parsed| {
+                !parsed.is_wildcard()
+                    && (parsed.target == name || live.is_some_and(|o| o.matches(&parsed.target)))
+            })
+        })
+    }
+
+    // A copy keeping only the lines for the named outputs (connectors or
+    // descriptions, resolved like find_output_line). Wildcard lines are dropped
+    // as they would touch the other outputs too. Fails on a name with no line.
+    pub fn restricted_to(
+        &self,
+        names: &[String],
+        connected: &[Output],
+    ) -> Result<DisplayConfig, String> {
+        let mut keep = vec![false; self.outputs.len()];
+        for name in names {
+            let live = connected
+                .iter()
+                .find(|o| o.name == *name || o.identifier() == *name);
+            let mut found = false;
+            for (index, line) in self.outputs.iter().enumerate() {
+                let command = output::split_condition(line).map_or(line.as_str(), |(_, c)| c);
+                let matches = OutputLine::parse(command).is_some_and(|parsed| {
+                    !parsed.is_wildcard()
+                        && (parsed.target == *name
+                            || live.is_some_and(|o| o.matches(&parsed.target)))
+                });
+                if matches {
+                    keep[index] = true;
+                    found = true;
+                }
+            }
+            if !found {
+                return Err(tr!("no-line-for-output", self.description, name));
+            }
+        }
+        let mut restricted = self.clone();
+        let mut keep = keep.into_iter();
+        restricted.outputs.retain(|_| keep.next().unwrap_or(false));
+        Ok(restricted)
+    }
+
+    // A copy with `--set` overrides applied to the lines for their outputs,
+    // adding a line for an output the profile does not configure yet
+    pub fn with_overrides(
+        &self,
+        overrides: &[output::Override],
+        connected: &[Output],
+    ) -> Result<DisplayConfig, String> {
+        let mut overridden = self.clone();
+        for change in overrides {
+            let live = connected
+                .iter()
+                .find(|o| o.name == change.output || o.identifier() == change.output);
+            match overridden.find_output_line(&change.output, connected) {
+                Some(index) => {
+                    overridden.outputs[index] = change.apply(&overridden.outputs[index], live)?;
+                }
+                None => {
+                    let line = format!("output {}", output::quote(&change.output));
+                    overridden.outputs.push(change.apply(&line, live)?);
+                }
+            }
+        }
+        Ok(overridden)
+    }
+
+    // Whether any line is guarded by an `?if-present`/`?if-absent` condition
+    pub fn has_conditions(&self) -> bool {
+        self.outputs
+            .iter()
+            .any(|line| output::split_condition(line).is_some())
+    }
+
+    // The profile as it applies to the outputs connected now: outputs are
+    // re-identified (see `reidentified`), conditional lines whose condition
+    // holds keep just their command, and the other conditional lines and the
+    // `?identity` lines go
+    pub fn resolved(&self, connected: &[Output]) -> DisplayConfig {
+        let mut resolved = self.reidentified(connected);
+        resolved.outputs = resolved
+            .outputs
+            .iter()
+            .filter_map(|line| match output::split_condition(line) {
+                Some((condition, command)) => {
+                    condition.holds(connected).then(|| command.to_string())
+                }
+                None if output::parse_identity(line).is_some() || output::is_comment(line) => None,
+                None => Some(line.clone()),
+            })
+            .collect();
+        resolved
+    }
+
+    // The connector and description of each `?identity` line
+    pub fn identities(&self) -> Vec<(String, String)> {
+        self.outputs
+            .iter()
+            .filter_map(|line| output::parse_identity(line))
+            .collect()
+    }
+
+    // The profile with each output recorded in an `?identity` line addressed
+    // by whichever of its connector and description still matches: lines for a
+    // connector move to the one its description is on now (DP-1 coming back
+    // as DP-3), and lines for a description that is gone fall back to the
+    // connector. The `?identity` lines follow their outputs' new connectors.
+    pub fn reidentified(&self, connected: &[Output]) -> DisplayConfig {
+        // Targets to replace, and the connector each description is on now
+        let mut moves: Vec<(String, String)> = Vec::new();
+        let mut renamed: Vec<(String, String)> = Vec::new();
+        for (connector, description) in self.identities() {
+            match connected.iter().find(|o| o.identifier() == description) {
+                Some(now) if now.name != connector => {
+                    moves.push((connector, now.name.clone()));
+                    renamed.push((description, now.name.clone()));
+                }
+                None if connected.iter().any(|o| o.name == connector) => {
+                    moves.push((description, connector));
+                }
+                _ => {}
+            }
+        }
+        let mut updated = self.clone();
+        if moves.is_empty() {
+            return updated;
+        }
+        for line in updated.outputs.iter_mut() {
+            if let Some((_, description)) = output::parse_identity(line) {
+                if let Some((_, now)) = renamed.iter().find(|(d, _)| *d == description) {
+                    *line = output::identity_line(now, &description);
+                }
+                continue;
+            }
+            let Some(parsed) = OutputLine::parse(line) else {
+                continue;
+            };
+            let new_target = moves
+                .iter()
+                .find(|(from, _)| *from == parsed.target)
+                .and_then(|(_, to)| output::replace_target(line, to));
+            if let Some(rewritten) = new_target {
+                *line = rewritten;
+            }
+        }
+        updated
+    }
+
+    // Short names from `Alias = dock desk` in the header
+    pub fn aliases(&self) -> Vec<&str> {
+        self.metadata_list("Alias")
+    }
+
+    // Labels from `Tags = home, office` in the header
+    pub fn tags(&self) -> Vec<&str> {
+        self.metadata_list("Tags")
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    // Look up a header metadata value by key (case-insensitive)
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    // A metadata value read as a list, such as `Tags = work, travel` or
+    // `Alias = dock desk`: split on commas and whitespace
+    pub fn metadata_list(&self, key: &str) -> Vec<&str> {
+        self.metadata(key)
+            .map(|value| {
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // A metadata value read as a yes/no switch (yes, true, on or 1)
+    pub fn metadata_flag(&self, key: &str) -> bool {
+        self.metadata(key).is_some_and(|value| {
+            ["yes", "true", "on", "1"]
+                .iter()
+                .any(|truthy| value.eq_ignore_ascii_case(truthy))
+        })
+    }
+
+    // Set a metadata value, replacing an existing key in place (keeping its
+    // position) or appending a new one
+    pub fn set_metadata(&mut self, key: &str, value: &str) {
+        match self
+            .metadata
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.metadata.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    // Drop a metadata key, if present
+    pub fn remove_metadata(&mut self, key: &str) {
+        self.metadata.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+    }
+}
+
+// Comment written right after the start marker, holding the checksum of the
+// section as it was last generated so hand edits can be recognized
+pub const CHECKSUM_PREFIX: &str = "# sway-display-switcher: format 1, checksum ";
+
+// The config currently marked Enabled, if any
+pub fn enabled_config(configs: &[DisplayConfig]) -> Option<&DisplayConfig> {
+    configs.iter().find(|c| c.is_enabled())
+}
+
+// The config Sway reads for users without one of their own, shared by
+// everyone on the machine (kiosks, lab machines)
+pub const SYSTEM_PATH: &str = "/etc/sway/config";
+
+// The user's config, or the system one when the user has none, as Sway does
+pub fn default_path() -> error::Result<PathBuf> {
+    let user = expand_home("~/.config/sway/config");
+    if !user.exists() && Path::new(SYSTEM_PATH).exists() {
+        return Ok(PathBuf::from(SYSTEM_PATH));
+    }
+    Ok(user)
+}
+
+// `path` with a leading `~` replaced by the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+// The sway config file together with the location of the managed display section
+pub struct SwayConfig {
+    pub path: PathBuf,
+    pub lines: Vec<String>,
+    pub display_start: usize,
+    pub display_end: usize,
+    // Whether bytes that are not UTF-8 were replaced in reading, so the lines
+    // do not give the file back and it must not be rewritten from them
+    pub lossy: bool,
+}
+
+impl SwayConfig {
+    pub fn load(path: &Path) -> error::Result<SwayConfig> {
+        // Read all lines from the config file. Invalid UTF-8 is replaced rather
+        // than cutting the file short, and CRLF endings are stripped (the
+        // writer puts them back, see `write_config`).
+        let bytes = fs::read(path).map_err(|e| Error::file("read", path, e))?;
+        let text = String::from_utf8_lossy(&bytes);
+        let lossy = matches!(text, Cow::Owned(_));
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+        // Identify the 'Display Start' and 'Display End' indices
+        let display_start = lines
+            .iter()
+            .position(|line| line.contains("Display Start"))
+            .ok_or_else(|| Error::Config {
+                path: path.to_path_buf(),
+                message: tr!("marker-missing", "Display Start"),
+            })?;
+        let display_end = lines
+            .iter()
+            .position(|line| line.contains("Display End"))
+            .ok_or_else(|| Error::Config {
+                path: path.to_path_buf(),
+                message: tr!("marker-missing", "Display End"),
+            })?;
+        // Everything below slices the lines from one marker to the other
+        if display_end < display_start {
+            return Err(Error::Config {
+                path: path.to_path_buf(),
+                message: tr!("markers-order"),
+            });
+        }
+
+        Ok(SwayConfig {
+            path: path.to_path_buf(),
+            lines,
+            display_start,
+            display_end,
+            lossy,
+        })
+    }
+
+    // Refuse to rewrite a file whose lines are not what it holds, which would
+    // change bytes outside the managed section
+    pub fn check_rewritable(&self) -> error::Result<()> {
+        if self.lossy {
+            return Err(Error::Config {
+                path: self.path.clone(),
+                message: tr!("not-utf8"),
+            });
+        }
+        Ok(())
+    }
+
+    // The lines between the markers, without the checksum comment
+    pub fn section_lines(&self) -> Vec<String> {
+        self.lines[self.display_start + 1..self.display_end.max(self.display_start + 1)]
+            .iter()
+            .filter(|line| !line.starts_with(CHECKSUM_PREFIX))
+            .cloned()
+            .collect()
+    }
+
+    // The checksum recorded when the section was last written, if any
+    fn stored_checksum(&self) -> Option<u64> {
+        self.lines[self.display_start..self.display_end]
+            .iter()
+            .find_map(|line| line.strip_prefix(CHECKSUM_PREFIX))
+            .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())
+    }
+
+    // Whether the section was changed by hand since it was last written
+    pub fn hand_edited(&self) -> bool {
+        self.stored_checksum()
+            .is_some_and(|stored| stored != checksum(&self.section_lines()))
+    }
+
+    // Lines of the section that rewriting it would change even with every
+    // profile left as it is, such as an output line commented out by hand in
+    // the Enabled profile
+    pub fn lossy_lines(&self) -> Vec<String> {
+        let section = self.section_lines();
+        let regenerated = Section::parse(&section).render(&self.display_configs());
+        diff::diff_lines(&section, &regenerated)
+            .into_iter()
+            .filter_map(|line| match line {
+                DiffLine::Removed(text) => Some(text.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // The lines of the managed section that parse_configs cannot take as they
+    // are: text before the first profile header, and text that is not a Sway
+    // command, which it keeps as a comment. Commented-out remarks are fine.
+    pub fn malformed_lines(&self) -> Vec<Malformed> {
+        let mut malformed = Vec::new();
+        let mut in_profile = false;
+        for (index, line) in self.lines[self.display_start..self.display_end]
+            .iter()
+            .enumerate()
+            .skip(1)
+        {
+            if line.starts_with(CHECKSUM_PREFIX) {
+                continue;
+            }
+            if parse_header(line).is_some() {
+                in_profile = true;
+                continue;
+            }
+            let text = line.trim_start().trim_start_matches('#').trim();
+            if text.is_empty() {
+                continue;
+            }
+            // Behind comment marks, prose is just a comment; only text meant
+            // as a command is worth a warning
+            let reason = if line.trim_start().starts_with('#') && !looks_like_directive(text) {
+                None
+            } else if in_profile {
+                line_problem(text)
+            } else {
+                Some(tr!("outside-profile"))
+            };
+            if let Some(reason) = reason {
+                malformed.push(Malformed {
+                    number: self.display_start + index + 1,
+                    line: line.clone(),
+                    reason,
+                    in_profile,
+                });
+            }
+        }
+        malformed
+    }
+
+    // Parse the display section into DisplayConfig structs
+    pub fn display_configs(&self) -> Vec<DisplayConfig> {
+        parse_configs(&self.lines[self.display_start..self.display_end])
+    }
+
+    // Build the full config with the display section replaced by the given
+    // configs. Profiles that did not change keep their lines as they were.
+    pub fn with_display_configs(&self, configs: &[DisplayConfig]) -> Vec<String> {
+        self.with_section(self.managed_section(configs))
+    }
+
+    // Build the full config with the display section rendered from the given
+    // configs in the canonical style, as `format` writes it
+    pub fn with_formatted_section(&self, configs: &[DisplayConfig]) -> Vec<String> {
+        let section = Section::parse(&self.section_lines()).render_canonical(configs);
+        self.with_section(with_checksum(section))
+    }
+
+    // The display section lines for the given configs preceded by their
+    // checksum comment, keeping the lines of unchanged profiles
+    pub fn managed_section(&self, configs: &[DisplayConfig]) -> Vec<String> {
+        with_checksum(Section::parse(&self.section_lines()).render(configs))
+    }
+
+    fn with_section(&self, section: Vec<String>) -> Vec<String> {
+        let mut new_lines = Vec::new();
+
+        // Add lines before the display section
+        new_lines.extend_from_slice(&self.lines[..=self.display_start]);
+
+        // Add the new display section
+        new_lines.extend(section);
+
+        // Add lines after the display section
+        if self.display_end < self.lines.len() {
+            new_lines.extend_from_slice(&self.lines[self.display_end..]);
+        }
+
+        new_lines
+    }
+
+    // Write the config with the display section replaced by the given configs.
+    // When the file changed since it was loaded (an editor or a dotfile sync
+    // wrote it in between), the changes made to the loaded profiles are made to
+    // the new contents instead of overwriting them.
+    pub fn save(&self, configs: &[DisplayConfig]) -> error::Result<()> {
+        let current = SwayConfig::load(&self.path)?;
+        current.check_rewritable()?;
+        let new_lines = if current.lines == self.lines {
+            self.with_display_configs(configs)
+        } else {
+            let merged =
+                merge_configs(&self.display_configs(), configs, &current.display_configs())
+                    .map_err(|profile| Error::Config {
+                        path: self.path.clone(),
+                        message: tr!("merge-conflict", profile),
+                    })?;
+            eprintln!("{}", tr!("merged-note", self.path.display()));
+            current.with_display_configs(&merged)
+        };
+        write_config(&self.path, &new_lines).map_err(|e| Error::file("write", &self.path, e))
+    }
+
+    // Write `lines` as the whole config, refusing when the file changed since
+    // it was loaded, as they were made from what it held then
+    pub fn write(&self, lines: &[String]) -> error::Result<()> {
+        self.check_rewritable()?;
+        let current = fs::read(&self.path).map_err(|e| Error::file("read", &self.path, e))?;
+        let unchanged = String::from_utf8_lossy(&current)
+            .lines()
+            .eq(self.lines.iter().map(String::as_str));
+        if !unchanged {
+            return Err(Error::Config {
+                path: self.path.clone(),
+                message: tr!("changed-since-read"),
+            });
+        }
+        write_config(&self.path, lines).map_err(|e| Error::file("write", &self.path, e))
+    }
+}
+
+// Make the changes that turned `base` into `ours` to `theirs`, a newer version
+// of `base` written by someone else. Profiles are matched by description; for
+// each one the status, the metadata and the lines `ours` changed are taken
+// from it, everything else from `theirs`. Profiles `ours` added are appended,
+// and those it removed are left out. Err names a profile both changed in the
+// same way differently, or that `ours` changed and `theirs` removed.
+pub fn merge_configs(
+    base: &[DisplayConfig],
+    ours: &[DisplayConfig],
+    theirs: &[DisplayConfig],
+) -> Result<Vec<DisplayConfig>, String> {
+    let find = |configs: &[DisplayConfig], description: &str| {
+        configs
+            .iter()
+            .find(|c| c.description == description)
+            .cloned()
+    };
+    // Take what `ours` changed, unless `theirs` changed it otherwise
+    fn pick<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> Option<T> {
+        if ours == base {
+            Some(theirs.clone())
+        } else if theirs == base || theirs == ours {
+            Some(ours.clone())
+        } else {
+            None
+        }
+    }
+
+    let mut merged = Vec::new();
+    for their in theirs {
+        let Some(base_config) = find(base, &their.description) else {
+            // Added by `theirs`
+            merged.push(their.clone());
+            continue;
+        };
+        let Some(our) = find(ours, &their.description) else {
+            // Removed by `ours`
+            continue;
+        };
+        let conflict = || their.description.clone();
+        merged.push(DisplayConfig {
+            description: their.description.clone(),
+            status: pick(&base_config.status, &our.status, &their.status).ok_or_else(conflict)?,
+            metadata: pick(&base_config.metadata, &our.metadata, &their.metadata)
+                .ok_or_else(conflict)?,
+            outputs: pick(&base_config.outputs, &our.outputs, &their.outputs)
+                .ok_or_else(conflict)?,
+        });
+    }
+    for our in ours {
+        match (find(base, &our.description), find(theirs, &our.description)) {
+            // Changed by `ours` but removed by `theirs`
+            (Some(base_config), None) if base_config != *our => {
+                return Err(our.description.clone())
+            }
+            // Added by `ours`, unless `theirs` added the same
+            (None, None) => merged.push(our.clone()),
+            (None, Some(their)) if their != *our => return Err(our.description.clone()),
+            _ => {}
+        }
+    }
+    // A profile `ours` switched to stays the only Enabled one
+    let switched_to: Vec<&DisplayConfig> = ours
+        .iter()
+        .filter(|our| {
+            our.is_enabled()
+                && find(base, &our.description).is_none_or(|base_config| !base_config.is_enabled())
+        })
+        .collect();
+    if let [selected] = switched_to.as_slice() {
+        for config in merged.iter_mut() {
+            if config.description != selected.description && config.is_enabled() {
+                config.status = "Disabled".to_string();
+            }
+        }
+    }
+    Ok(merged)
+}
+
+// Parse the display section into DisplayConfig structs
+pub fn parse_configs<'a, I>(lines: I) -> Vec<DisplayConfig>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    Section::parse(lines).configs()
+}
+
+// The entry a line below a profile header adds to the profile's outputs: the
+// line without comment marks and surrounding whitespace, or None for a blank
+// one. Text that is no Sway command stays a comment, so enabling the profile
+// does not hand it to Sway.
+pub fn profile_line(line: &str) -> Option<String> {
+    let trimmed_line = line.trim_start().trim_start_matches('#').trim();
+    if trimmed_line.is_empty() {
+        None
+    } else if line_problem(trimmed_line).is_some() {
+        Some(format!("# {}", trimmed_line))
+    } else {
+        Some(trimmed_line.to_string())
+    }
+}
+
+// A line of the managed section that is not part of any profile or not a
+// command Sway knows, with its line number in the file (counting from 1)
+#[derive(Debug, Clone)]
+pub struct Malformed {
+    pub number: usize,
+    pub line: String,
+    pub reason: String,
+    // Inside a profile the line is kept as a comment; before the first header
+    // it is kept where it is, belonging to no profile
+    pub in_profile: bool,
+}
+
+// Commands Sway accepts in its config file, to tell profile lines from stray
+// text; `client.*` colors and `[criteria]` commands are recognized separately
+const SWAY_COMMANDS: &[&str] = &[
+    "assign",
+    "bar",
+    "bindcode",
+    "bindgesture",
+    "bindswitch",
+    "bindsym",
+    "border",
+    "create_output",
+    "default_border",
+    "default_floating_border",
+    "default_orientation",
+    "exec",
+    "exec_always",
+    "exit",
+    "floating",
+    "floating_maximum_size",
+    "floating_minimum_size",
+    "floating_modifier",
+    "focus",
+    "focus_follows_mouse",
+    "focus_on_window_activation",
+    "focus_wrapping",
+    "font",
+    "for_window",
+    "force_display_urgency_hint",
+    "fullscreen",
+    "gaps",
+    "hide_edge_borders",
+    "include",
+    "inhibit_idle",
+    "input",
+    "kill",
+    "layout",
+    "mark",
+    "max_render_time",
+    "mode",
+    "mouse_warping",
+    "move",
+    "no_focus",
+    "nop",
+    "opacity",
+    "output",
+    "popup_during_fullscreen",
+    "primary_selection",
+    "reload",
+    "rename",
+    "resize",
+    "scratchpad",
+    "seat",
+    "set",
+    "shortcuts_inhibitor",
+    "show_marks",
+    "smart_borders",
+    "smart_gaps",
+    "split",
+    "splith",
+    "splitt",
+    "splitv",
+    "sticky",
+    "swap",
+    "swaybg_command",
+    "swaynag_command",
+    "tiling_drag",
+    "tiling_drag_threshold",
+    "title_align",
+    "title_format",
+    "titlebar_border_thickness",
+    "titlebar_padding",
+    "unbindcode",
+    "unbindgesture",
+    "unbindswitch",
+    "unbindsym",
+    "unmark",
+    "urgent",
+    "workspace",
+    "workspace_auto_back_and_forth",
+    "workspace_layout",
+    "xwayland",
+];
+
+// Why a profile line (without its comment marks) is not one the switcher can
+// keep as a command, or None when it is
+fn line_problem(line: &str) -> Option<String> {
+    if line.starts_with('?') {
+        if output::parse_identity(line).is_some() {
+            return None;
+        }
+        return match output::split_condition(line) {
+            Some((_, command)) => line_problem(command),
+            None => Some(tr!("invalid-condition")),
+        };
+    }
+    let first = line.split_whitespace().next()?;
+    if SWAY_COMMANDS.contains(&first) {
+        if first == "output" && OutputLine::parse(line).is_none() {
+            return Some(tr!("missing-output-name"));
+        }
+        return None;
+    }
+    if first.starts_with('[') || first.starts_with("client.") || first.starts_with('#') {
+        return None;
+    }
+    if !parse_pairs(line).is_empty() {
+        return Some(tr!("header-without-description"));
+    }
+    Some(tr!("not-sway-command", first))
+}
+
+// Whether commented-out text was meant as a profile line rather than a remark:
+// an `output` line, a `?` line or `Key = Value` pairs
+fn looks_like_directive(text: &str) -> bool {
+    text.starts_with('?')
+        || text.split_whitespace().next() == Some("output")
+        || !parse_pairs(text).is_empty()
+}
+
+// Parse a profile header: a comment holding a comma-separated `Key = Value`
+// list whose first key is `Description`. Any number of `#`, tabs and extra
+// spaces are accepted. `Status` may appear anywhere and defaults to Disabled;
+// every other pair is kept as metadata, unknown keys included.
+pub fn parse_header(line: &str) -> Option<DisplayConfig> {
+    let text = line.trim_start().strip_prefix('#')?.trim_start_matches('#');
+    let mut pairs = parse_pairs(text);
+    if pairs.is_empty() || !pairs[0].0.eq_ignore_ascii_case("Description") {
+        return None;
+    }
+    let (_, description) = pairs.remove(0);
+    let status = pairs
+        .iter()
+        .position(|(key, _)| key.eq_ignore_ascii_case("Status"))
+        .map(|index| pairs.remove(index).1)
+        .unwrap_or_else(|| "Disabled".to_string());
+
+    Some(DisplayConfig {
+        description,
+        status,
+        outputs: Vec::new(),
+        metadata: pairs,
+    })
+}
+
+// Split `Key = Value, Key = Value` into pairs. A comma only starts a new pair
+// when a `Key =` follows it, so values such as `Desk, left` keep their commas.
+fn parse_pairs(text: &str) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for piece in text.split(',') {
+        match piece.split_once('=').filter(|(key, _)| is_key(key.trim())) {
+            Some((key, value)) => pairs.push((key.trim().to_string(), value.to_string())),
+            None => match pairs.last_mut() {
+                Some((_, value)) => {
+                    value.push(',');
+                    value.push_str(piece);
+                }
+                None => return Vec::new(),
+            },
+        }
+    }
+    for (_, value) in pairs.iter_mut() {
+        *value = value.trim().to_string();
+    }
+    pairs
+}
+
+// Whether a word can be a header key: a letter followed by letters, digits,
+// `-` or `_`
+fn is_key(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Set the selected config to Enabled and every other config to Disabled
+pub fn activate(configs: &[DisplayConfig], selected_index: usize) -> Vec<DisplayConfig> {
+    let mut updated_configs = configs.to_vec();
+    for (i, config) in updated_configs.iter_mut().enumerate() {
+        if i == selected_index {
+            config.status = "Enabled".to_string();
+        } else {
+            config.status = "Disabled".to_string();
+        }
+    }
+    updated_configs
+}
+
+// Merge the output lines of several profiles into one. The lines a later
+// profile has for an output replace every earlier line for that output; lines
+// that are not output commands are kept as they are. The result is named after
+// its parts and Disabled.
+pub fn compose(parts: &[&DisplayConfig]) -> DisplayConfig {
+    let mut outputs: Vec<String> = Vec::new();
+    for part in parts {
+        let targets: Vec<String> = part
+            .outputs
+            .iter()
+            .filter_map(|line| OutputLine::parse(line))
+            .map(|line| line.target)
+            .collect();
+        outputs.retain(|line| {
+            OutputLine::parse(line).is_none_or(|parsed| !targets.contains(&parsed.target))
+        });
+        outputs.extend(part.outputs.iter().cloned());
+    }
+    DisplayConfig {
+        description: parts
+            .iter()
+            .map(|part| part.description.as_str())
+            .collect::<Vec<_>>()
+            .join(" + "),
+        outputs,
+        status: "Disabled".to_string(),
+        metadata: Vec::new(),
+    }
+}
+
+// The display section lines preceded by their checksum comment
+fn with_checksum(section: Vec<String>) -> Vec<String> {
+    let mut lines = vec![format!("{}{:016x}", CHECKSUM_PREFIX, checksum(&section))];
+    lines.extend(section);
+    lines
+}
+
+// Reconstruct the display section lines from the configs
+pub fn render_section(configs: &[DisplayConfig]) -> Vec<String> {
+    let mut section = Vec::new();
+
+    for (index, config) in configs.iter().enumerate() {
+        // One blank line between profiles
+        if index > 0 {
+            section.push(String::new());
+        }
+        section.push(render_header(config));
+        section.extend(render_outputs(config));
+    }
+
+    section
+}
+
+// The description line of a profile, with its status and metadata
+pub fn render_header(config: &DisplayConfig) -> String {
+    let mut header = format!(
+        "# Description = {}, Status = {}",
+        config.description, config.status
+    );
+    for (key, value) in &config.metadata {
+        header.push_str(&format!(", {} = {}", key, value));
+    }
+    header
+}
+
+// The output lines of a profile, commented or uncommented based on status.
+// Conditional and `?identity` lines are always commented, as Sway does not know
+// them, and lines kept as comments already are.
+pub fn render_outputs(config: &DisplayConfig) -> Vec<String> {
+    config
+        .outputs
+        .iter()
+        .map(|output_line| {
+            let conditional = output::split_condition(output_line).is_some()
+                || output::parse_identity(output_line).is_some();
+            if config.is_enabled() && !conditional {
+                output_line.clone() // Uncommented
+            } else if output_line.starts_with("# ") {
+                // Ensure only single '#' and space
+                output_line.clone()
+            } else if output_line.starts_with('#') {
+                format!("# {}", output_line.trim_start_matches('#').trim_start())
+            } else {
+                format!("# {}", output_line)
+            }
+        })
+        .collect()
+}
+
+// Find a config by its 1-based number, its description or one of its aliases
+// (case-insensitive). A description wins over another profile's alias.
+pub fn find_config(configs: &[DisplayConfig], query: &str) -> Option<usize> {
+    if let Ok(number) = query.parse::<usize>() {
+        if number > 0 && number <= configs.len() {
+            return Some(number - 1);
+        }
+    }
+    configs
+        .iter()
+        .position(|c| c.description.eq_ignore_ascii_case(query))
+        .or_else(|| {
+            configs.iter().position(|c| {
+                c.aliases()
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(query))
+            })
+        })
+}
+
+// The profile after `current` in file order among those carrying `tag` (every
+// profile without one), wrapping around at the end; None when none qualifies
+pub fn next_config(
+    configs: &[DisplayConfig],
+    current: Option<usize>,
+    tag: Option<&str>,
+) -> Option<usize> {
+    let start = current.map_or(0, |index| index + 1);
+    (0..configs.len())
+        .map(|offset| (start + offset) % configs.len())
+        .find(|&index| tag.is_none_or(|tag| configs[index].has_tag(tag)))
+}
+
+// Describe aliases that clash with another profile's description or alias, or
+// that look like a list number, so they would not select their own profile
+pub fn alias_conflicts(configs: &[DisplayConfig]) -> Vec<String> {
+    let mut conflicts = Vec::new();
+    for (index, config) in configs.iter().enumerate() {
+        for alias in config.aliases() {
+            if alias.parse::<usize>().is_ok() {
+                conflicts.push(tr!("alias-is-number", alias, config.description));
+                continue;
+            }
+            for (other_index, other) in configs.iter().enumerate() {
+                if other_index == index {
+                    continue;
+                }
+                if other.description.eq_ignore_ascii_case(alias) {
+                    conflicts.push(tr!("alias-is-profile", alias, config.description));
+                } else if other_index < index
+                    && other
+                        .aliases()
+                        .iter()
+                        .any(|a| a.eq_ignore_ascii_case(alias))
+                {
+                    conflicts.push(tr!(
+                        "alias-shared",
+                        alias,
+                        other.description,
+                        config.description
+                    ));
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+// Variables defined with `set $name value`, in definition order
+pub fn variables(lines: &[String]) -> Vec<(String, String)> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("set ")?;
+            let (name, value) = rest.trim_start().split_once(char::is_whitespace)?;
+            name.starts_with('$')
+                .then(|| (name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+// Replace the lines between two marker lines, appending a new marked block at
+// the end of the file when the markers are not present yet
+pub fn replace_block(lines: &[String], start: &str, end: &str, content: &[String]) -> Vec<String> {
+    let start_index = lines.iter().position(|line| line.trim() == start);
+    let end_index = lines.iter().position(|line| line.trim() == end);
+    let mut new_lines = Vec::new();
+    match (start_index, end_index) {
+        (Some(start_index), Some(end_index)) if start_index < end_index => {
+            new_lines.extend_from_slice(&lines[..=start_index]);
+            new_lines.extend_from_slice(content);
+            new_lines.extend_from_slice(&lines[end_index..]);
+        }
+        _ => {
+            new_lines.extend_from_slice(lines);
+            new_lines.push(String::new());
+            new_lines.push(start.to_string());
+            new_lines.extend_from_slice(content);
+            new_lines.push(end.to_string());
+        }
+    }
+    new_lines
+}
+
+// A stable 64-bit FNV-1a hash of the lines, for change detection that must
+// survive upgrades of the program
+pub fn checksum(lines: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for line in lines {
+        for byte in line.bytes().chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+// `<config>` with a suffix appended, for the files kept next to it
+fn sibling(config_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = config_path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn temp_path(config_path: &Path) -> PathBuf {
+    sibling(config_path, "_temp")
+}
+
+// Records the checksum of a write in progress until it is complete
+fn journal_path(config_path: &Path) -> PathBuf {
+    sibling(config_path, "_journal")
+}
+
+// Write all lines to a temporary file next to the config, then rename it into
+// place, keeping CRLF line endings and a missing final newline if the current
+// file has them. A journal holding the checksum of the new contents is written
+// and synced first and removed last, so an interrupted write can be told apart
+// from a finished one on the next start (see `interrupted_write`). When the
+// system config is not writable, the write is done as root instead, and the
+// local copy of a remote host's config is sent back to the host.
+pub fn write_config(config_path: &Path, lines: &[String]) -> io::Result<()> {
+    // A failed copy is no reason to leave the config as it is
+    if let Err(e) = backup::keep(config_path) {
+        eprintln!(
+            "{}",
+            tr!("warning", tr!("backup-failed", config_path.display(), e))
+        );
+    }
+    match write_directly(config_path, lines) {
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied && is_system(config_path) => {
+            write_privileged(config_path, lines)
+        }
+        Ok(()) if remote::is_mirror(config_path) => remote::upload(config_path),
+        result => result,
+    }
+}
+
+fn write_directly(config_path: &Path, lines: &[String]) -> io::Result<()> {
+    let temp_path = temp_path(config_path);
+    let journal_path = journal_path(config_path);
+    let (ending, final_newline) = line_endings(config_path);
+
+    let mut journal = File::create(&journal_path)?;
+    writeln!(journal, "checksum = {}", checksum(lines))?;
+    journal.sync_all()?;
+
+    let temp_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&temp_path)?;
+    // The replacement keeps the permissions of the file it replaces
+    if let Ok(metadata) = fs::metadata(config_path) {
+        temp_file.set_permissions(metadata.permissions())?;
+    }
+    let mut writer = BufWriter::new(temp_file);
+
+    for (index, line) in lines.iter().enumerate() {
+        write!(writer, "{}", line)?;
+        if final_newline || index + 1 < lines.len() {
+            write!(writer, "{}", ending)?;
+        }
+    }
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+
+    // Rename the temporary file to replace the old configuration, and make
+    // the rename itself durable
+    fs::rename(&temp_path, config_path)?;
+    if let Some(parent) = config_path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    fs::remove_file(&journal_path)
+}
+
+fn is_system(config_path: &Path) -> bool {
+    config_path == Path::new(SYSTEM_PATH)
+}
+
+// Hand the write to the `write-config` helper running as root: through pkexec,
+// or sudo when polkit is missing or has no agent to ask with (pkexec exits
+// with 127). The new contents are staged in a fresh file in the temp directory.
+fn write_privileged(config_path: &Path, lines: &[String]) -> io::Result<()> {
+    let staged = env::temp_dir().join(format!("sway-display-switcher-{}", process::id()));
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&staged)?;
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    drop(file);
+
+    let program = env::current_exe()?;
+    let run = |tool: &str| {
+        Command::new(tool)
+            .arg(&program)
+            .arg("write-config")
+            .arg(&staged)
+            .arg(config_path)
+            .status()
+    };
+    let status = match run("pkexec") {
+        Ok(status) if status.code() != Some(127) => Ok(status),
+        _ => run("sudo"),
+    };
+    let _ = fs::remove_file(&staged);
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            tr!("root-write-failed", config_path.display()),
+        )),
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            tr!("no-privilege-tool", config_path.display()),
+        )),
+    }
+}
+
+// The privileged half of `write_privileged`: move the staged contents into
+// place. Nothing but the system config is accepted as the target, so the
+// helper cannot be used to write other files as root.
+pub fn write_staged(staged: &Path, config_path: &Path) -> io::Result<()> {
+    if !is_system(config_path) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            tr!("system-path-only", SYSTEM_PATH),
+        ));
+    }
+    let lines: Vec<String> = fs::read_to_string(staged)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    write_directly(config_path, &lines)
+}
+
+// The line ending the file uses, CRLF when its first line ends in one, and
+// whether its last line has one too; a new or empty file gets both in full
+fn line_endings(path: &Path) -> (&'static str, bool) {
+    let contents = fs::read(path).unwrap_or_default();
+    let first_line = contents.split_inclusive(|&byte| byte == b'\n').next();
+    let ending = if first_line.is_some_and(|line| line.ends_with(b"\r\n")) {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    (ending, contents.last().is_none_or(|&byte| byte == b'\n'))
+}
+
+// What an earlier run left behind when it stopped in the middle of write_config
+#[derive(Debug, Clone, PartialEq)]
+pub enum Interrupted {
+    // The new config was fully written to the temp file but not moved into place
+    Complete(PathBuf),
+    // The temp file is partial; the config itself was not touched
+    Incomplete(PathBuf),
+    // The config was replaced, only the journal was not cleaned up
+    Finished,
+}
+
+// Detect a write to `config_path` that did not run to completion
+pub fn interrupted_write(config_path: &Path) -> Option<Interrupted> {
+    let temp_path = temp_path(config_path);
+    let journal = fs::read_to_string(journal_path(config_path)).ok();
+    let expected = journal.as_deref().and_then(|contents| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix("checksum = "))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    });
+    match (journal.is_some(), temp_path.exists()) {
+        (false, false) => None,
+        (true, false) => Some(Interrupted::Finished),
+        (_, true) => {
+            let written: Vec<String> = fs::read_to_string(&temp_path)
+                .map(|contents| contents.lines().map(str::to_string).collect())
+                .unwrap_or_default();
+            if expected == Some(checksum(&written)) {
+                Some(Interrupted::Complete(temp_path))
+            } else {
+                Some(Interrupted::Incomplete(temp_path))
+            }
+        }
+    }
+}
+
+// Clean up after an interrupted write, moving a complete temp file into place
+// when `finish` is set and discarding it otherwise
+pub fn recover(config_path: &Path, finish: bool) -> io::Result<()> {
+    let temp_path = temp_path(config_path);
+    let complete = matches!(
+        interrupted_write(config_path),
+        Some(Interrupted::Complete(_))
+    );
+    if finish && complete {
+        fs::rename(&temp_path, config_path)?;
+    } else if temp_path.exists() {
+        fs::remove_file(&temp_path)?;
+    }
+    match fs::remove_file(journal_path(config_path)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(name: &str, model: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            make: "Make".to_string(),
+            model: model.to_string(),
+            serial: String::new(),
+            active: true,
+            width: 1920,
+            height: 1080,
+            refresh: 60000,
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            transform: "normal".to_string(),
+            modes: Vec::new(),
+            physical_width: 0,
+            physical_height: 0,
+        }
+    }
+
+    fn desk() -> DisplayConfig {
+        DisplayConfig {
+            description: "Desk".to_string(),
+            outputs: vec![
+                "?identity DP-1 \"Make Monitor Unknown\"".to_string(),
+                "output DP-1 enable pos 0 0".to_string(),
+                "?identity HDMI-A-1 \"Make TV Unknown\"".to_string(),
+                "output \"Make TV Unknown\" enable pos 1920 0".to_string(),
+            ],
+            status: "Disabled".to_string(),
+            metadata: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lines_follow_a_monitor_to_another_connector() {
+        let outputs = [connected("DP-3", "Monitor")];
+        assert_eq!(
+            desk().reidentified(&outputs).outputs,
+            [
+                "?identity DP-3 \"Make Monitor Unknown\"",
+                "output DP-3 enable pos 0 0",
+                "?identity HDMI-A-1 \"Make TV Unknown\"",
+                "output \"Make TV Unknown\" enable pos 1920 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_fall_back_to_the_connector() {
+        let outputs = [
+            connected("DP-1", "Monitor"),
+            connected("HDMI-A-1", "Beamer"),
+        ];
+        let resolved = desk().resolved(&outputs);
+        assert_eq!(
+            resolved.outputs,
+            [
+                "output DP-1 enable pos 0 0",
+                "output HDMI-A-1 enable pos 1920 0"
+            ]
+        );
+    }
+}
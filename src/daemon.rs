@@ -0,0 +1,604 @@
+use crate::audit;
+use crate::config::{self, DisplayConfig, SwayConfig};
+use crate::effects;
+use crate::error::Error;
+use crate::events;
+use crate::generate;
+use crate::gpu;
+use crate::matching;
+use crate::mqtt;
+use crate::rules::{self, Action, Rule};
+use crate::settings::Settings;
+use crate::state;
+use crate::sway::{self, Output};
+use crate::switcher;
+use crate::tr;
+use crate::windows;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+#[cfg(feature = "dbus")]
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// How often the config file is checked for modifications
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// Give up waiting for the outputs to settle after this many rounds and act on
+// the last state seen
+const MAX_SETTLE_ROUNDS: usize = 10;
+
+// Keep the Enabled profile applied, re-asserting it when the config file or the
+// live output state changes underneath us. When observing, every event and the
+// profile that would be applied are logged, but nothing is applied or bound.
+// Bursts of output events are handled once, settled (see `settled_outputs`).
+// An output being plugged in or removed, or the lid closing or opening, first
+// runs the matching rule from the settings, if any, and `at` rules run when
+// the clock reaches their time. A GPU appearing or going away runs its own
+// rule, or switches to the best match. After a resume from suspend the outputs are
+// verified against the Enabled profile (see `after_resume`). Runs until the
+// connection to Sway is lost, and returns why.
+pub fn watch(config_path: &Path, observe: bool, settings: &Settings) -> Error {
+    let settle = settings.settle_delay;
+    if let Err(e) = events::serve() {
+        eprintln!("{}", tr!("warning", tr!("events-not-serving", e)));
+    }
+    if !observe {
+        mqtt::follow_commands(config_path);
+        if let Ok(config) = SwayConfig::load(config_path) {
+            if let Some(enabled) = config::enabled_config(&config.display_configs()) {
+                mqtt::publish_state(&enabled.description);
+            }
+        }
+    }
+    let (sender, receiver) = mpsc::channel();
+    let (reload_sender, reloads) = mpsc::channel::<()>();
+    // Workspace events are only wanted for the one a config reload sends
+    let mut subscription = match sway::subscribe("[\"output\", \"workspace\"]") {
+        Ok(subscription) => subscription,
+        Err(e) => return Error::Sway(tr!("subscribe-failed", e)),
+    };
+    let Some(stdout) = subscription.stdout.take() else {
+        return Error::Sway(tr!("subscription-no-stdout"));
+    };
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let event: serde_json::Value = serde_json::from_str(&line).unwrap_or_default();
+            let reload = event["change"] == "reload";
+            if !reload && (event.get("current").is_some() || event.get("old").is_some()) {
+                continue;
+            }
+            if observe {
+                log_event(&line);
+            }
+            let sent = if reload {
+                reload_sender.send(()).is_ok()
+            } else {
+                sender.send(line).is_ok()
+            };
+            if !sent {
+                break;
+            }
+        }
+        let _ = subscription.wait();
+    });
+
+    // Without the dbus feature no resume is ever reported
+    #[cfg_attr(not(feature = "dbus"), allow(unused_variables))]
+    let (resume_sender, resumes) = mpsc::channel::<()>();
+    #[cfg(feature = "dbus")]
+    if let Err(e) = watch_sleep(resume_sender) {
+        eprintln!("{}", tr!("warning", tr!("resume-not-watched", e)));
+    }
+
+    // Re-apply the Enabled profile, or only say so when observing
+    let respond = |reason: Option<&str>| {
+        if let Some(reason) = reason {
+            say(observe, reason);
+        }
+        if observe {
+            observe_reassert(config_path)
+        } else {
+            reassert(config_path, settle)
+        }
+    };
+
+    let mut modified = modified_time(config_path);
+    let mut hotkeys = Vec::new();
+    if !observe {
+        sync_hotkeys(config_path, &mut hotkeys);
+    }
+    let mut expected = respond(None);
+    let mut announced = Vec::new();
+    announce_unknown(config_path, expected.as_deref(), &mut announced, observe);
+    let mut lid = rules::lid_closed();
+    let mut gpus = gpu::gpus();
+    let mut clock = state::now() / 60;
+
+    loop {
+        // `at` rules fire once, in the minute the clock reaches their time;
+        // the first that can be applied wins. Time zones are whole minutes, so
+        // the local time only needs reading when the minute changes.
+        if state::now() / 60 != clock {
+            clock = state::now() / 60;
+            // Keep the window placement of the Enabled profile current, so an
+            // unplugged dock's windows can be put back when it returns
+            if !observe && windows::remembering() {
+                if let Ok(config) = SwayConfig::load(config_path) {
+                    if let Some(enabled) = config::enabled_config(&config.display_configs()) {
+                        windows::save(enabled);
+                    }
+                }
+            }
+            let due = rules::local_time()
+                .map(|(day, minute)| rules::due(&settings.rules, day, minute))
+                .unwrap_or_default();
+            if let Some(state) = due
+                .into_iter()
+                .find_map(|rule| run_rule(config_path, rule, observe, settle))
+            {
+                expected = Some(state);
+                modified = modified_time(config_path);
+            }
+        }
+        // A reload, ours after a switch or `swaymsg reload`, drops the
+        // bindings made over IPC
+        if reloads.try_recv().is_ok() {
+            while reloads.try_recv().is_ok() {}
+            if !observe {
+                sync_hotkeys(config_path, &mut hotkeys);
+            }
+        }
+        if resumes.try_recv().is_ok() {
+            expected = after_resume(config_path, observe, settings);
+            modified = modified_time(config_path);
+            continue;
+        }
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(_) => {
+                let (current, events) = settled_outputs(&receiver, settle);
+                if observe {
+                    println!("{}", tr!("observed", tr!("outputs-settled", events)));
+                }
+                announce_unknown(config_path, current.as_deref(), &mut announced, observe);
+                let before = expected.as_deref().unwrap_or_default();
+                let after = current.as_deref().unwrap_or_default();
+                let lid_now = rules::lid_closed();
+                // A GPU coming or going renames connectors, so it takes
+                // precedence over the rules for single outputs
+                let gpus_now = gpu::gpus();
+                if gpus_now != gpus {
+                    let added = gpus_now.iter().any(|gpu| !gpus.contains(gpu));
+                    gpus = gpus_now;
+                    lid = lid_now;
+                    let rule = rules::gpu_rule(&settings.rules, added);
+                    if let Some(state) = run_rule(config_path, &rule, observe, settle) {
+                        expected = Some(state);
+                        modified = modified_time(config_path);
+                        continue;
+                    }
+                }
+                if let Some(rule) = rules::first_fired(&settings.rules, before, after, lid, lid_now)
+                {
+                    lid = lid_now;
+                    if let Some(state) = run_rule(config_path, rule, observe, settle) {
+                        expected = Some(state);
+                        modified = modified_time(config_path);
+                        continue;
+                    }
+                }
+                lid = lid_now;
+                if current.as_deref().is_some_and(live_in_effect) {
+                    // Set up by this tool on purpose, e.g. a transient switch
+                    expected = current;
+                } else if current.is_some() && current != expected {
+                    expected = respond(Some(&tr!("outputs-changed")));
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                // Sway reports no event for the lid unless it changes the
+                // outputs, so its state is polled
+                let lid_now = rules::lid_closed();
+                if lid_now != lid {
+                    let outputs = expected.clone().unwrap_or_default();
+                    let fired =
+                        rules::first_fired(&settings.rules, &outputs, &outputs, lid, lid_now);
+                    lid = lid_now;
+                    if let Some(state) =
+                        fired.and_then(|rule| run_rule(config_path, rule, observe, settle))
+                    {
+                        expected = Some(state);
+                        modified = modified_time(config_path);
+                        continue;
+                    }
+                }
+                let current_modified = modified_time(config_path);
+                if current_modified != modified {
+                    modified = current_modified;
+                    if !observe {
+                        sync_hotkeys(config_path, &mut hotkeys);
+                    }
+                    expected = respond(Some(&tr!("config-changed")));
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Error::Sway(tr!("subscription-ended"));
+            }
+        }
+    }
+}
+
+// Follow logind's PrepareForSleep signal with dbus-monitor and send a message
+// on each resume
+#[cfg(feature = "dbus")]
+fn watch_sleep(sender: mpsc::Sender<()>) -> std::io::Result<()> {
+    let mut monitor = Command::new("dbus-monitor")
+        .args([
+            "--system",
+            "type='signal',interface='org.freedesktop.login1.Manager',member='PrepareForSleep'",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let stdout = monitor
+        .stdout
+        .take()
+        .ok_or_else(|| std::io::Error::other(tr!("dbus-no-stdout")))?;
+    thread::spawn(move || {
+        // The signal's argument follows on its own line: true when going to
+        // sleep, false when waking up
+        let mut in_signal = false;
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if line.contains("member=PrepareForSleep") {
+                in_signal = true;
+            } else if in_signal {
+                in_signal = false;
+                if line.trim() == "boolean false" && sender.send(()).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = monitor.wait();
+    });
+    Ok(())
+}
+
+// After a resume, give the outputs `resume_delay` to come back, then verify
+// that they still match the Enabled profile. If they do not, it is re-applied
+// and checked once more. Returns the output state seen last.
+fn after_resume(config_path: &Path, observe: bool, settings: &Settings) -> Option<Vec<Output>> {
+    say(observe, &tr!("resumed"));
+    thread::sleep(settings.resume_delay);
+    let config = SwayConfig::load(config_path).ok()?;
+    let display_configs = config.display_configs();
+    let Some(enabled) = config::enabled_config(&display_configs) else {
+        return sway::get_outputs();
+    };
+    let in_effect = |connected: &Option<Vec<Output>>| {
+        connected
+            .as_deref()
+            .is_some_and(|connected| matching::in_effect(enabled, connected))
+    };
+    let connected = sway::get_outputs();
+    if in_effect(&connected) {
+        say(observe, &tr!("still-in-effect", enabled.description));
+        return connected;
+    }
+    say(observe, &tr!("no-longer-match", enabled.description));
+    if observe {
+        return observe_reassert(config_path);
+    }
+    let connected = reassert(config_path, settings.settle_delay);
+    if !in_effect(&connected) {
+        let body = tr!("not-restored", enabled.description);
+        eprintln!("{}", body);
+        effects::notify(&tr!("not-restored-title"), &body);
+    }
+    connected
+}
+
+// Switch to the profile a rule names and return the output state it produced.
+// None when the profile cannot be found or applied, so the Enabled profile is
+// re-asserted as usual.
+fn run_rule(
+    config_path: &Path,
+    rule: &Rule,
+    observe: bool,
+    settle: Duration,
+) -> Option<Vec<Output>> {
+    let config = SwayConfig::load(config_path).ok()?;
+    let display_configs = config.display_configs();
+    // Profiles tied for the best match, offered as a choice after switching
+    let mut tied = Vec::new();
+    let index = match &rule.action {
+        Action::Profile(query) => config::find_config(&display_configs, query),
+        Action::BestMatch => {
+            let connected = sway::get_outputs().unwrap_or_default();
+            tied = matching::best_matches(&display_configs, &connected);
+            matching::best_match(&display_configs, &connected)
+        }
+    };
+    let Some(index) = index else {
+        eprintln!("{}", tr!("rule-no-profile", rule.event));
+        return None;
+    };
+    let description = &display_configs[index].description;
+    // The clock never overrides the hardware: a scheduled profile that does not
+    // fit the connected outputs is skipped
+    let connected = sway::get_outputs().unwrap_or_default();
+    if rule.event.is_scheduled() && matching::score(&display_configs[index], &connected).is_none() {
+        println!("{}", tr!("rule-skipped", rule.event, description));
+        return None;
+    }
+    let tied: Vec<String> = tied
+        .iter()
+        .map(|&index| display_configs[index].description.clone())
+        .collect();
+    let ambiguous = tied.len() > 1 && !rule.event.is_scheduled();
+    if observe {
+        println!(
+            "{}",
+            tr!(
+                "observed",
+                tr!("rule-would-switch", rule.event, description)
+            )
+        );
+        if ambiguous {
+            println!(
+                "{}",
+                tr!("observed", tr!("would-offer-choice", tied.join(", ")))
+            );
+        }
+        return sway::get_outputs();
+    }
+    println!("{}", tr!("rule-fired", rule.event, description));
+    if let Err(e) = switcher::switch_persistent(
+        &config,
+        &display_configs,
+        index,
+        &format!("watch: rule '{}'", rule.event),
+    ) {
+        eprintln!("{}", tr!("switch-to-failed", description, e));
+        return None;
+    }
+    if ambiguous {
+        offer_choice(config_path, tied, description);
+    }
+    thread::sleep(settle);
+    sway::get_outputs()
+}
+
+// Several profiles fitted equally well and `applied` was picked by file order:
+// ask with a notification which one to use, in the background, and switch
+// to the one clicked. The loop in `watch` then re-asserts it as the Enabled
+// profile like after any other switch.
+fn offer_choice(config_path: &Path, choices: Vec<String>, applied: &str) {
+    let config_path = config_path.to_path_buf();
+    let body = tr!("tied-profiles", applied, choices.len());
+    let applied = applied.to_string();
+    thread::spawn(move || {
+        let Some(chosen) = effects::ask(&tr!("choose-profile-title"), &body, &choices) else {
+            return;
+        };
+        let chosen = &choices[chosen];
+        if *chosen == applied {
+            return;
+        }
+        let Ok(config) = SwayConfig::load(&config_path) else {
+            return;
+        };
+        let display_configs = config.display_configs();
+        let Some(index) = config::find_config(&display_configs, chosen) else {
+            return;
+        };
+        println!("{}", tr!("chosen-in-notification", chosen));
+        if let Err(e) =
+            switcher::switch_persistent(&config, &display_configs, index, "watch: notification")
+        {
+            eprintln!("{}", tr!("switch-to-failed", chosen, e));
+        }
+    });
+}
+
+// After an output event, wait until the topology settles: no further event for
+// `settle`, and the same outputs reported on two queries `settle` apart. A dock
+// adds and removes several outputs within a second; acting on any of the
+// intermediate states would thrash. Returns the settled outputs and how many
+// events the burst had.
+fn settled_outputs(
+    receiver: &mpsc::Receiver<String>,
+    settle: Duration,
+) -> (Option<Vec<Output>>, usize) {
+    let mut events = 1;
+    let mut previous = None;
+    for _ in 0..MAX_SETTLE_ROUNDS {
+        // Events are taken in arrival order until the line goes quiet
+        while receiver.recv_timeout(settle).is_ok() {
+            events += 1;
+        }
+        let current = sway::get_outputs();
+        if previous.as_ref() == Some(&current) {
+            return (current, events);
+        }
+        previous = Some(current);
+    }
+    (previous.flatten(), events)
+}
+
+// Whether the outputs show what this tool last set up without saving it (see
+// state::record_live), which they are then meant to rather than the Enabled
+// profile
+fn live_in_effect(current: &[Output]) -> bool {
+    let state = state::State::load();
+    state.live_profile.is_some_and(|description| {
+        let live = DisplayConfig {
+            description,
+            outputs: state.live_outputs,
+            status: "Disabled".to_string(),
+            metadata: Vec::new(),
+        };
+        matching::in_effect(&live, current)
+    })
+}
+
+// Apply the Enabled profile and return the output state it produced
+fn reassert(config_path: &Path, settle: Duration) -> Option<Vec<Output>> {
+    match SwayConfig::load(config_path) {
+        Ok(config) => {
+            let display_configs = config.display_configs();
+            let connected = sway::get_outputs().unwrap_or_default();
+            match config::enabled_config(&display_configs) {
+                // A profile that would leave every output off gives way to
+                // the best fitting one, which never does
+                Some(enabled) if matching::leaves_all_off(enabled, &connected) => {
+                    println!("{}", tr!("would-blackout", enabled.description));
+                    match matching::best_match(&display_configs, &connected) {
+                        Some(index) => {
+                            println!(
+                                "{}",
+                                tr!("switching-to", display_configs[index].description)
+                            );
+                            if let Err(e) = switcher::switch_persistent(
+                                &config,
+                                &display_configs,
+                                index,
+                                "watch: blackout",
+                            ) {
+                                eprintln!(
+                                    "{}",
+                                    tr!("switch-to-failed", display_configs[index].description, e)
+                                );
+                            }
+                        }
+                        None => println!("{}", tr!("nothing-fits")),
+                    }
+                }
+                Some(enabled) => {
+                    let description = enabled.description.as_str();
+                    let pending =
+                        audit::start("watch: re-assert", description, Some(description), false);
+                    state::clear_live();
+                    if sway::apply_outputs(enabled) {
+                        pending.finish(None);
+                    } else {
+                        eprintln!("{}", tr!("apply-profile-failed", description));
+                        pending.finish(Some(tr!("output-line-rejected")));
+                    }
+                }
+                None => println!("{}", tr!("none-enabled")),
+            }
+        }
+        Err(error) => eprintln!("{}", tr!("error", error)),
+    }
+    thread::sleep(settle);
+    sway::get_outputs()
+}
+
+// Log what reassert would do instead of doing it, and return the output state
+// as it is, so that only further changes are reported
+fn observe_reassert(config_path: &Path) -> Option<Vec<Output>> {
+    match SwayConfig::load(config_path) {
+        Ok(config) => {
+            let display_configs = config.display_configs();
+            let connected = sway::get_outputs().unwrap_or_default();
+            match config::enabled_config(&display_configs) {
+                Some(enabled) if matching::leaves_all_off(enabled, &connected) => {
+                    println!(
+                        "{}",
+                        tr!("observed", tr!("would-blackout", enabled.description))
+                    );
+                    match matching::best_match(&display_configs, &connected) {
+                        Some(index) => println!(
+                            "{}",
+                            tr!(
+                                "observed",
+                                tr!("would-switch-instead", display_configs[index].description)
+                            )
+                        ),
+                        None => println!("{}", tr!("observed", tr!("nothing-fits-observe"))),
+                    }
+                }
+                Some(enabled) => {
+                    println!(
+                        "{}",
+                        tr!("observed", tr!("would-apply", enabled.description))
+                    );
+                    for line in &enabled.outputs {
+                        println!("{}", tr!("observed", format!("  {}", line)));
+                    }
+                }
+                None => println!("{}", tr!("observed", tr!("none-enabled"))),
+            }
+        }
+        Err(error) => eprintln!("{}", tr!("error", error)),
+    }
+    sway::get_outputs()
+}
+
+// Print a message, marked as such when only observing
+fn say(observe: bool, message: &str) {
+    if observe {
+        println!("{}", tr!("observed", message));
+    } else {
+        println!("{}", message);
+    }
+}
+
+// Print one raw Sway event, with the time it arrived
+fn log_event(event: &str) {
+    println!(
+        "{}",
+        tr!("observed", tr!("event-logged", state::now(), event.trim()))
+    );
+}
+
+// Bind each profile's `Hotkey =` over IPC, first dropping the keys bound last
+// time so that removed or re-keyed profiles lose their old binding. Run again
+// on every Sway reload, which discards bindings made over IPC.
+fn sync_hotkeys(config_path: &Path, bound: &mut Vec<String>) {
+    let Ok(config) = SwayConfig::load(config_path) else {
+        return;
+    };
+    let hotkeys = generate::hotkeys(&config.display_configs(), &config::variables(&config.lines));
+    for key in bound.drain(..) {
+        sway::try_command(&format!("unbindsym --to-code {}", key));
+    }
+    for (key, command) in hotkeys {
+        if sway::run_command(&format!("bindsym --to-code {} {}", key, command)) {
+            bound.push(key);
+        } else {
+            eprintln!("{}", tr!("bind-failed", key));
+        }
+    }
+}
+
+// Tell the user about connected outputs that no profile mentions, once per
+// output until it is unplugged again
+fn announce_unknown(
+    config_path: &Path,
+    connected: Option<&[Output]>,
+    announced: &mut Vec<String>,
+    observe: bool,
+) {
+    let (Some(connected), Ok(config)) = (connected, SwayConfig::load(config_path)) else {
+        return;
+    };
+    announced.retain(|id| connected.iter().any(|o| o.identifier() == *id));
+    for output in matching::unknown_outputs(&config.display_configs(), connected) {
+        let id = output.identifier();
+        if announced.contains(&id) {
+            continue;
+        }
+        let body = tr!("output-unknown", output.name, id);
+        println!("{}", body);
+        if !observe {
+            effects::notify(&tr!("output-unknown-title"), &body);
+        }
+        announced.push(id);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,87 @@
+// Graphical selection dialogs for launches without a terminal (.desktop files,
+// bar clicks), using zenity or yad
+
+use std::process::{self, Stdio};
+use sway_display_switcher::config::DisplayConfig;
+use sway_display_switcher::tr;
+
+// The result of asking through a dialog
+pub enum Choice {
+    Selected(usize),
+    Cancelled,
+    // Nobody answered within the timeout
+    TimedOut,
+    // Neither zenity nor yad could be run
+    Unavailable,
+}
+
+// Exit statuses of zenity and yad when their --timeout runs out
+const TIMEOUT_STATUSES: &[i32] = &[5, 70];
+
+// Offer the configs at the given indices, in that order, closing the dialog
+// after `timeout` seconds if given
+pub fn choose(display_configs: &[DisplayConfig], order: &[usize], timeout: Option<u64>) -> Choice {
+    let mut rows = Vec::new();
+    for config in order.iter().map(|&i| &display_configs[i]) {
+        rows.push(config.description.clone());
+        rows.push(config.status.clone());
+    }
+
+    let title = format!("--title={}", tr!("dialog-title"));
+    let profile_column = format!("--column={}", tr!("column-profile"));
+    let status_column = format!("--column={}", tr!("column-status"));
+    let timeout: Vec<String> = timeout
+        .map(|seconds| format!("--timeout={}", seconds))
+        .into_iter()
+        .collect();
+
+    let zenity = process::Command::new("zenity")
+        .args([
+            "--list",
+            &title,
+            &format!("--text={}", tr!("dialog-text")),
+            &profile_column,
+            &status_column,
+        ])
+        .args(&timeout)
+        .args(&rows)
+        .stderr(Stdio::null())
+        .output();
+    let yad = || {
+        process::Command::new("yad")
+            .args([
+                "--list",
+                &title,
+                &profile_column,
+                &status_column,
+                "--print-column=1",
+                "--separator=",
+            ])
+            .args(&timeout)
+            .args(&rows)
+            .stderr(Stdio::null())
+            .output()
+    };
+
+    let Ok(reply) = zenity.or_else(|_| yad()) else {
+        return Choice::Unavailable;
+    };
+    if reply
+        .status
+        .code()
+        .is_some_and(|code| TIMEOUT_STATUSES.contains(&code))
+    {
+        return Choice::TimedOut;
+    }
+    if !reply.status.success() {
+        return Choice::Cancelled;
+    }
+    let selected = String::from_utf8_lossy(&reply.stdout).trim().to_string();
+    match display_configs
+        .iter()
+        .position(|c| c.description == selected)
+    {
+        Some(index) => Choice::Selected(index),
+        None => Choice::Cancelled,
+    }
+}
@@ -0,0 +1,290 @@
+// Minimal line diff used to preview config changes, and a settings-level
+// comparison of two profiles
+
+use crate::config::DisplayConfig;
+use crate::output::OutputLine;
+use crate::tr;
+
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// Compute a line diff via the longest common subsequence
+pub fn diff_lines<'a>(old: &'a [String], new: &'a [String]) -> Vec<DiffLine<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            result.push(DiffLine::Same(&old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(&old[i]));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(&new[j]));
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    result.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+    result
+}
+
+// Render changed lines with a little surrounding context, unified-diff style
+pub fn format_diff(old: &[String], new: &[String], context: usize) -> Vec<String> {
+    let lines = diff_lines(old, new);
+    let changed: Vec<bool> = lines
+        .iter()
+        .map(|line| !matches!(line, DiffLine::Same(_)))
+        .collect();
+
+    let mut output = Vec::new();
+    let mut last_printed = None;
+    for (index, line) in lines.iter().enumerate() {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(lines.len());
+        if !changed[start..end].iter().any(|&c| c) {
+            continue;
+        }
+        if last_printed.is_some_and(|last| last + 1 != index) {
+            output.push("...".to_string());
+        }
+        output.push(match line {
+            DiffLine::Same(text) => format!("  {}", text),
+            DiffLine::Removed(text) => format!("- {}", text),
+            DiffLine::Added(text) => format!("+ {}", text),
+        });
+        last_printed = Some(index);
+    }
+    output
+}
+
+// The settings of one output line keyed by subcommand, the last occurrence
+// winning as in Sway. `enable`/`disable` are folded into a `state` entry.
+fn output_settings(line: &OutputLine) -> Vec<(String, String)> {
+    let state = if line.is_disabled() {
+        "disabled"
+    } else {
+        "enabled"
+    };
+    let mut settings = vec![("state".to_string(), state.to_string())];
+    for attribute in &line.attributes {
+        let key = attribute.canonical_key();
+        if matches!(key, "enable" | "disable") {
+            continue;
+        }
+        let mut words = attribute.flags.clone();
+        words.extend(attribute.values.iter().cloned());
+        let value = words.join(" ");
+        match settings.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => settings.push((key.to_string(), value)),
+        }
+    }
+    settings
+}
+
+// Compare two profiles output by output and attribute by attribute, listing
+// only what differs: `- ` marks the first profile's value, `+ ` the second's
+pub fn diff_profiles(a: &DisplayConfig, b: &DisplayConfig) -> Vec<String> {
+    let parse = |config: &DisplayConfig| -> Vec<OutputLine> {
+        config
+            .outputs
+            .iter()
+            .filter_map(|line| OutputLine::parse(line))
+            .collect()
+    };
+    let (lines_a, lines_b) = (parse(a), parse(b));
+    let mut targets: Vec<&str> = Vec::new();
+    for line in lines_a.iter().chain(&lines_b) {
+        if !targets.contains(&line.target.as_str()) {
+            targets.push(&line.target);
+        }
+    }
+
+    let mut output = Vec::new();
+    for target in targets {
+        let find = |lines: &[OutputLine]| lines.iter().rev().find(|l| l.target == target).cloned();
+        match (find(&lines_a), find(&lines_b)) {
+            (Some(_), None) => output.push(format!(
+                "- output {} {}",
+                target,
+                tr!("only-in", a.description)
+            )),
+            (None, Some(_)) => output.push(format!(
+                "+ output {} {}",
+                target,
+                tr!("only-in", b.description)
+            )),
+            (Some(line_a), Some(line_b)) => {
+                let (settings_a, settings_b) = (output_settings(&line_a), output_settings(&line_b));
+                let mut changes = Vec::new();
+                let mut keys: Vec<&str> = settings_a.iter().map(|(k, _)| k.as_str()).collect();
+                for (key, _) in &settings_b {
+                    if !keys.contains(&key.as_str()) {
+                        keys.push(key);
+                    }
+                }
+                for key in keys {
+                    let value = |settings: &[(String, String)]| {
+                        settings
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .map(|(_, v)| v.clone())
+                    };
+                    let (value_a, value_b) = (value(&settings_a), value(&settings_b));
+                    if value_a == value_b {
+                        continue;
+                    }
+                    if let Some(value) = value_a {
+                        changes.push(format!("-   {} {}", key, value));
+                    }
+                    if let Some(value) = value_b {
+                        changes.push(format!("+   {} {}", key, value));
+                    }
+                }
+                if !changes.is_empty() {
+                    output.push(format!("  output {}", target));
+                    output.extend(changes);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Lines other than output commands, compared as a whole
+    let others = |config: &DisplayConfig| -> Vec<String> {
+        config
+            .outputs
+            .iter()
+            .filter(|line| OutputLine::parse(line).is_none())
+            .cloned()
+            .collect()
+    };
+    let (others_a, others_b) = (others(a), others(b));
+    for line in others_a.iter().filter(|line| !others_b.contains(line)) {
+        output.push(format!("- {}", line));
+    }
+    for line in others_b.iter().filter(|line| !others_a.contains(line)) {
+        output.push(format!("+ {}", line));
+    }
+
+    // Header metadata, by key
+    let mut keys: Vec<&str> = a.metadata.iter().map(|(k, _)| k.as_str()).collect();
+    for (key, _) in &b.metadata {
+        if !keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+            keys.push(key);
+        }
+    }
+    for key in keys {
+        let (value_a, value_b) = (a.metadata(key), b.metadata(key));
+        if value_a == value_b {
+            continue;
+        }
+        if let Some(value) = value_a {
+            output.push(format!("- {} = {}", key, value));
+        }
+        if let Some(value) = value_b {
+            output.push(format!("+ {} = {}", key, value));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn diffs_lines_in_order() {
+        let (old, new) = (lines("a\nb\nc"), lines("a\nc\nd"));
+        assert_eq!(
+            diff_lines(&old, &new),
+            [
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shows_changes_with_context() {
+        let old = lines("1\n2\n3\n4\n5\n6\n7\n8\n9");
+        let new = lines("1\nTwo\n3\n4\n5\n6\n7\n8\nNine");
+        assert_eq!(
+            format_diff(&old, &new, 1),
+            ["  1", "- 2", "+ Two", "  3", "...", "  8", "- 9", "+ Nine"]
+        );
+        assert!(format_diff(&old, &old, 3).is_empty());
+    }
+
+    fn profile(description: &str, outputs: &[&str], metadata: &[(&str, &str)]) -> DisplayConfig {
+        DisplayConfig {
+            description: description.to_string(),
+            outputs: outputs.iter().map(|line| line.to_string()).collect(),
+            status: "Disabled".to_string(),
+            metadata: metadata
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn compares_profiles_setting_by_setting() {
+        let desk = profile(
+            "Desk",
+            &[
+                "output eDP-1 enable scale 2",
+                "output DP-1 enable mode 2560x1440 pos 0 0",
+                "workspace 1 output DP-1",
+            ],
+            &[("Hotkey", "Mod4+F1")],
+        );
+        let mirror = profile(
+            "Mirror",
+            &[
+                "output eDP-1 scale 2 enable",
+                "output DP-1 enable pos 0 0 transform 90",
+                "output HDMI-A-1 disable",
+            ],
+            &[("Hotkey", "Mod4+F2"), ("Priority", "1")],
+        );
+        assert_eq!(
+            diff_profiles(&desk, &mirror),
+            [
+                "  output DP-1".to_string(),
+                "-   mode 2560x1440".to_string(),
+                "+   transform 90".to_string(),
+                format!("+ output HDMI-A-1 {}", tr!("only-in", "Mirror")),
+                "- workspace 1 output DP-1".to_string(),
+                "- Hotkey = Mod4+F1".to_string(),
+                "+ Hotkey = Mod4+F2".to_string(),
+                "+ Priority = 1".to_string(),
+            ]
+        );
+        assert!(diff_profiles(&desk, &desk).is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+// Commands that edit a single output of a profile, applying it live when the
+// profile is enabled
+
+use crate::cli::Args;
+#[cfg(feature = "interactive")]
+use crate::{prompt, wizard};
+use sway_display_switcher::config::{self, DisplayConfig, SwayConfig};
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::layout::Side;
+#[cfg(feature = "interactive")]
+use sway_display_switcher::matching;
+use sway_display_switcher::output::{self, OutputLine};
+use sway_display_switcher::sway::{self, Mode, Output};
+use sway_display_switcher::tr;
+
+// The line configuring `name` in a profile, or a bare `output <name>` line
+fn current_line(profile: &DisplayConfig, name: &str, connected: &[Output]) -> String {
+    match profile.find_output_line(name, connected) {
+        Some(index) => profile.outputs[index].clone(),
+        None => format!("output {}", name),
+    }
+}
+
+// Rewrite the line configuring `name` in the Enabled profile (adding one if
+// needed) and send it to Sway. The config is only saved when Sway accepts the
+// new line; returns whether it did.
+fn update_enabled_output(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    name: &str,
+    edit: impl Fn(&str) -> String,
+) -> error::Result<bool> {
+    update_enabled_outputs(config, display_configs, &[name.to_string()], edit)
+}
+
+// update_enabled_output for several outputs at once, saving only when Sway
+// accepts every new line
+fn update_enabled_outputs(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    names: &[String],
+    edit: impl Fn(&str) -> String,
+) -> error::Result<bool> {
+    let enabled_index = crate::require_enabled(display_configs)?;
+    let connected = sway::get_outputs().unwrap_or_default();
+    let mut updated = display_configs.to_vec();
+    let enabled = &mut updated[enabled_index];
+    let mut accepted = true;
+    for name in names {
+        let new_line = match enabled.find_output_line(name, &connected) {
+            Some(index) => {
+                enabled.outputs[index] = edit(&enabled.outputs[index]);
+                enabled.outputs[index].clone()
+            }
+            None => {
+                let line = edit(&format!("output {}", name));
+                enabled.outputs.push(line.clone());
+                line
+            }
+        };
+        if !sway::run_command(&new_line) {
+            eprintln!("{}", tr!("command-rejected", new_line));
+            accepted = false;
+        }
+    }
+    if accepted {
+        config.save(&updated)?;
+    }
+    Ok(accepted)
+}
+
+// What to report when Sway did not take a line from update_enabled_output
+fn rejected() -> Error {
+    Error::Sway(tr!("edit-rejected"))
+}
+
+// Update the pos of one output in the Enabled profile and apply the change live
+pub fn position(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let usage = || Error::Usage(tr!("usage-position"));
+    let (Some(name), Some(where_to)) = (args.positional.first(), args.positional.get(1)) else {
+        return Err(usage());
+    };
+    let enabled = &display_configs[crate::require_enabled(display_configs)?];
+    let connected = sway::get_outputs().unwrap_or_default();
+    let current_line = current_line(enabled, name, &connected);
+
+    let (x, y) = match &args.relative_to {
+        Some(other) => {
+            let side = Side::parse(where_to).ok_or_else(usage)?;
+            let rect_of = |output: &str, line: &str| {
+                let live = connected
+                    .iter()
+                    .find(|o| o.name == output || o.matches(output));
+                OutputLine::parse(line).and_then(|parsed| parsed.rect(live))
+            };
+            let anchor_line = enabled
+                .find_output_line(other, &connected)
+                .map(|index| enabled.outputs[index].clone())
+                .unwrap_or_else(|| format!("output {}", other));
+            match (rect_of(name, &current_line), rect_of(other, &anchor_line)) {
+                (Some(own), Some(anchor)) => own.placed(&anchor, side),
+                _ => {
+                    return Err(Error::Failed(tr!("size-unknown", name, other)));
+                }
+            }
+        }
+        None => {
+            let parsed = where_to
+                .split_once(',')
+                .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+            parsed.ok_or_else(usage)?
+        }
+    };
+
+    let accepted = update_enabled_output(config, display_configs, name, |line| {
+        output::set_attribute(line, "pos", &[x.to_string(), y.to_string()])
+    })?;
+    if !accepted {
+        return Err(rejected());
+    }
+    println!("{}", tr!("moved", name, x, y));
+    Ok(())
+}
+
+// Change the refresh rate of an output in the Enabled profile, keeping the
+// size of its mode, and apply it; without a rate, list the advertised ones
+pub fn refresh(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let Some(name) = args.positional.first() else {
+        return Err(Error::Usage(tr!("usage-refresh")));
+    };
+    let enabled = &display_configs[crate::require_enabled(display_configs)?];
+    let connected = sway::get_outputs().unwrap_or_default();
+    let live = connected
+        .iter()
+        .find(|o| o.name == *name || o.identifier() == *name)
+        .ok_or_else(|| Error::Failed(tr!("not-connected", name)))?;
+    let size = OutputLine::parse(&current_line(enabled, name, &connected))
+        .and_then(|parsed| parsed.mode())
+        .unwrap_or_else(|| live.current_mode());
+    let mut rates: Vec<i64> = live
+        .modes
+        .iter()
+        .filter(|mode| mode.width == size.width && mode.height == size.height)
+        .map(|mode| mode.refresh)
+        .collect();
+    rates.sort_unstable_by(|a, b| b.cmp(a));
+    rates.dedup();
+    let listed = rates
+        .iter()
+        .map(|rate| format!("{} Hz", *rate as f64 / 1000.0))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let Some(wanted) = args.positional.get(1) else {
+        println!("{}x{}: {}", size.width, size.height, listed);
+        return Ok(());
+    };
+    let wanted: f64 = wanted
+        .trim_end_matches("Hz")
+        .parse()
+        .map_err(|_| Error::Usage(tr!("not-refresh-rate", wanted)))?;
+    // Pick the advertised rate, e.g. 143.998 for 144
+    let wanted = (wanted * 1000.0).round() as i64;
+    let Some(rate) = rates
+        .iter()
+        .copied()
+        .filter(|rate| (rate - wanted).abs() < 1000)
+        .min_by_key(|rate| (rate - wanted).abs())
+    else {
+        let offered = if listed.is_empty() {
+            tr!("none")
+        } else {
+            listed
+        };
+        return Err(Error::Failed(tr!(
+            "rate-not-advertised",
+            live.name,
+            wanted as f64 / 1000.0,
+            size.width,
+            size.height,
+            offered
+        )));
+    };
+
+    let mode = Mode {
+        refresh: rate,
+        ..size
+    };
+    let accepted = update_enabled_output(config, display_configs, name, |line| {
+        output::set_attribute(line, "mode", &[mode.to_string()])
+    })?;
+    if !accepted {
+        return Err(rejected());
+    }
+    println!("{}", tr!("set-mode", name, mode));
+    Ok(())
+}
+
+// Allow or forbid tearing on outputs of the Enabled profile: `tearing
+// [<output>] on|off|toggle`, every output the profile enables when none is
+// named. Without a state, print whether each output allows it.
+pub fn tearing(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let is_state = |word: &str| matches!(word, "on" | "off" | "toggle");
+    let (names, state): (Vec<String>, Option<&str>) = match args.positional.as_slice() {
+        [] => (Vec::new(), None),
+        [word] if is_state(word) => (Vec::new(), Some(word)),
+        [name] => (vec![name.clone()], None),
+        [name, word] if is_state(word) => (vec![name.clone()], Some(word)),
+        _ => {
+            return Err(Error::Usage(tr!("usage-tearing")));
+        }
+    };
+    let enabled = &display_configs[crate::require_enabled(display_configs)?];
+    let connected = sway::get_outputs().unwrap_or_default();
+    let names = if names.is_empty() {
+        enabled
+            .outputs
+            .iter()
+            .filter_map(|line| OutputLine::parse(line))
+            .filter(|line| !line.is_wildcard() && !line.is_disabled())
+            .map(|line| line.target)
+            .collect()
+    } else {
+        names
+    };
+    let allows = |line: &str| {
+        OutputLine::parse(line)
+            .and_then(|parsed| Some(parsed.attribute("allow_tearing")?.first()? == "yes"))
+            .unwrap_or(false)
+    };
+
+    let Some(state) = state else {
+        for name in &names {
+            let line = current_line(enabled, name, &connected);
+            let id = if allows(&line) {
+                "tearing-allowed"
+            } else {
+                "tearing-not-allowed"
+            };
+            println!("{}", tr!(id, name));
+        }
+        return Ok(());
+    };
+    require_support("allow_tearing")?;
+    let accepted = update_enabled_outputs(config, display_configs, &names, |line| {
+        let allow = match state {
+            "on" => true,
+            "off" => false,
+            _ => !allows(line),
+        };
+        let value = if allow { "yes" } else { "no" };
+        output::set_attribute(line, "allow_tearing", &[value.to_string()])
+    })?;
+    if !accepted {
+        return Err(rejected());
+    }
+    println!(
+        "{}",
+        tr!("tearing-updated", names.join(", "), enabled.description)
+    );
+    Ok(())
+}
+
+// Refuse to write a subcommand the running Sway does not know, which would
+// make the next reload fail
+fn require_support(key: &str) -> error::Result<()> {
+    match (sway::version(), output::introduced_in(key)) {
+        (Some(version), Some(since)) if !version.at_least(since) => Err(Error::Failed(tr!(
+            "needs-sway",
+            key,
+            since.0,
+            since.1,
+            version
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// Manage per-output ICC color profiles in the Enabled profile
+pub fn icc(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let operands: Vec<&str> = args.positional.iter().map(String::as_str).collect();
+    match operands.as_slice() {
+        ["set", name, path] => {
+            let path = config::expand_home(path);
+            if !path.is_file() {
+                return Err(Error::Failed(tr!("icc-missing", path.display())));
+            }
+            require_support("color_profile")?;
+            let path = path.to_string_lossy().into_owned();
+            let accepted = update_enabled_output(config, display_configs, name, |line| {
+                output::set_attribute(line, "color_profile", &["icc".to_string(), path.clone()])
+            })?;
+            if !accepted {
+                eprintln!("{}", tr!("icc-note"));
+                return Err(rejected());
+            }
+            println!("{}", tr!("icc-set", name));
+            Ok(())
+        }
+        ["show"] => {
+            let enabled = &display_configs[crate::require_enabled(display_configs)?];
+            for line in enabled.outputs.iter().filter_map(|l| OutputLine::parse(l)) {
+                if let Some(values) = line.attribute("color_profile") {
+                    println!("{}: {}", line.target, values.join(" "));
+                }
+            }
+            Ok(())
+        }
+        _ => Err(Error::Usage(tr!("usage-icc"))),
+    }
+}
+
+// Add a connected output (by default one that no profile mentions yet) to an
+// existing profile, or create a new profile for the current outputs
+#[cfg(feature = "interactive")]
+pub fn add_output(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    let output = match args.positional.first() {
+        Some(name) => connected
+            .iter()
+            .find(|o| o.name == *name || o.identifier() == *name)
+            .ok_or_else(|| Error::Failed(tr!("not-connected", name)))?,
+        None => {
+            let unknown = matching::unknown_outputs(display_configs, &connected);
+            match unknown.as_slice() {
+                [] => {
+                    println!("{}", tr!("all-outputs-known"));
+                    return Ok(());
+                }
+                [output] => *output,
+                _ => {
+                    for (i, output) in unknown.iter().enumerate() {
+                        println!("  {}. {} ({})", i + 1, output.name, output.identifier());
+                    }
+                    unknown[prompt::choose(&tr!("output"), unknown.len(), 0)?]
+                }
+            }
+        }
+    };
+
+    println!("{}", tr!("add-to-which", output.name, output.identifier()));
+    for (i, profile) in display_configs.iter().enumerate() {
+        println!("  {}. {} [{}]", i + 1, profile.description, profile.status);
+    }
+    println!(
+        "  {}. {}",
+        display_configs.len() + 1,
+        tr!("new-profile-choice")
+    );
+    let default = display_configs
+        .iter()
+        .position(|c| c.is_enabled())
+        .unwrap_or(0);
+    let choice = prompt::choose(&tr!("profile"), display_configs.len() + 1, default)?;
+    if choice == display_configs.len() {
+        return crate::create_profile(args, config, display_configs);
+    }
+
+    let line = wizard::output_line(
+        &display_configs[choice],
+        output,
+        &connected,
+        args.auto_scale,
+    )?;
+    let mut updated = display_configs.to_vec();
+    updated[choice].outputs.push(line.clone());
+    if updated[choice].is_enabled() && !sway::run_command(&line) {
+        return Err(Error::Sway(tr!("command-rejected", line)));
+    }
+    config.save(&updated)?;
+    println!(
+        "{}",
+        tr!("added-output", output.name, updated[choice].description)
+    );
+    Ok(())
+}
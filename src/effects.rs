@@ -0,0 +1,646 @@
+// Side effects declared in profile metadata, run after a profile is applied
+
+use crate::config::{self, DisplayConfig};
+use crate::output;
+use crate::remote;
+use crate::roles;
+use crate::state;
+use crate::sway::{self, Output};
+use crate::tr;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{self, Stdio};
+use std::sync::OnceLock;
+
+// HTTP endpoints that are sent every profile change (see `settings`)
+static WEBHOOKS: OnceLock<Vec<String>> = OnceLock::new();
+
+// Night-light daemons we know how to manage, in order of preference
+const NIGHT_LIGHTS: &[&str] = &["wlsunset", "gammastep"];
+
+// State file noting the night-light daemons running before a profile's `Gamma`
+// changed them, so profiles without the key can bring them back
+const NIGHT_LIGHT_BEFORE: &str = "night-light-before";
+
+// The same for the swayidle instances running before `Idle`/`Lock` replaced
+// them, e.g. the one started by the user's Sway config
+const SWAYIDLE_BEFORE: &str = "swayidle-before";
+
+// Run every side effect the profile asks for. On a remote host only those
+// done through Sway apply; the others would start programs on this machine.
+pub fn apply(profile: &DisplayConfig) {
+    for setting in profile.metadata_list("Bar") {
+        apply_bar(setting);
+    }
+    if let Some(criteria) = profile.metadata("Tearing") {
+        apply_tearing(criteria);
+    }
+    if remote::host().is_some() {
+        return;
+    }
+    match profile.metadata("Gamma") {
+        Some(gamma) => apply_gamma(gamma),
+        None => hand_back(NIGHT_LIGHT_BEFORE, NIGHT_LIGHTS),
+    }
+    // Before swayidle is restarted, so its lock command picks the file up
+    apply_lockscreen(profile);
+    if profile.metadata("Idle").is_some() || profile.metadata("Lock").is_some() {
+        apply_idle(profile.metadata("Idle"), profile.metadata("Lock"));
+    } else {
+        hand_back(SWAYIDLE_BEFORE, &["swayidle"]);
+    }
+    let brightness = profile.metadata_list("Brightness");
+    if !brightness.is_empty() {
+        apply_brightness(&brightness);
+    }
+    apply_environment(&profile.metadata_list("Env"));
+    if let Some(setting) = profile.metadata("Xwayland") {
+        apply_xwayland(profile, setting);
+    }
+}
+
+// `Xwayland = dpi 192` sets Xft.dpi for X11 apps through xrdb, which they read
+// when they start; `Xwayland = dpi auto` uses 96 DPI per unit of the largest
+// scale among the profile's enabled outputs
+fn apply_xwayland(profile: &DisplayConfig, setting: &str) {
+    let dpi = match setting.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["dpi", "auto"] => {
+            let scale = profile
+                .outputs
+                .iter()
+                .filter_map(|line| output::OutputLine::parse(line))
+                .filter(|line| !line.is_disabled())
+                .filter_map(|line| line.scale())
+                .fold(1.0, f64::max);
+            (96.0 * scale).round() as u32
+        }
+        ["dpi", value] => match value.parse() {
+            Ok(dpi) => dpi,
+            Err(_) => {
+                eprintln!("{}", tr!("warning", tr!("invalid-dpi", value)));
+                return;
+            }
+        },
+        _ => {
+            eprintln!("{}", tr!("warning", tr!("invalid-xwayland", setting)));
+            return;
+        }
+    };
+    let xrdb = process::Command::new("xrdb")
+        .arg("-merge")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let written = xrdb.and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Xft.dpi: {}", dpi)?;
+        }
+        child.wait()
+    });
+    if !written.is_ok_and(|status| status.success()) {
+        eprintln!("{}", tr!("warning", tr!("xrdb-failed")));
+    }
+}
+
+// Where the variables of the last `Env =` profile are written for shells to
+// source
+pub fn environment_path() -> PathBuf {
+    state::state_dir().join("env")
+}
+
+// `Env = GDK_SCALE=2 QT_SCALE_FACTOR=1.5` exports toolkit scaling variables:
+// they are written to environment_path() and pushed into the systemd user
+// manager and the D-Bus activation environment, so apps started afterwards
+// follow the new scale. Variables the previous profile set and this one does
+// not are unset again; profiles that never set any change nothing.
+fn apply_environment(items: &[&str]) {
+    let variables: Vec<(&str, &str)> = items
+        .iter()
+        .filter_map(|item| match item.split_once('=') {
+            Some((name, value)) if is_variable_name(name) => Some((name, value)),
+            _ => {
+                eprintln!("{}", tr!("warning", tr!("invalid-environment", item)));
+                None
+            }
+        })
+        .collect();
+    let path = environment_path();
+    let previous: Vec<String> = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (name, _) = line.strip_prefix("export ")?.split_once('=')?;
+            Some(name.to_string())
+        })
+        .collect();
+    if variables.is_empty() && previous.is_empty() {
+        return;
+    }
+    let removed: Vec<&str> = previous
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !variables.iter().any(|(set, _)| set == name))
+        .collect();
+
+    let written = if variables.is_empty() {
+        fs::remove_file(&path)
+    } else {
+        let contents: String = variables
+            .iter()
+            .map(|(name, value)| format!("export {}='{}'\n", name, value.replace('\'', "'\\''")))
+            .collect();
+        fs::create_dir_all(state::state_dir()).and_then(|_| fs::write(&path, contents))
+    };
+    if let Err(e) = written {
+        eprintln!(
+            "{}",
+            tr!("warning", tr!("update-failed", path.display(), e))
+        );
+    }
+
+    let quiet = |command: &mut process::Command| {
+        let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).status();
+    };
+    if !removed.is_empty() {
+        quiet(
+            process::Command::new("systemctl")
+                .args(["--user", "unset-environment"])
+                .args(&removed),
+        );
+    }
+    if !variables.is_empty() {
+        quiet(
+            process::Command::new("systemctl")
+                .args(["--user", "import-environment"])
+                .args(variables.iter().map(|(name, _)| name))
+                .envs(variables.iter().copied()),
+        );
+    }
+    // D-Bus cannot unset a variable, so removed ones are set empty
+    #[cfg(feature = "dbus")]
+    quiet(
+        process::Command::new("dbus-update-activation-environment").args(
+            variables
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .chain(removed.iter().map(|name| format!("{}=", name))),
+        ),
+    );
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// `Tearing = [app_id="game"]` lets the matching windows tear, both those open
+// now and new ones; the outputs also need `allow_tearing yes`. A reload drops
+// the rule again.
+fn apply_tearing(criteria: &str) {
+    let criteria = criteria.trim();
+    if !(criteria.starts_with('[') && criteria.ends_with(']')) {
+        eprintln!("{}", tr!("warning", tr!("invalid-tearing", criteria)));
+        return;
+    }
+    for command in [
+        format!("for_window {} allow_tearing yes", criteria),
+        format!("{} allow_tearing yes", criteria),
+    ] {
+        // Without matching windows the second command fails harmlessly
+        sway::try_command(&command);
+    }
+}
+
+// Visibility modes Sway accepts for a bar
+const BAR_MODES: &[&str] = &["dock", "hide", "invisible", "overlay"];
+
+// `Bar = invisible` sets the mode of every bar; `Bar = bar-0:dock bar-1:invisible`
+// sets it per bar ID, as listed by `swaymsg -t get_bar_config`
+fn apply_bar(setting: &str) {
+    let (id, mode) = match setting.rsplit_once(':') {
+        Some((id, mode)) => (Some(id), mode),
+        None => (None, setting),
+    };
+    let mode = mode.to_ascii_lowercase();
+    if !BAR_MODES.contains(&mode.as_str()) {
+        eprintln!("{}", tr!("warning", tr!("invalid-bar-mode", setting)));
+        return;
+    }
+    let command = match id {
+        Some(id) => format!("bar {} mode {}", id, mode),
+        None => format!("bar mode {}", mode),
+    };
+    if !sway::run_command(&command) {
+        eprintln!("{}", tr!("warning", tr!("sway-rejected", command)));
+    }
+}
+
+// `Brightness = 60` sets every enabled output to 60%; `Brightness = eDP-1:40
+// external:70` sets it per connector or role (see `roles`), later items
+// winning. Built-in panels go through brightnessctl, other outputs through
+// ddcutil (DDC/CI VCP feature 10), which is slow, so neither is waited for.
+fn apply_brightness(items: &[&str]) {
+    let Some(connected) = sway::get_outputs() else {
+        eprintln!("{}", tr!("warning", tr!("brightness-no-outputs")));
+        return;
+    };
+    let active: Vec<Output> = connected.into_iter().filter(|o| o.active).collect();
+    let mut levels: Vec<(&Output, u32)> = Vec::new();
+    for item in items {
+        let (target, value) = match item.rsplit_once(':') {
+            Some((target, value)) => (Some(target), value),
+            None => (None, *item),
+        };
+        let level = match value.trim_end_matches('%').parse::<u32>() {
+            Ok(level) if level <= 100 => level,
+            _ => {
+                eprintln!("{}", tr!("warning", tr!("invalid-brightness", item)));
+                continue;
+            }
+        };
+        let outputs: Vec<&Output> = match target {
+            None => active.iter().collect(),
+            Some(name) => match roles::Role::parse(name) {
+                Some(role) => roles::find(role, &active),
+                None => active.iter().filter(|o| o.name == name).collect(),
+            },
+        };
+        for output in outputs {
+            levels.retain(|(set, _)| set.name != output.name);
+            levels.push((output, level));
+        }
+    }
+
+    for (output, level) in levels {
+        let command: Vec<String> = if roles::is_internal(&output.name) {
+            ["brightnessctl", "--quiet", "--class=backlight", "set"]
+                .map(str::to_string)
+                .into_iter()
+                .chain([format!("{}%", level)])
+                .collect()
+        } else {
+            // The serial number tells identical monitors apart when the EDID
+            // has one
+            let (flag, value) = if output.serial.is_empty() {
+                ("--model", &output.model)
+            } else {
+                ("--sn", &output.serial)
+            };
+            vec![
+                "ddcutil".to_string(),
+                flag.to_string(),
+                value.clone(),
+                "setvcp".to_string(),
+                "10".to_string(),
+                level.to_string(),
+            ]
+        };
+        if !spawn_detached(&command) {
+            eprintln!(
+                "{}",
+                tr!("warning", tr!("brightness-failed", command[0], output.name))
+            );
+        }
+    }
+}
+
+// Where the swaylock config for the last profile with `Lock-image` or
+// `Lock-indicator` is written
+pub fn lockscreen_path() -> PathBuf {
+    state::state_dir().join("swaylock")
+}
+
+// The command that locks the screen the way the applied profile asks for:
+// swaylock with the file at lockscreen_path() when there is one, otherwise
+// with the user's own swaylock config
+pub fn lock_command() -> Vec<String> {
+    let mut command = vec!["swaylock".to_string(), "-f".to_string()];
+    let path = lockscreen_path();
+    if path.exists() {
+        command.extend(["-C".to_string(), path.to_string_lossy().into_owned()]);
+    }
+    command
+}
+
+// The swaylock config swaylock itself would read
+fn swaylock_config() -> Option<PathBuf> {
+    let user = dirs::config_dir().map(|dir| dir.join("swaylock/config"));
+    let legacy = dirs::home_dir().map(|home| home.join(".swaylock/config"));
+    [user, legacy, Some(PathBuf::from("/etc/swaylock/config"))]
+        .into_iter()
+        .flatten()
+        .find(|path| path.exists())
+}
+
+// `Lock-image = eDP-1:~/desk.png HDMI-A-1:~/wall.png` gives each output its own
+// lock screen background (a path without an output covers the others), and
+// `Lock-indicator = HDMI-A-1` names the output the unlock indicator should be
+// seen on. The user's swaylock config is copied with these added, replacing
+// its own images, to lockscreen_path(); a profile with neither key removes the
+// copy so swaylock goes back to the user's config.
+fn apply_lockscreen(profile: &DisplayConfig) {
+    let images = profile.metadata_list("Lock-image");
+    let indicator = profile.metadata("Lock-indicator");
+    let path = lockscreen_path();
+    if images.is_empty() && indicator.is_none() {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                eprintln!(
+                    "{}",
+                    tr!("warning", tr!("remove-failed", path.display(), e))
+                );
+            }
+        }
+        return;
+    }
+
+    let mut contents = format!(
+        "# Written by sway-display-switcher for '{}'; replaced on every switch\n",
+        profile.description
+    );
+    let base = swaylock_config()
+        .and_then(|base| fs::read_to_string(base).ok())
+        .unwrap_or_default();
+    for line in base.lines() {
+        let option = line.trim_start().trim_start_matches('-');
+        if !images.is_empty() && (option.starts_with("image=") || option.starts_with("i=")) {
+            continue;
+        }
+        contents.push_str(line);
+        contents.push('\n');
+    }
+    for image in &images {
+        let image = match image.split_once(':') {
+            Some((output, file)) => format!("{}:{}", output, config::expand_home(file).display()),
+            None => config::expand_home(image).display().to_string(),
+        };
+        contents.push_str(&format!("image={}\n", image));
+    }
+    if let Some(indicator) = indicator {
+        // swaylock draws the indicator on every output it locks; showing it
+        // before a key is pressed is what makes it findable on the named one
+        contents.push_str("indicator-idle-visible\n");
+        let enabled = profile
+            .outputs
+            .iter()
+            .filter_map(|line| output::OutputLine::parse(line))
+            .any(|line| line.target == indicator && !line.is_disabled());
+        if !enabled {
+            eprintln!(
+                "{}",
+                tr!(
+                    "warning",
+                    tr!("indicator-disabled", indicator, profile.description)
+                )
+            );
+        }
+    }
+
+    let written = fs::create_dir_all(state::state_dir()).and_then(|_| fs::write(&path, contents));
+    if let Err(e) = written {
+        eprintln!(
+            "{}",
+            tr!("warning", tr!("update-failed", path.display(), e))
+        );
+    }
+}
+
+// Restart swayidle with the profile's timeouts. `Idle = <seconds>` powers the
+// outputs off, `Lock = <seconds>` runs swaylock; `off` (or `never`) for both
+// means swayidle is not running at all, e.g. for a presentation profile. An
+// invalid value leaves swayidle as it is rather than stop the lock.
+fn apply_idle(idle: Option<&str>, lock: Option<&str>) {
+    let seconds = |value: Option<&str>| -> Result<Option<u64>, ()> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        if value.eq_ignore_ascii_case("off") || value.eq_ignore_ascii_case("never") {
+            return Ok(None);
+        }
+        value.parse().map(Some).map_err(|_| {
+            eprintln!("{}", tr!("warning", tr!("invalid-idle", value)));
+        })
+    };
+    let (Ok(idle), Ok(lock)) = (seconds(idle), seconds(lock)) else {
+        return;
+    };
+
+    remember(SWAYIDLE_BEFORE, &["swayidle"]);
+    stop_all(&["swayidle"]);
+    if idle.is_none() && lock.is_none() {
+        return;
+    }
+
+    let mut command: Vec<String> = vec!["swayidle".into(), "-w".into()];
+    if let Some(lock) = lock {
+        // Looked up when it locks, as a later switch may write or remove the
+        // file without restarting swayidle
+        let path = lockscreen_path().to_string_lossy().replace('\'', "'\\''");
+        let swaylock = format!(
+            "if [ -e '{0}' ]; then swaylock -f -C '{0}'; else swaylock -f; fi",
+            path
+        );
+        command.extend(["timeout".into(), lock.to_string(), swaylock.clone()]);
+        command.extend(["before-sleep".into(), swaylock]);
+    }
+    if let Some(idle) = idle {
+        command.extend([
+            "timeout".into(),
+            idle.to_string(),
+            format!("swaymsg 'output * {} off'", sway::power_keyword()),
+            "resume".into(),
+            format!("swaymsg 'output * {} on'", sway::power_keyword()),
+        ]);
+    }
+    if !spawn_detached(&command) {
+        eprintln!("{}", tr!("warning", tr!("swayidle-failed")));
+    }
+}
+
+// `Gamma = off` stops the night-light daemon, `Gamma = on` starts one if none
+// is running, and any other value is a command line to (re)start it with, e.g.
+// `Gamma = wlsunset -t 4500 -T 6500`. What ran before is noted first, for the
+// next profile without `Gamma` to restore.
+fn apply_gamma(setting: &str) {
+    remember(NIGHT_LIGHT_BEFORE, NIGHT_LIGHTS);
+    match setting.to_ascii_lowercase().as_str() {
+        "off" => stop_all(NIGHT_LIGHTS),
+        "on" => {
+            if !NIGHT_LIGHTS.iter().any(|name| is_running(name)) {
+                let started = NIGHT_LIGHTS
+                    .iter()
+                    .any(|name| spawn_detached(&[name.to_string()]));
+                if !started {
+                    eprintln!("{}", tr!("warning", tr!("night-light-failed")));
+                }
+            }
+        }
+        _ => {
+            let command = output::tokenize(setting);
+            stop_all(NIGHT_LIGHTS);
+            if !spawn_detached(&command) {
+                eprintln!("{}", tr!("warning", tr!("start-failed", setting)));
+            }
+        }
+    }
+}
+
+// Note in the state file `file` how the `names` processes running now were
+// started, unless it is already there: then they were stopped or replaced by
+// an earlier profile, and what ran before that is what should come back
+fn remember(file: &str, names: &[&str]) {
+    let path = state::state_dir().join(file);
+    if path.exists() {
+        return;
+    }
+    let commands = serde_json::to_string(&running_commands(names)).unwrap_or_default();
+    let written = fs::create_dir_all(state::state_dir()).and_then(|_| fs::write(&path, commands));
+    if let Err(e) = written {
+        eprintln!(
+            "{}",
+            tr!("warning", tr!("update-failed", path.display(), e))
+        );
+    }
+}
+
+// Undo what remember() noted in `file`: stop the `names` processes started
+// since and start again those that ran before, then forget the note. Without a
+// note nothing was changed, and nothing is done.
+fn hand_back(file: &str, names: &[&str]) {
+    let path = state::state_dir().join(file);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return;
+    };
+    let commands: Vec<Vec<String>> = serde_json::from_str(&contents).unwrap_or_default();
+    stop_all(names);
+    for command in commands {
+        if !spawn_detached(&command) {
+            eprintln!("{}", tr!("warning", tr!("start-failed", command.join(" "))));
+        }
+    }
+    if let Err(e) = fs::remove_file(&path) {
+        eprintln!(
+            "{}",
+            tr!("warning", tr!("remove-failed", path.display(), e))
+        );
+    }
+}
+
+// The command lines of the running processes called one of `names`, read from
+// /proc so arguments with spaces stay whole
+fn running_commands(names: &[&str]) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    for name in names {
+        let Ok(found) = process::Command::new("pgrep").args(["-x", name]).output() else {
+            continue;
+        };
+        for pid in String::from_utf8_lossy(&found.stdout).split_whitespace() {
+            let Ok(cmdline) = fs::read(format!("/proc/{}/cmdline", pid)) else {
+                continue;
+            };
+            // Each argument ends in a NUL byte
+            let Some(args) = cmdline.strip_suffix(&[0]) else {
+                continue;
+            };
+            commands.push(
+                args.split(|&byte| byte == 0)
+                    .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                    .collect(),
+            );
+        }
+    }
+    commands
+}
+
+pub fn is_running(name: &str) -> bool {
+    process::Command::new("pgrep")
+        .args(["-x", name])
+        .stdout(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+pub fn stop_all(names: &[&str]) {
+    for name in names {
+        let _ = process::Command::new("pkill")
+            .args(["-x", name])
+            .stdout(Stdio::null())
+            .status();
+    }
+}
+
+// Show a desktop notification, if notify-send is installed
+pub fn notify(summary: &str, body: &str) -> bool {
+    spawn_detached(&[
+        "notify-send".to_string(),
+        "--app-name=sway-display-switcher".to_string(),
+        summary.to_string(),
+        body.to_string(),
+    ])
+}
+
+// Show a desktop notification with a button for each choice and wait until
+// one is clicked, returning its index; None when the notification is
+// dismissed or notify-send (0.7.10 or newer for actions) is missing
+pub fn ask(summary: &str, body: &str, choices: &[String]) -> Option<usize> {
+    let reply = process::Command::new("notify-send")
+        .arg("--app-name=sway-display-switcher")
+        .args(
+            choices
+                .iter()
+                .enumerate()
+                .map(|(index, choice)| format!("--action={}={}", index, choice)),
+        )
+        .args([summary, body])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let index: usize = String::from_utf8_lossy(&reply.stdout).trim().parse().ok()?;
+    (index < choices.len()).then_some(index)
+}
+
+// POST profile changes to these URLs from now on
+pub fn set_webhooks(urls: Vec<String>) {
+    let _ = WEBHOOKS.set(urls);
+}
+
+// POST a JSON payload to every configured webhook with curl, in the
+// background so a slow endpoint never holds up a switch
+pub fn post_webhooks(payload: &str) {
+    for url in WEBHOOKS.get().into_iter().flatten() {
+        let command = [
+            "curl",
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--max-time",
+            "10",
+            "--header",
+            "Content-Type: application/json",
+            "--data-binary",
+            payload,
+            url,
+        ];
+        if !spawn_detached(&command.map(str::to_string)) {
+            eprintln!("{}", tr!("warning", tr!("webhook-failed", url)));
+        }
+    }
+}
+
+// Start a long-running helper that outlives this process
+pub fn spawn_detached(command: &[String]) -> bool {
+    let Some((program, args)) = command.split_first() else {
+        return false;
+    };
+    process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .is_ok()
+}
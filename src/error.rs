@@ -0,0 +1,108 @@
+// What can go wrong, reported once at the top of the program: as a sentence on
+// stderr, or as a JSON object with `--json` for scripts and frontends
+
+use crate::tr;
+use serde_json::{json, Value};
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    // A file could not be read or written; `operation` says what was tried
+    #[error("{}", tr!(&format!("{}-failed", operation), path.display(), source))]
+    File {
+        operation: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    // The config file is missing something the switcher needs
+    #[error("{}: {message}", path.display())]
+    Config { path: PathBuf, message: String },
+    // Sway could not be asked, or refused what it was sent
+    #[error("{0}")]
+    Sway(String),
+    // A profile was named that the config does not have
+    #[error("{}", tr!("no-such-profile", .0))]
+    NoSuchProfile(String),
+    // The command line asks for something that does not make sense
+    #[error("{0}")]
+    Usage(String),
+    // A command could not do what was asked, for the reason given
+    #[error("{0}")]
+    Failed(String),
+    // There was nothing to do, e.g. switching to the profile already in
+    // effect; not a failure, but told apart by its exit status
+    #[error("{0}")]
+    Unchanged(String),
+    // The person at the terminal quit a prompt or declined to go on
+    #[error("{}", tr!("exiting"))]
+    Cancelled,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // `operation` on `path` failed with `source`
+    pub fn file(operation: &'static str, path: impl Into<PathBuf>, source: io::Error) -> Error {
+        Error::File {
+            operation,
+            path: path.into(),
+            source,
+        }
+    }
+
+    // A short stable name for the kind of error, for JSON output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::File { .. } => "file",
+            Error::Config { .. } => "config",
+            Error::Sway(_) => "sway",
+            Error::NoSuchProfile(_) => "no-such-profile",
+            Error::Usage(_) => "usage",
+            Error::Failed(_) => "failed",
+            Error::Unchanged(_) => "unchanged",
+            Error::Cancelled => "cancelled",
+            Error::Io(_) => "io",
+        }
+    }
+
+    // The exit status: 2 for a command line that was not understood, as
+    // before, 3 when nothing had to be done, 0 when the person quit, and 1 for
+    // everything else
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::Unchanged(_) => 3,
+            Error::Cancelled => 0,
+            _ => 1,
+        }
+    }
+}
+
+impl Error {
+    pub fn to_json(&self) -> Value {
+        let mut fields = json!({ "kind": self.kind(), "message": self.to_string() });
+        if let Error::File { path, .. } | Error::Config { path, .. } = self {
+            fields["path"] = path.display().to_string().into();
+        }
+        json!({ "error": fields })
+    }
+}
+
+// Print `error` for a person, or as JSON on stdout when `json` is set, and exit
+// with its status
+pub fn report(error: &Error, json: bool) -> ! {
+    match error {
+        _ if json => println!("{}", error.to_json()),
+        // Says what is already the case, which is no error to the reader
+        Error::Unchanged(message) => println!("{}", message),
+        Error::Cancelled => println!("{}", error),
+        _ => eprintln!("{}", tr!("error", error)),
+    }
+    process::exit(error.exit_code())
+}
@@ -0,0 +1,98 @@
+// Live profile-change events. The --watch daemon listens on a Unix socket in
+// XDG_RUNTIME_DIR; every instance that applies a switch publishes it there and
+// the daemon passes it on to each subscriber (`events --follow`).
+//
+// The protocol is line based: a client sends `subscribe` and then receives one
+// JSON event per line, or sends `publish <json>` to broadcast an event.
+
+use crate::state;
+use crate::tr;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Where the daemon of this seat listens
+pub fn socket_path() -> PathBuf {
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    let name = match state::seat() {
+        Some(seat) => format!("sway-display-switcher.{}.sock", seat),
+        None => "sway-display-switcher.sock".to_string(),
+    };
+    runtime_dir.join(name)
+}
+
+// Start accepting subscribers and publishers in the background. Fails when
+// another daemon is already listening; a socket left behind by a crashed one
+// is replaced.
+pub fn serve() -> io::Result<()> {
+    let path = socket_path();
+    if UnixStream::connect(&path).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            tr!("daemon-listening", path.display()),
+        ));
+    }
+    let _ = fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    let subscribers: Arc<Mutex<Vec<UnixStream>>> = Arc::default();
+    thread::spawn(move || {
+        for stream in listener.incoming().map_while(Result::ok) {
+            let subscribers = Arc::clone(&subscribers);
+            thread::spawn(move || handle(stream, &subscribers));
+        }
+    });
+    Ok(())
+}
+
+// Serve one connection: register a subscriber, or broadcast each published line
+fn handle(stream: UnixStream, subscribers: &Mutex<Vec<UnixStream>>) {
+    let Ok(reader_stream) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(reader_stream).lines().map_while(Result::ok) {
+        if line.trim() == "subscribe" {
+            // A subscriber that stops reading is dropped by the next
+            // broadcast rather than blocking it
+            if stream.set_nonblocking(true).is_ok() {
+                if let Ok(mut subscribers) = subscribers.lock() {
+                    subscribers.push(stream);
+                }
+            }
+            return;
+        }
+        if let Some(event) = line.strip_prefix("publish ") {
+            broadcast(event, subscribers);
+        }
+    }
+}
+
+// Send an event to every subscriber, dropping those that went away or whose
+// socket buffer is full
+fn broadcast(event: &str, subscribers: &Mutex<Vec<UnixStream>>) {
+    if let Ok(mut subscribers) = subscribers.lock() {
+        subscribers.retain_mut(|subscriber| writeln!(subscriber, "{}", event).is_ok());
+    }
+}
+
+// Hand an event to the daemon for its subscribers. Without a running daemon
+// nobody is listening, so the event is dropped silently.
+pub fn publish(event: &str) {
+    if let Ok(mut stream) = UnixStream::connect(socket_path()) {
+        let _ = writeln!(stream, "publish {}", event);
+    }
+}
+
+// Connect to the daemon as a subscriber; read events line by line from the
+// returned reader
+pub fn subscribe() -> io::Result<BufReader<UnixStream>> {
+    let mut stream = UnixStream::connect(socket_path())?;
+    writeln!(stream, "subscribe")?;
+    Ok(BufReader::new(stream))
+}
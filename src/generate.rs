@@ -0,0 +1,561 @@
+// Generation of Sway config snippets from the profiles
+
+use crate::config::DisplayConfig;
+use crate::layout::{Rect, Side};
+use crate::migrate;
+use crate::output::{self, OutputLine};
+use crate::sway::{Mode, Output};
+use crate::{roles, scale, tr};
+use std::path::Path;
+
+// Markers delimiting the generated keybinding block in the Sway config
+pub const BINDINGS_START: &str = "# Display Bindings Start";
+pub const BINDINGS_END: &str = "# Display Bindings End";
+
+// Quote a profile name for the shell command run by `exec`
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+// The command a binding runs to switch to a profile
+fn switch_command(config: &DisplayConfig) -> String {
+    format!(
+        "exec sway-display-switcher switch {}",
+        shell_quote(&config.description)
+    )
+}
+
+// The key and command of every profile with `Hotkey =` metadata, with Sway
+// variables such as `$mod` expanded so they can be bound at runtime over IPC
+pub fn hotkeys(configs: &[DisplayConfig], variables: &[(String, String)]) -> Vec<(String, String)> {
+    configs
+        .iter()
+        .filter_map(|config| {
+            let key = config.metadata("Hotkey")?;
+            Some((expand_variables(key, variables), switch_command(config)))
+        })
+        .collect()
+}
+
+// Replace `$name` references, longest names first so `$mod` does not clobber `$mod2`
+fn expand_variables(text: &str, variables: &[(String, String)]) -> String {
+    let mut sorted: Vec<&(String, String)> = variables.iter().collect();
+    sorted.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+    let mut expanded = text.to_string();
+    for (name, value) in sorted {
+        expanded = expanded.replace(name.as_str(), value);
+    }
+    expanded
+}
+
+// One `bindsym` line per profile. A profile's `Hotkey =` metadata wins;
+// the others get the next free $mod+F1..F12 key in list order.
+pub fn bindings(configs: &[DisplayConfig]) -> Vec<String> {
+    let explicit: Vec<&str> = configs
+        .iter()
+        .filter_map(|c| c.metadata("Hotkey"))
+        .collect();
+    let mut free_keys = (1..=12)
+        .map(|n| format!("$mod+F{}", n))
+        .filter(|key| !explicit.contains(&key.as_str()));
+
+    let mut lines = Vec::new();
+    for config in configs {
+        let key = match config.metadata("Hotkey") {
+            Some(hotkey) => hotkey.to_string(),
+            None => match free_keys.next() {
+                Some(key) => key,
+                None => continue,
+            },
+        };
+        lines.push(format!("bindsym {} {}", key, switch_command(config)));
+    }
+    lines
+}
+
+// Markers of the managed display section, and the line re-applying the
+// Enabled profile whenever Sway starts or reloads
+pub const DISPLAY_START: &str = "# Display Start";
+pub const DISPLAY_END: &str = "# Display End";
+pub const RESTORE_LINE: &str = "exec_always sway-display-switcher restore";
+
+// The parts of the recommended Sway config integration that `lines` lacks:
+// the restore line (unless the switcher is already started some other way),
+// the keybinding block and the display section markers
+pub fn integration(lines: &[String], configs: &[DisplayConfig]) -> Vec<String> {
+    let has = |text: &str| lines.iter().any(|line| line.contains(text));
+    let started = lines.iter().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("exec") && line.contains("sway-display-switcher")
+    });
+
+    let mut snippet = vec!["# Managed by sway-display-switcher".to_string()];
+    if !started {
+        snippet.push(RESTORE_LINE.to_string());
+    }
+    if !has(BINDINGS_START) {
+        snippet.push(BINDINGS_START.to_string());
+        snippet.extend(bindings(configs));
+        snippet.push(BINDINGS_END.to_string());
+    }
+    if !has("Display Start") {
+        snippet.push(DISPLAY_START.to_string());
+        snippet.push(DISPLAY_END.to_string());
+    }
+    snippet
+}
+
+// Commands offered by the completion scripts, and those that take a profile
+const COMMANDS: &str =
+    "switch list show diff lint format compose which get plan apply export migrate import position refresh tearing icc gpu detect confirm next test capture visual-edit present power blank lock generate wizard add-output outputs sockets restore recover backups log events names";
+const PROFILE_COMMANDS: &str = "switch show diff compose plan export test";
+
+// A completion script for the given shell. Profile names and aliases are
+// looked up with `names` when completing, so the script never goes stale.
+pub fn completions(shell: &str) -> Option<String> {
+    let script = match shell {
+        "bash" => format!(
+            r#"_sway_display_switcher() {{
+    local cur=${{COMP_WORDS[COMP_CWORD]}}
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+        return
+    fi
+    case ${{COMP_WORDS[1]}} in
+        {profile_cases})
+            local IFS=$'\n'
+            COMPREPLY=($(compgen -W "$(sway-display-switcher names 2>/dev/null)" -- "$cur"))
+            COMPREPLY=("${{COMPREPLY[@]// /\\ }}")
+            ;;
+    esac
+}}
+complete -F _sway_display_switcher sway-display-switcher
+"#,
+            commands = COMMANDS,
+            profile_cases = PROFILE_COMMANDS.replace(' ', "|"),
+        ),
+        "zsh" => format!(
+            r#"#compdef sway-display-switcher
+if (( CURRENT == 2 )); then
+    compadd {commands}
+else
+    case $words[2] in
+        {profile_cases})
+            local -a names
+            names=("${{(@f)$(sway-display-switcher names 2>/dev/null)}}")
+            compadd -a names
+            ;;
+    esac
+fi
+"#,
+            commands = COMMANDS,
+            profile_cases = PROFILE_COMMANDS.replace(' ', "|"),
+        ),
+        "fish" => format!(
+            r#"complete -c sway-display-switcher -f
+complete -c sway-display-switcher -n __fish_use_subcommand -a "{commands}"
+complete -c sway-display-switcher -n "__fish_seen_subcommand_from {profile_commands}" -a "(sway-display-switcher names 2>/dev/null)"
+"#,
+            commands = COMMANDS,
+            profile_commands = PROFILE_COMMANDS,
+        ),
+        _ => return None,
+    };
+    Some(script)
+}
+
+// File names of the generated udev rule and the helper script it runs
+pub const UDEV_RULE_FILE: &str = "99-sway-display-switcher.rules";
+pub const UDEV_HELPER_FILE: &str = "sway-display-switcher-udev";
+
+// A udev rule running the helper whenever a DRM device reports a change, such
+// as a monitor being plugged in or unplugged
+pub fn udev_rule(helper: &Path) -> String {
+    format!(
+        "# Switch Sway display profiles when monitors are plugged in or unplugged\n\
+         ACTION==\"change\", SUBSYSTEM==\"drm\", RUN+=\"{}\"\n",
+        helper.display()
+    )
+}
+
+// The helper run by the udev rule. udev runs it as root, without a session and
+// kills it after a short while, so it finds `user`'s Sway instances itself and
+// starts the switcher for each in the background with --from-udev.
+pub fn udev_helper(user: &str, binary: &Path) -> String {
+    format!(
+        r#"#!/bin/sh
+# Run by udev on DRM changes; generated by 'sway-display-switcher generate udev'
+user={user}
+uid=$(id -u "$user") || exit 0
+runtime=/run/user/$uid
+for socket in "$runtime"/sway-ipc.*.sock; do
+    [ -S "$socket" ] || continue
+    XDG_RUNTIME_DIR="$runtime" SWAYSOCK="$socket" setsid runuser -u "$user" -- {binary} --from-udev </dev/null >/dev/null 2>&1 &
+done
+exit 0
+"#,
+        user = user,
+        binary = binary.display(),
+    )
+}
+
+// Name of the profile `capture` generates, and the mode it forces
+pub const CAPTURE_PROFILE: &str = "Capture";
+const CAPTURE_MODE: &str = "1920x1080@60Hz";
+
+// A profile for screen capture (OBS, NDI): `base` with `output` forced to
+// exactly 1920x1080 at 60 Hz and scale 1, in its old place. With `headless`, a
+// headless output of the same size is created and put on top of it, as Sway
+// cannot clone outputs. `Restore =` names `base`, for `capture stop`.
+pub fn capture(
+    base: &DisplayConfig,
+    output: &str,
+    connected: &[Output],
+    headless: bool,
+) -> DisplayConfig {
+    let live = connected
+        .iter()
+        .find(|o| o.name == output || o.identifier() == output);
+    let mut profile = base.clone();
+    let mut target = output.to_string();
+    let mut position = live.map(|o| (o.x, o.y));
+    while let Some(index) = profile.find_output_line(output, connected) {
+        if let Some(parsed) = output::OutputLine::parse(&profile.outputs.remove(index)) {
+            position = parsed.position().or(position);
+            target = parsed.target;
+        }
+    }
+    let (x, y) = position.unwrap_or((0, 0));
+    let line = |name: &str| {
+        format!(
+            "output {} enable mode {} pos {} {} scale 1",
+            output::quote(name),
+            CAPTURE_MODE,
+            x,
+            y
+        )
+    };
+    profile.outputs.push(line(&target));
+    if headless {
+        profile
+            .outputs
+            .push("?if-absent HEADLESS-1: create_output".to_string());
+        profile.outputs.push(line("HEADLESS-1"));
+    }
+    profile.description = CAPTURE_PROFILE.to_string();
+    profile.status = "Disabled".to_string();
+    profile.metadata = vec![("Restore".to_string(), base.description.clone())];
+    profile
+}
+
+// The mode a generated profile uses: the current one, or the largest for an
+// output that is switched off
+fn preferred_mode(output: &Output) -> Mode {
+    if output.active || output.modes.is_empty() {
+        return output.current_mode();
+    }
+    output
+        .modes
+        .iter()
+        .copied()
+        .max_by_key(|m| (m.width * m.height, m.refresh))
+        .unwrap_or_else(|| output.current_mode())
+}
+
+// A short human name for an output: "Laptop" for a built-in panel, else its
+// model, with the connector added when several outputs share the model
+fn output_label(output: &Output, connected: &[Output]) -> String {
+    if roles::is_internal(&output.name) {
+        return "Laptop".to_string();
+    }
+    if output.model.is_empty() {
+        return output.name.clone();
+    }
+    if connected.iter().filter(|o| o.model == output.model).count() > 1 {
+        format!("{} ({})", output.model, output.name)
+    } else {
+        output.model.clone()
+    }
+}
+
+// The output to present on: the one named, else the connected external output
+// the Enabled profile does not configure (the one just plugged in), else the
+// only external output
+pub fn presentation_target<'a>(
+    connected: &'a [Output],
+    enabled: Option<&DisplayConfig>,
+    name: Option<&str>,
+) -> Option<&'a Output> {
+    if let Some(name) = name {
+        return connected
+            .iter()
+            .find(|o| o.name == name || o.identifier() == name);
+    }
+    let external = roles::externals(connected);
+    let new: Vec<&Output> = external
+        .iter()
+        .copied()
+        .filter(|o| enabled.is_none_or(|c| c.find_output_line(&o.name, connected).is_none()))
+        .collect();
+    match (new.as_slice(), external.as_slice()) {
+        ([output], _) | (_, [output]) => Some(output),
+        _ => None,
+    }
+}
+
+// A profile mirroring the built-in panel onto `external` at the largest mode
+// both support (each at its highest refresh rate for it) and scale 1, with
+// every other output turned off. None when there is no panel or no common mode.
+pub fn presentation(connected: &[Output], external: &Output) -> Option<DisplayConfig> {
+    let panel = roles::internal(connected)?;
+    let modes = |output: &Output| -> Vec<Mode> {
+        if output.modes.is_empty() {
+            vec![output.current_mode()]
+        } else {
+            output.modes.clone()
+        }
+    };
+    let (panel_modes, external_modes) = (modes(panel), modes(external));
+    let best = |modes: &[Mode], width: i64, height: i64| {
+        modes
+            .iter()
+            .filter(|m| m.width == width && m.height == height)
+            .max_by_key(|m| m.refresh)
+            .copied()
+    };
+    let (panel_mode, external_mode) = panel_modes
+        .iter()
+        .filter_map(|m| {
+            Some((
+                best(&panel_modes, m.width, m.height)?,
+                best(&external_modes, m.width, m.height)?,
+            ))
+        })
+        .max_by_key(|(m, _)| m.width * m.height)?;
+
+    let mut outputs = vec![
+        enable_line(panel, &panel_mode, 1.0, 0, 0),
+        enable_line(external, &external_mode, 1.0, 0, 0),
+    ];
+    for output in connected {
+        if output.name != panel.name && output.name != external.name {
+            outputs.push(format!("output {} disable", output::quote(&output.name)));
+        }
+    }
+    Some(DisplayConfig {
+        description: tr!("presenting-profile", external.name),
+        outputs,
+        status: "Disabled".to_string(),
+        metadata: Vec::new(),
+    })
+}
+
+// Lines putting the outputs back the way they are now
+pub fn snapshot(connected: &[Output]) -> Vec<String> {
+    connected
+        .iter()
+        .map(|output| {
+            if !output.active {
+                return format!("output {} disable", output::quote(&output.name));
+            }
+            let mut line = enable_line(
+                output,
+                &output.current_mode(),
+                output.scale,
+                output.x,
+                output.y,
+            );
+            if !output.transform.is_empty() && output.transform != "normal" {
+                line.push_str(&format!(" transform {}", output.transform));
+            }
+            line
+        })
+        .collect()
+}
+
+// The profile brought in line with the outputs as they are now, e.g. after a
+// visual editor rearranged them. The last line for each connected output gets
+// the live mode, position, scale and transform where they differ, or becomes a
+// plain enable or disable line, and outputs the profile leaves out are added,
+// all with `?identity` lines.
+pub fn recapture(profile: &DisplayConfig, connected: &[Output]) -> DisplayConfig {
+    let mut updated = profile.clone();
+    for output in connected {
+        let last = updated.outputs.iter().rposition(|line| {
+            OutputLine::parse(line)
+                .is_some_and(|parsed| !parsed.is_wildcard() && output.matches(&parsed.target))
+        });
+        let Some((index, parsed)) =
+            last.and_then(|index| Some((index, OutputLine::parse(&updated.outputs[index])?)))
+        else {
+            updated
+                .outputs
+                .extend(snapshot(std::slice::from_ref(output)));
+            continue;
+        };
+        let target = output::quote(&parsed.target);
+        if !output.active {
+            if !parsed.is_disabled() {
+                updated.outputs[index] = format!("output {} disable", target);
+            }
+            continue;
+        }
+        let mut line = if parsed.is_disabled() {
+            format!("output {} enable", target)
+        } else {
+            updated.outputs[index].clone()
+        };
+        let Some(parsed) = OutputLine::parse(&line) else {
+            continue;
+        };
+        let mode = output.current_mode();
+        let mode_changed = match parsed.mode() {
+            Some(wanted) => !output.shows_mode(&wanted),
+            None => mode != preferred_mode(output),
+        };
+        if mode_changed {
+            line = output::set_attribute(&line, "mode", &[mode.to_string()]);
+        }
+        if parsed.position() != Some((output.x, output.y)) {
+            let position = [output.x.to_string(), output.y.to_string()];
+            line = output::set_attribute(&line, "pos", &position);
+        }
+        if (parsed.scale().unwrap_or(1.0) - output.scale).abs() >= 0.001 {
+            line = output::set_attribute(&line, "scale", &[output.scale.to_string()]);
+        }
+        let transform = match output.transform.as_str() {
+            "" => "normal",
+            transform => transform,
+        };
+        if parsed.transform().unwrap_or("normal") != transform {
+            line = output::set_attribute(&line, "transform", &[transform.to_string()]);
+        }
+        updated.outputs[index] = line;
+    }
+    migrate::add_identities(&mut updated, connected);
+    updated
+}
+
+// An `output` line enabling an output at the given position
+fn enable_line(output: &Output, mode: &Mode, scale: f64, x: i64, y: i64) -> String {
+    format!(
+        "output {} enable mode {} pos {} {} scale {}",
+        output::quote(&output.name),
+        mode,
+        x,
+        y,
+        scale
+    )
+}
+
+// Lines enabling `order` side by side (each on the given side of the previous
+// one) and disabling every other connected output
+fn row(connected: &[Output], order: &[&Output], side: Side) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut previous: Option<Rect> = None;
+    let mut rects = Vec::new();
+    for output in order {
+        let mode = preferred_mode(output);
+        let scale = scale::suggest(output, &mode).unwrap_or(1.0);
+        let mut rect = Rect::logical(0, 0, mode.width, mode.height, scale, false);
+        if let Some(anchor) = &previous {
+            (rect.x, rect.y) = rect.placed(anchor, side);
+        }
+        previous = Some(rect);
+        rects.push((output, mode, scale, rect));
+    }
+    // Keep every position non-negative
+    let min_x = rects.iter().map(|(_, _, _, r)| r.x).min().unwrap_or(0);
+    for (output, mode, scale, rect) in &rects {
+        lines.push(enable_line(output, mode, *scale, rect.x - min_x, rect.y));
+    }
+    for output in connected {
+        if !order.iter().any(|o| o.name == output.name) {
+            lines.push(format!("output {} disable", output::quote(&output.name)));
+        }
+    }
+    lines
+}
+
+// A starting set of profiles for the connected outputs: each output alone,
+// mirrored, extended to either side of the built-in panel, and all outputs as
+// currently laid out. All are Disabled; names already in `existing` are skipped.
+pub fn matrix(connected: &[Output], existing: &[DisplayConfig]) -> Vec<DisplayConfig> {
+    let mut profiles: Vec<(String, Vec<String>)> = Vec::new();
+
+    for output in connected {
+        profiles.push((
+            format!("{} only", output_label(output, connected)),
+            row(connected, &[output], Side::Right),
+        ));
+    }
+
+    if connected.len() > 1 {
+        // The built-in panel (or else the first output) anchors the layouts
+        let mut ordered: Vec<&Output> = connected.iter().collect();
+        ordered.sort_by_key(|o| !roles::is_internal(&o.name));
+
+        // Sway cannot clone outputs, so mirroring places them all at 0,0
+        let mirror = ordered
+            .iter()
+            .map(|output| {
+                let mode = preferred_mode(output);
+                enable_line(
+                    output,
+                    &mode,
+                    scale::suggest(output, &mode).unwrap_or(1.0),
+                    0,
+                    0,
+                )
+            })
+            .collect();
+        profiles.push(("Mirror".to_string(), mirror));
+        profiles.push((
+            "Extend right".to_string(),
+            row(connected, &ordered, Side::Right),
+        ));
+        profiles.push((
+            "Extend left".to_string(),
+            row(connected, &ordered, Side::Left),
+        ));
+
+        // Active outputs keep their place; the others are lined up to the right
+        let mut right_edge = connected
+            .iter()
+            .filter(|o| o.active)
+            .map(|o| o.x + Rect::logical(0, 0, o.width, o.height, o.scale, false).width)
+            .max()
+            .unwrap_or(0);
+        let mut all_on = Vec::new();
+        for output in connected {
+            let mode = preferred_mode(output);
+            if output.active {
+                all_on.push(enable_line(output, &mode, output.scale, output.x, output.y));
+            } else {
+                let scale = scale::suggest(output, &mode).unwrap_or(1.0);
+                all_on.push(enable_line(output, &mode, scale, right_edge, 0));
+                right_edge += Rect::logical(0, 0, mode.width, mode.height, scale, false).width;
+            }
+        }
+        profiles.push(("All on".to_string(), all_on));
+    }
+
+    profiles
+        .into_iter()
+        .filter(|(name, _)| {
+            !existing
+                .iter()
+                .any(|config| config.description.eq_ignore_ascii_case(name))
+        })
+        .map(|(description, outputs)| {
+            let mut profile = DisplayConfig {
+                description,
+                outputs,
+                status: "Disabled".to_string(),
+                metadata: Vec::new(),
+            };
+            migrate::add_identities(&mut profile, connected);
+            profile
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+// GPUs as seen in sysfs, for profiles that depend on an external GPU and for
+// taking its outputs down before it is unplugged
+
+use crate::sway::Output;
+use std::fs;
+use std::path::Path;
+
+// Where the kernel lists DRM devices and their connectors
+const DRM: &str = "/sys/class/drm";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gpu {
+    // The DRM device name, e.g. `card1`; it changes when GPUs come and go
+    pub card: String,
+    // The PCI address, e.g. `0000:0a:00.0`, which stays the same
+    pub slot: String,
+    // PCI vendor and device ID, e.g. `1002:73df`
+    pub id: String,
+    // The GPU the firmware booted with; every other one counts as external
+    pub boot: bool,
+}
+
+impl Gpu {
+    // Whether a `Gpu =` value refers to this GPU: `external`, `internal`, its
+    // card, PCI address or vendor:device ID
+    pub fn matches(&self, query: &str) -> bool {
+        match query.to_ascii_lowercase().as_str() {
+            "external" | "egpu" => !self.boot,
+            "internal" => self.boot,
+            query => query == self.card || query == self.slot || query == self.id,
+        }
+    }
+}
+
+// The GPUs present now, by card number
+pub fn gpus() -> Vec<Gpu> {
+    let Ok(entries) = fs::read_dir(DRM) else {
+        return Vec::new();
+    };
+    let mut gpus: Vec<Gpu> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let card = entry.file_name().to_string_lossy().to_string();
+            let number = card.strip_prefix("card")?;
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let device = entry.path().join("device");
+            let read = |name: &str| {
+                fs::read_to_string(device.join(name))
+                    .map(|text| text.trim().trim_start_matches("0x").to_string())
+                    .unwrap_or_default()
+            };
+            let slot = fs::canonicalize(&device)
+                .ok()
+                .and_then(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            Some(Gpu {
+                slot,
+                id: format!("{}:{}", read("vendor"), read("device")),
+                boot: read("boot_vga") == "1",
+                card,
+            })
+        })
+        .collect();
+    gpus.sort_by_key(|gpu| gpu.card[4..].parse::<u32>().unwrap_or(u32::MAX));
+    // Without a VGA boot device (e.g. on ARM), the first card is the built-in one
+    if !gpus.iter().any(|gpu| gpu.boot) {
+        if let Some(first) = gpus.first_mut() {
+            first.boot = true;
+        }
+    }
+    gpus
+}
+
+// The card an output's connector belongs to, from the `card1-DP-3` style
+// connector entries
+pub fn card_of(output: &Output) -> Option<String> {
+    let suffix = format!("-{}", output.name);
+    fs::read_dir(DRM)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .find_map(|name| Some(name.strip_suffix(&suffix)?.to_string()))
+        .filter(|card| Path::new(DRM).join(card).exists())
+}
+
+// Connected outputs driven by a GPU other than the boot one
+pub fn external_outputs<'a>(gpus: &[Gpu], connected: &'a [Output]) -> Vec<&'a Output> {
+    connected
+        .iter()
+        .filter(|output| {
+            card_of(output).is_some_and(|card| gpus.iter().any(|gpu| gpu.card == card && !gpu.boot))
+        })
+        .collect()
+}
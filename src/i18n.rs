@@ -0,0 +1,4638 @@
+// Translated prompts, errors and headers, with the language taken from the locale
+//
+// Messages are looked up by id in a built-in catalog. Each entry holds the
+// English text followed by its translations; `{}` placeholders are filled in
+// order by the `tr!` macro, and `{0}`, `{1}`... name an argument by position so
+// that a translation can put them in another order.
+
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+impl Language {
+    // Map a locale name such as `de_DE.UTF-8` or `fr` to a supported language
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let code = locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match code.as_str() {
+            "en" | "c" | "posix" => Some(Language::English),
+            "es" => Some(Language::Spanish),
+            "de" => Some(Language::German),
+            "fr" => Some(Language::French),
+            _ => None,
+        }
+    }
+
+    fn column(self) -> usize {
+        match self {
+            Language::English => 0,
+            Language::Spanish => 1,
+            Language::German => 2,
+            Language::French => 3,
+        }
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+// The language for messages, chosen like gettext: LC_ALL, then LC_MESSAGES,
+// then LANG. Unsupported or unset locales fall back to English.
+pub fn language() -> Language {
+    *LANGUAGE.get_or_init(|| {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_locale(&value))
+            .unwrap_or(Language::English)
+    })
+}
+
+// Look up a message in the current language and fill in its placeholders
+pub fn tr(id: &str, args: &[&dyn Display]) -> String {
+    let template = CATALOG
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, texts)| texts[language().column()])
+        .unwrap_or(id);
+    fill(template, args)
+}
+
+// Replace the placeholders in `template`. Ones without an argument are left as
+// they are, and braces around anything else are plain text.
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut message = String::new();
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest.find('}').and_then(|end| {
+            let index = match &rest[1..end] {
+                "" => {
+                    next += 1;
+                    next - 1
+                }
+                digits if digits.bytes().all(|b| b.is_ascii_digit()) => digits.parse().ok()?,
+                _ => return None,
+            };
+            Some((index, end))
+        });
+        match placeholder {
+            Some((index, end)) => {
+                match args.get(index) {
+                    Some(arg) => message.push_str(&arg.to_string()),
+                    None => message.push_str(&rest[..=end]),
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+// Translate a message id, e.g. `tr!("no-such-profile", query)`
+#[macro_export]
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::tr($id, &[])
+    };
+    ($id:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::tr($id, &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+// Message id followed by the English, Spanish, German and French texts
+const CATALOG: &[(&str, [&str; 4])] = &[
+    // Listing and selection
+    (
+        "connected-header",
+        [
+            "Connected outputs:",
+            "Salidas conectadas:",
+            "Angeschlossene Ausgänge:",
+            "Sorties connectées :",
+        ],
+    ),
+    (
+        "inactive",
+        ["inactive", "inactiva", "inaktiv", "inactive"],
+    ),
+    (
+        "outputs-connected",
+        [
+            "{}/{} connected",
+            "{}/{} conectadas",
+            "{}/{} angeschlossen",
+            "{}/{} connectées",
+        ],
+    ),
+    (
+        "outputs-configured",
+        ["outputs: {}", "salidas: {}", "Ausgänge: {}", "sorties : {}"],
+    ),
+    (
+        "current-active",
+        [
+            "Current active configuration: {}",
+            "Configuración activa actual: {}",
+            "Aktuell aktive Konfiguration: {}",
+            "Configuration active : {}",
+        ],
+    ),
+    (
+        "none-enabled",
+        [
+            "No configuration is currently enabled.",
+            "No hay ninguna configuración activada.",
+            "Derzeit ist keine Konfiguration aktiviert.",
+            "Aucune configuration n'est activée.",
+        ],
+    ),
+    (
+        "available-header",
+        [
+            "Available display configurations:",
+            "Configuraciones de pantalla disponibles:",
+            "Verfügbare Anzeigekonfigurationen:",
+            "Configurations d'affichage disponibles :",
+        ],
+    ),
+    (
+        "select-profile",
+        [
+            "Enter the number of the configuration you want to activate, or 'q' to quit:",
+            "Introduzca el número de la configuración que desea activar, o 'q' para salir:",
+            "Geben Sie die Nummer der zu aktivierenden Konfiguration ein oder 'q' zum Beenden:",
+            "Saisissez le numéro de la configuration à activer, ou 'q' pour quitter :",
+        ],
+    ),
+    (
+        "invalid-profile-selection",
+        [
+            "Invalid selection. Please enter a number between 1 and {}, or 'q' to quit.",
+            "Selección no válida. Introduzca un número entre 1 y {}, o 'q' para salir.",
+            "Ungültige Auswahl. Bitte geben Sie eine Zahl zwischen 1 und {} ein oder 'q' zum Beenden.",
+            "Sélection invalide. Saisissez un nombre entre 1 et {}, ou 'q' pour quitter.",
+        ],
+    ),
+    (
+        "prompt-timed-out",
+        [
+            "No answer within {} seconds; applying '{}'.",
+            "Sin respuesta en {} segundos; aplicando '{}'.",
+            "Keine Antwort innerhalb von {} Sekunden; '{}' wird angewendet.",
+            "Aucune réponse en {} secondes ; application de '{}'.",
+        ],
+    ),
+    (
+        "select-socket",
+        [
+            "Enter the number of the Sway instance to manage, or 'q' to quit:",
+            "Introduzca el número de la instancia de Sway que desea gestionar, o 'q' para salir:",
+            "Geben Sie die Nummer der zu verwaltenden Sway-Instanz ein oder 'q' zum Beenden:",
+            "Saisissez le numéro de l'instance Sway à gérer, ou 'q' pour quitter :",
+        ],
+    ),
+    (
+        "invalid-selection",
+        [
+            "Invalid selection.",
+            "Selección no válida.",
+            "Ungültige Auswahl.",
+            "Sélection invalide.",
+        ],
+    ),
+    (
+        "multiple-instances",
+        [
+            "Multiple Sway instances are running:",
+            "Hay varias instancias de Sway en ejecución:",
+            "Es laufen mehrere Sway-Instanzen:",
+            "Plusieurs instances de Sway sont en cours d'exécution :",
+        ],
+    ),
+    (
+        "exiting",
+        [
+            "Exiting without making changes.",
+            "Saliendo sin hacer cambios.",
+            "Beenden ohne Änderungen.",
+            "Sortie sans modification.",
+        ],
+    ),
+    // Prompts
+    ("yes", ["y", "s", "j", "o"]),
+    ("no", ["n", "n", "n", "n"]),
+    (
+        "answer-yes-no",
+        [
+            "Please answer y or n.",
+            "Responda s o n.",
+            "Bitte mit j oder n antworten.",
+            "Répondez par o ou n.",
+        ],
+    ),
+    (
+        "number-range",
+        [
+            "Please enter a number between 1 and {}.",
+            "Introduzca un número entre 1 y {}.",
+            "Bitte geben Sie eine Zahl zwischen 1 und {} ein.",
+            "Saisissez un nombre entre 1 et {}.",
+        ],
+    ),
+    // Results
+    (
+        "applied-transient",
+        [
+            "Applied '{}' transiently; the next Sway reload will revert it.",
+            "'{}' aplicado de forma temporal; la próxima recarga de Sway lo revertirá.",
+            "'{}' vorübergehend angewendet; das nächste Neuladen von Sway macht es rückgängig.",
+            "'{}' appliqué temporairement ; le prochain rechargement de Sway l'annulera.",
+        ],
+    ),
+    (
+        "apply-failed",
+        [
+            "Failed to apply configuration via swaymsg.",
+            "No se pudo aplicar la configuración mediante swaymsg.",
+            "Die Konfiguration konnte nicht über swaymsg angewendet werden.",
+            "Échec de l'application de la configuration via swaymsg.",
+        ],
+    ),
+    (
+        "restore-failed",
+        [
+            "Failed to restore '{}'.",
+            "No se pudo restaurar '{}'.",
+            "'{}' konnte nicht wiederhergestellt werden.",
+            "Échec de la restauration de '{}'.",
+        ],
+    ),
+    (
+        "saved-profile",
+        [
+            "Saved profile '{}'.",
+            "Perfil '{}' guardado.",
+            "Profil '{}' gespeichert.",
+            "Profil '{}' enregistré.",
+        ],
+    ),
+    // Errors
+    ("error", ["Error: {}", "Error: {}", "Fehler: {}", "Erreur : {}"]),
+    (
+        "multiple-instances-error",
+        [
+            "multiple Sway instances are running; choose one with --socket:",
+            "hay varias instancias de Sway en ejecución; elija una con --socket:",
+            "Es laufen mehrere Sway-Instanzen; wählen Sie eine mit --socket aus:",
+            "plusieurs instances de Sway sont en cours d'exécution ; choisissez-en une avec --socket :",
+        ],
+    ),
+    (
+        "no-dialog",
+        [
+            "no terminal is attached for input and neither zenity nor yad is installed.",
+            "no hay ninguna terminal conectada para la entrada y no está instalado zenity ni yad.",
+            "Kein Terminal für die Eingabe verbunden, und weder zenity noch yad ist installiert.",
+            "aucun terminal n'est attaché en entrée et ni zenity ni yad n'est installé.",
+        ],
+    ),
+    (
+        "no-dialog-hint",
+        [
+            "Use 'switch <profile>' to select a profile non-interactively, pipe its number or name in, or read the profiles with 'list --json'.",
+            "Use 'switch <perfil>' para seleccionar un perfil de forma no interactiva, pase su número o nombre por una tubería, o consulte los perfiles con 'list --json'.",
+            "Verwenden Sie 'switch <Profil>', um ein Profil nicht-interaktiv auszuwählen, übergeben Sie seine Nummer oder seinen Namen über eine Pipe, oder lesen Sie die Profile mit 'list --json'.",
+            "Utilisez 'switch <profil>' pour choisir un profil de manière non interactive, transmettez son numéro ou son nom par un tube, ou lisez les profils avec 'list --json'.",
+        ],
+    ),
+    (
+        "no-enabled",
+        [
+            "no configuration is currently enabled.",
+            "no hay ninguna configuración activada.",
+            "Derzeit ist keine Konfiguration aktiviert.",
+            "aucune configuration n'est activée.",
+        ],
+    ),
+    (
+        "profile-required",
+        [
+            "a profile name or number is required.",
+            "se requiere un nombre o número de perfil.",
+            "Ein Profilname oder eine Profilnummer ist erforderlich.",
+            "un nom ou un numéro de profil est requis.",
+        ],
+    ),
+    (
+        "no-such-profile",
+        [
+            "no profile named '{}'.",
+            "no existe ningún perfil llamado '{}'.",
+            "Kein Profil namens '{}'.",
+            "aucun profil nommé '{}'.",
+        ],
+    ),
+    (
+        "unknown-command",
+        [
+            "unknown command '{}'.",
+            "orden desconocida '{}'.",
+            "Unbekannter Befehl '{}'.",
+            "commande inconnue '{}'.",
+        ],
+    ),
+    (
+        "outputs-query-failed",
+        [
+            "failed to query outputs from Sway.",
+            "no se pudieron consultar las salidas de Sway.",
+            "Die Ausgaben konnten nicht von Sway abgefragt werden.",
+            "impossible d'interroger les sorties de Sway.",
+        ],
+    ),
+    (
+        "no-match",
+        [
+            "no profile is enabled and none matches the connected outputs.",
+            "no hay ningún perfil activado y ninguno coincide con las salidas conectadas.",
+            "Kein Profil ist aktiviert, und keines passt zu den angeschlossenen Ausgaben.",
+            "aucun profil n'est activé et aucun ne correspond aux sorties connectées.",
+        ],
+    ),
+    (
+        "already-active",
+        [
+            "'{}' is already active; nothing to do.",
+            "'{}' ya está activo; no hay nada que hacer.",
+            "'{}' ist bereits aktiv; nichts zu tun.",
+            "'{}' est déjà actif ; rien à faire.",
+        ],
+    ),
+    (
+        "no-fit",
+        [
+            "no profile matches the connected outputs.",
+            "ningún perfil coincide con las salidas conectadas.",
+            "Kein Profil passt zu den angeschlossenen Ausgaben.",
+            "aucun profil ne correspond aux sorties connectées.",
+        ],
+    ),
+    (
+        "no-plan",
+        [
+            "no saved plan found. Run 'plan <profile>' first.",
+            "no se encontró ningún plan guardado. Ejecute primero 'plan <perfil>'.",
+            "Kein gespeicherter Plan gefunden. Führen Sie zuerst 'plan <Profil>' aus.",
+            "aucun plan enregistré. Exécutez d'abord 'plan <profil>'.",
+        ],
+    ),
+    (
+        "marker-missing",
+        [
+            "'{}' marker not found in the config file.",
+            "no se encontró el marcador '{}' en el archivo de configuración.",
+            "Markierung '{}' nicht in der Konfigurationsdatei gefunden.",
+            "marqueur '{}' introuvable dans le fichier de configuration.",
+        ],
+    ),
+    (
+        "markers-order",
+        [
+            "'Display End' comes before 'Display Start' in the config file.",
+            "'Display End' aparece antes de 'Display Start' en el archivo de configuración.",
+            "'Display End' steht in der Konfigurationsdatei vor 'Display Start'.",
+            "'Display End' précède 'Display Start' dans le fichier de configuration.",
+        ],
+    ),
+    // Dialog
+    (
+        "dialog-title",
+        [
+            "Display profiles",
+            "Perfiles de pantalla",
+            "Anzeigeprofile",
+            "Profils d'affichage",
+        ],
+    ),
+    (
+        "dialog-text",
+        [
+            "Select the display configuration to activate",
+            "Seleccione la configuración de pantalla que desea activar",
+            "Wählen Sie die zu aktivierende Anzeigekonfiguration",
+            "Sélectionnez la configuration d'affichage à activer",
+        ],
+    ),
+    (
+        "window-switch",
+        [
+            "Switch",
+            "Cambiar",
+            "Wechseln",
+            "Changer",
+        ],
+    ),
+    (
+        "window-active",
+        [
+            "Active",
+            "Activo",
+            "Aktiv",
+            "Actif",
+        ],
+    ),
+    (
+        "window-switched",
+        [
+            "Switched to '{}'.",
+            "Se cambió a '{}'.",
+            "Zu '{}' gewechselt.",
+            "Passé à '{}'.",
+        ],
+    ),
+    (
+        "window-failed",
+        [
+            "the window could not be opened.",
+            "no se pudo abrir la ventana.",
+            "Das Fenster konnte nicht geöffnet werden.",
+            "impossible d'ouvrir la fenêtre.",
+        ],
+    ),
+    ("column-profile", ["Profile", "Perfil", "Profil", "Profil"]),
+    ("column-status", ["Status", "Estado", "Status", "État"]),
+    // Wizard
+    (
+        "no-outputs",
+        [
+            "Sway reports no connected outputs.",
+            "Sway no informa de ninguna salida conectada.",
+            "Sway meldet keine angeschlossenen Ausgaben.",
+            "Sway ne signale aucune sortie connectée.",
+        ],
+    ),
+    (
+        "enable-output",
+        [
+            "Enable this output?",
+            "¿Activar esta salida?",
+            "Diese Ausgabe aktivieren?",
+            "Activer cette sortie ?",
+        ],
+    ),
+    (
+        "all-disabled",
+        [
+            "Warning: every output is disabled in this profile.",
+            "Aviso: todas las salidas están desactivadas en este perfil.",
+            "Warnung: In diesem Profil sind alle Ausgaben deaktiviert.",
+            "Avertissement : toutes les sorties sont désactivées dans ce profil.",
+        ],
+    ),
+    (
+        "layout-preview",
+        [
+            "Layout preview:",
+            "Vista previa de la disposición:",
+            "Layout-Vorschau:",
+            "Aperçu de la disposition :",
+        ],
+    ),
+    (
+        "profile-lines",
+        [
+            "Profile lines:",
+            "Líneas del perfil:",
+            "Profilzeilen:",
+            "Lignes du profil :",
+        ],
+    ),
+    (
+        "profile-name",
+        [
+            "Name for the new profile",
+            "Nombre del nuevo perfil",
+            "Name des neuen Profils",
+            "Nom du nouveau profil",
+        ],
+    ),
+    (
+        "name-invalid",
+        [
+            "Please enter a non-empty name without commas.",
+            "Introduzca un nombre no vacío y sin comas.",
+            "Bitte geben Sie einen nicht leeren Namen ohne Kommas ein.",
+            "Saisissez un nom non vide et sans virgule.",
+        ],
+    ),
+    (
+        "available-modes",
+        [
+            "Available modes:",
+            "Modos disponibles:",
+            "Verfügbare Modi:",
+            "Modes disponibles :",
+        ],
+    ),
+    ("mode", ["Mode", "Modo", "Modus", "Mode"]),
+    (
+        "dpi-suggestion",
+        [
+            "{} DPI at this mode; suggested scale {}.",
+            "{} DPI en este modo; escala sugerida {}.",
+            "{} DPI in diesem Modus; empfohlene Skalierung {}.",
+            "{} DPI dans ce mode ; échelle suggérée {}.",
+        ],
+    ),
+    ("scale", ["Scale", "Escala", "Skalierung", "Échelle"]),
+    (
+        "scale-invalid",
+        [
+            "Please enter a positive number such as 1 or 1.5.",
+            "Introduzca un número positivo, como 1 o 1.5.",
+            "Bitte geben Sie eine positive Zahl wie 1 oder 1.5 ein.",
+            "Saisissez un nombre positif, par exemple 1 ou 1.5.",
+        ],
+    ),
+    (
+        "place-relative",
+        [
+            "Place relative to which output?",
+            "¿Respecto a qué salida colocarla?",
+            "Relativ zu welcher Ausgabe platzieren?",
+            "Placer par rapport à quelle sortie ?",
+        ],
+    ),
+    ("output", ["Output", "Salida", "Ausgabe", "Sortie"]),
+    (
+        "position-relative",
+        [
+            "Position relative to {} (left/right/above/below)",
+            "Posición respecto a {} (left/right/above/below)",
+            "Position relativ zu {} (left/right/above/below)",
+            "Position par rapport à {} (left/right/above/below)",
+        ],
+    ),
+    (
+        "side-invalid",
+        [
+            "Please enter left, right, above or below.",
+            "Introduzca left, right, above o below.",
+            "Bitte geben Sie left, right, above oder below ein.",
+            "Saisissez left, right, above ou below.",
+        ],
+    ),
+    (
+        "activate-now",
+        [
+            "Activate it now?",
+            "¿Activarlo ahora?",
+            "Jetzt aktivieren?",
+            "L'activer maintenant ?",
+        ],
+    ),
+    // Switching
+    (
+        "warning",
+        [
+            "Warning: {}",
+            "Advertencia: {}",
+            "Warnung: {}",
+            "Avertissement : {}",
+        ],
+    ),
+    (
+        "write-failed",
+        [
+            "failed to write {}: {}",
+            "no se pudo escribir {}: {}",
+            "{} konnte nicht geschrieben werden: {}",
+            "impossible d'écrire {} : {}",
+        ],
+    ),
+    (
+        "blackout",
+        [
+            "'{}' would turn off every connected output; use --force to apply it anyway.",
+            "'{}' apagaría todas las salidas conectadas; use --force para aplicarlo de todos modos.",
+            "'{}' würde alle angeschlossenen Ausgänge ausschalten; verwenden Sie --force, um es trotzdem anzuwenden.",
+            "'{}' éteindrait toutes les sorties connectées ; utilisez --force pour l'appliquer quand même.",
+        ],
+    ),
+    (
+        "reload-rejected",
+        [
+            "Sway could not reload the config.",
+            "Sway no pudo recargar la configuración.",
+            "Sway konnte die Konfiguration nicht neu laden.",
+            "Sway n'a pas pu recharger la configuration.",
+        ],
+    ),
+    (
+        "conditional-rejected",
+        [
+            "Sway rejected a conditional output line.",
+            "Sway rechazó una línea de salida condicional.",
+            "Sway hat eine bedingte Ausgabezeile abgelehnt.",
+            "Sway a rejeté une ligne de sortie conditionnelle.",
+        ],
+    ),
+    (
+        "safe-mode-failed",
+        [
+            "An output took neither its mode nor a safe mode.",
+            "Una salida no aceptó ni su modo ni un modo seguro.",
+            "Eine Ausgabe hat weder ihren Modus noch einen sicheren Modus übernommen.",
+            "Une sortie n'a accepté ni son mode ni un mode sûr.",
+        ],
+    ),
+    (
+        "safe-mode-used",
+        [
+            "{} did not take {}; using {} instead.",
+            "{} no aceptó {}; se usa {} en su lugar.",
+            "{} hat {} nicht übernommen; stattdessen wird {} verwendet.",
+            "{} n'a pas accepté {} ; {} est utilisé à la place.",
+        ],
+    ),
+    (
+        "safe-mode-none",
+        [
+            "{} took neither {} nor a safe mode.",
+            "{} no aceptó ni {} ni un modo seguro.",
+            "{} hat weder {} noch einen sicheren Modus übernommen.",
+            "{} n'a accepté ni {} ni un mode sûr.",
+        ],
+    ),
+    // Lint findings
+    (
+        "lint-priority",
+        [
+            "Priority '{}' is not a whole number and counts as 0",
+            "la prioridad '{}' no es un número entero y cuenta como 0",
+            "Priorität '{}' ist keine ganze Zahl und zählt als 0",
+            "la priorité '{}' n'est pas un nombre entier et compte pour 0",
+        ],
+    ),
+    (
+        "lint-needs-sway",
+        [
+            "{} needs Sway {}.{} or newer; this is Sway {}",
+            "{} requiere Sway {}.{} o posterior; esta es Sway {}",
+            "{} erfordert Sway {}.{} oder neuer; dies ist Sway {}",
+            "{} nécessite Sway {}.{} ou plus récent ; ceci est Sway {}",
+        ],
+    ),
+    (
+        "lint-needs-sway-dpms",
+        [
+            "{} needs Sway {}.{} or newer; this is Sway {}, which calls it dpms",
+            "{} requiere Sway {}.{} o posterior; esta es Sway {}, que lo llama dpms",
+            "{} erfordert Sway {}.{} oder neuer; dies ist Sway {}, das es dpms nennt",
+            "{} nécessite Sway {}.{} ou plus récent ; ceci est Sway {}, qui l'appelle dpms",
+        ],
+    ),
+    (
+        "lint-repeat",
+        [
+            "repeats an earlier line",
+            "repite una línea anterior",
+            "wiederholt eine frühere Zeile",
+            "répète une ligne précédente",
+        ],
+    ),
+    (
+        "lint-second-line",
+        [
+            "a second line for {}; merge it into the earlier one",
+            "una segunda línea para {}; combínela con la anterior",
+            "eine zweite Zeile für {}; führen Sie sie mit der früheren zusammen",
+            "une deuxième ligne pour {} ; fusionnez-la avec la précédente",
+        ],
+    ),
+    (
+        "lint-duplicate",
+        [
+            "has the same lines as '{}'",
+            "tiene las mismas líneas que '{}'",
+            "hat dieselben Zeilen wie '{}'",
+            "a les mêmes lignes que '{}'",
+        ],
+    ),
+    (
+        "lint-xwayland",
+        [
+            "scale {} blurs Xwayland windows",
+            "la escala {} desenfoca las ventanas de Xwayland",
+            "Skalierung {} macht Xwayland-Fenster unscharf",
+            "l'échelle {} rend floues les fenêtres Xwayland",
+        ],
+    ),
+    (
+        "lint-wildcard-off",
+        [
+            "turns off {}, which an earlier line enables",
+            "apaga {}, que una línea anterior activa",
+            "schaltet {} aus, das eine frühere Zeile einschaltet",
+            "éteint {}, qu'une ligne précédente active",
+        ],
+    ),
+    (
+        "lint-wildcard-overrides",
+        [
+            "overrides the {} an earlier line sets for {}; move it before that line",
+            "anula {} que una línea anterior fija para {}; muévala antes de esa línea",
+            "überschreibt {}, das eine frühere Zeile für {} setzt; verschieben Sie sie vor diese Zeile",
+            "remplace {} qu'une ligne précédente définit pour {} ; placez-la avant cette ligne",
+        ],
+    ),
+    (
+        "lint-not-command",
+        [
+            "'{}' is not a Sway command",
+            "'{}' no es una orden de Sway",
+            "'{}' ist kein Sway-Befehl",
+            "'{}' n'est pas une commande Sway",
+        ],
+    ),
+    (
+        "lint-no-name",
+        [
+            "missing output name",
+            "falta el nombre de la salida",
+            "Ausgabename fehlt",
+            "nom de sortie manquant",
+        ],
+    ),
+    (
+        "lint-no-subcommand",
+        [
+            "no subcommand after the output name",
+            "no hay ninguna suborden tras el nombre de la salida",
+            "kein Unterbefehl nach dem Ausgabenamen",
+            "aucune sous-commande après le nom de la sortie",
+        ],
+    ),
+    (
+        "lint-unknown-subcommand",
+        [
+            "unknown subcommand '{}'",
+            "suborden desconocida '{}'",
+            "unbekannter Unterbefehl '{}'",
+            "sous-commande inconnue '{}'",
+        ],
+    ),
+    (
+        "lint-mode-missing",
+        [
+            "mode needs a WIDTHxHEIGHT value",
+            "mode requiere un valor WIDTHxHEIGHT",
+            "mode erfordert einen Wert WIDTHxHEIGHT",
+            "mode nécessite une valeur WIDTHxHEIGHT",
+        ],
+    ),
+    (
+        "lint-mode-invalid",
+        [
+            "'{}' is not a WIDTHxHEIGHT[@RATEHz] mode",
+            "'{}' no es un modo WIDTHxHEIGHT[@RATEHz]",
+            "'{}' ist kein Modus der Form WIDTHxHEIGHT[@RATEHz]",
+            "'{}' n'est pas un mode WIDTHxHEIGHT[@RATEHz]",
+        ],
+    ),
+    (
+        "lint-pos",
+        [
+            "pos needs two integers: pos X Y",
+            "pos requiere dos enteros: pos X Y",
+            "pos erfordert zwei ganze Zahlen: pos X Y",
+            "pos nécessite deux entiers : pos X Y",
+        ],
+    ),
+    (
+        "lint-scale",
+        [
+            "scale needs a positive number",
+            "scale requiere un número positivo",
+            "scale erfordert eine positive Zahl",
+            "scale nécessite un nombre positif",
+        ],
+    ),
+    (
+        "lint-transform",
+        [
+            "'{}' is not a transform (use {})",
+            "'{}' no es una transformación (use {})",
+            "'{}' ist keine Transformation (verwenden Sie {})",
+            "'{}' n'est pas une transformation (utilisez {})",
+        ],
+    ),
+    (
+        "lint-max-render-time",
+        [
+            "max_render_time needs 'off' or a number of milliseconds",
+            "max_render_time requiere 'off' o un número de milisegundos",
+            "max_render_time erfordert 'off' oder eine Anzahl Millisekunden",
+            "max_render_time nécessite 'off' ou un nombre de millisecondes",
+        ],
+    ),
+    (
+        "lint-value",
+        [
+            "'{}' is not a valid value for {} (use {})",
+            "'{}' no es un valor válido para {} (use {})",
+            "'{}' ist kein gültiger Wert für {} (verwenden Sie {})",
+            "'{}' n'est pas une valeur valide pour {} (utilisez {})",
+        ],
+    ),
+    // Plans, Sway and MQTT
+    (
+        "plan-for",
+        [
+            "Plan for '{}':",
+            "Plan para '{}':",
+            "Plan für '{}':",
+            "Plan pour '{}' :",
+        ],
+    ),
+    (
+        "plan-commands",
+        [
+            "Sway commands:",
+            "Órdenes de Sway:",
+            "Sway-Befehle:",
+            "Commandes Sway :",
+        ],
+    ),
+    (
+        "plan-no-changes",
+        [
+            "No changes to {}.",
+            "Sin cambios en {}.",
+            "Keine Änderungen an {}.",
+            "Aucune modification de {}.",
+        ],
+    ),
+    (
+        "plan-changes",
+        [
+            "Changes to {}:",
+            "Cambios en {}:",
+            "Änderungen an {}:",
+            "Modifications de {} :",
+        ],
+    ),
+    (
+        "plan-transient",
+        [
+            "Transient plan: the config file will not be modified.",
+            "Plan temporal: el archivo de configuración no se modificará.",
+            "Vorübergehender Plan: Die Konfigurationsdatei wird nicht geändert.",
+            "Plan temporaire : le fichier de configuration ne sera pas modifié.",
+        ],
+    ),
+    (
+        "plan-stale",
+        [
+            "{} changed since the plan was made. Run 'plan' again.",
+            "{} cambió después de crear el plan. Ejecute 'plan' de nuevo.",
+            "{} hat sich seit dem Erstellen des Plans geändert. Führen Sie 'plan' erneut aus.",
+            "{} a changé depuis la création du plan. Relancez 'plan'.",
+        ],
+    ),
+    (
+        "command-rejected",
+        [
+            "swaymsg rejected: {}",
+            "swaymsg rechazó: {}",
+            "swaymsg hat abgelehnt: {}",
+            "swaymsg a rejeté : {}",
+        ],
+    ),
+    (
+        "reloaded",
+        [
+            "Successfully reloaded Sway configuration.",
+            "Configuración de Sway recargada correctamente.",
+            "Sway-Konfiguration erfolgreich neu geladen.",
+            "Configuration de Sway rechargée avec succès.",
+        ],
+    ),
+    (
+        "reload-failed",
+        [
+            "Failed to reload Sway configuration.",
+            "No se pudo recargar la configuración de Sway.",
+            "Die Sway-Konfiguration konnte nicht neu geladen werden.",
+            "Échec du rechargement de la configuration de Sway.",
+        ],
+    ),
+    (
+        "reload-failed-because",
+        [
+            "Failed to reload Sway configuration: {}",
+            "No se pudo recargar la configuración de Sway: {}",
+            "Die Sway-Konfiguration konnte nicht neu geladen werden: {}",
+            "Échec du rechargement de la configuration de Sway : {}",
+        ],
+    ),
+    (
+        "mqtt-publish-failed",
+        [
+            "failed to run mosquitto_pub.",
+            "no se pudo ejecutar mosquitto_pub.",
+            "mosquitto_pub konnte nicht ausgeführt werden.",
+            "impossible d'exécuter mosquitto_pub.",
+        ],
+    ),
+    (
+        "mqtt-not-listening",
+        [
+            "not listening for MQTT commands: mosquitto_sub: {}",
+            "no se escuchan órdenes MQTT: mosquitto_sub: {}",
+            "MQTT-Befehle werden nicht empfangen: mosquitto_sub: {}",
+            "les commandes MQTT ne sont pas écoutées : mosquitto_sub : {}",
+        ],
+    ),
+    (
+        "mqtt-ended",
+        [
+            "the MQTT subscription ended.",
+            "la suscripción MQTT terminó.",
+            "das MQTT-Abonnement wurde beendet.",
+            "l'abonnement MQTT s'est terminé.",
+        ],
+    ),
+    (
+        "mqtt-unknown-profile",
+        [
+            "MQTT asked for unknown profile '{}'.",
+            "MQTT pidió el perfil desconocido '{}'.",
+            "MQTT hat das unbekannte Profil '{}' angefordert.",
+            "MQTT a demandé le profil inconnu '{}'.",
+        ],
+    ),
+    (
+        "mqtt-switching",
+        [
+            "MQTT command; switching to '{}'.",
+            "Orden MQTT; cambiando a '{}'.",
+            "MQTT-Befehl; wechsle zu '{}'.",
+            "Commande MQTT ; passage à '{}'.",
+        ],
+    ),
+    (
+        "switch-failed",
+        [
+            "Failed to switch: {}",
+            "No se pudo cambiar: {}",
+            "Wechsel fehlgeschlagen: {}",
+            "Échec du changement : {}",
+        ],
+    ),
+    (
+        "state-write-failed",
+        [
+            "failed to update state file: {}",
+            "no se pudo actualizar el archivo de estado: {}",
+            "Die Statusdatei konnte nicht aktualisiert werden: {}",
+            "impossible de mettre à jour le fichier d'état : {}",
+        ],
+    ),
+    (
+        "log-write-failed",
+        [
+            "failed to update the switch log: {}",
+            "no se pudo actualizar el registro de cambios: {}",
+            "Das Wechselprotokoll konnte nicht aktualisiert werden: {}",
+            "impossible de mettre à jour le journal des changements : {}",
+        ],
+    ),
+    // Outputs checklist
+    (
+        "checklist-toggle",
+        [
+            "Toggle outputs by number, Enter to apply, q to quit",
+            "Active o desactive salidas por número, Intro para aplicar, q para salir",
+            "Ausgaben per Nummer umschalten, Eingabe zum Anwenden, q zum Beenden",
+            "Basculez les sorties par numéro, Entrée pour appliquer, q pour quitter",
+        ],
+    ),
+    (
+        "checklist-blackout",
+        [
+            "that would turn off every connected output; use --force to do it anyway.",
+            "eso apagaría todas las salidas conectadas; use --force para hacerlo de todos modos.",
+            "Das würde alle angeschlossenen Ausgänge ausschalten; verwenden Sie --force, um es trotzdem zu tun.",
+            "cela éteindrait toutes les sorties connectées ; utilisez --force pour le faire quand même.",
+        ],
+    ),
+    (
+        "checklist-unchanged",
+        [
+            "No outputs changed.",
+            "No cambió ninguna salida.",
+            "Keine Ausgabe wurde geändert.",
+            "Aucune sortie n'a changé.",
+        ],
+    ),
+    (
+        "sway-rejected",
+        [
+            "Sway rejected '{}'.",
+            "Sway rechazó '{}'.",
+            "Sway hat '{}' abgelehnt.",
+            "Sway a rejeté '{}'.",
+        ],
+    ),
+    (
+        "checklist-applied",
+        [
+            "Applied; the config was not changed and the next reload restores the Enabled profile.",
+            "Aplicado; la configuración no se modificó y la próxima recarga restaura el perfil Enabled.",
+            "Angewendet; die Konfiguration wurde nicht geändert, und das nächste Neuladen stellt das Profil mit Enabled wieder her.",
+            "Appliqué ; la configuration n'a pas été modifiée et le prochain rechargement rétablit le profil Enabled.",
+        ],
+    ),
+    (
+        "checklist-save",
+        [
+            "Save these outputs as a new profile?",
+            "¿Guardar estas salidas como un perfil nuevo?",
+            "Diese Ausgaben als neues Profil speichern?",
+            "Enregistrer ces sorties comme nouveau profil ?",
+        ],
+    ),
+    (
+        "profile-exists",
+        [
+            "There is a profile called '{}' already.",
+            "Ya existe un perfil llamado '{}'.",
+            "Es gibt bereits ein Profil namens '{}'.",
+            "Il existe déjà un profil nommé '{}'.",
+        ],
+    ),
+    // Output edits
+    (
+        "edit-rejected",
+        [
+            "Sway rejected the change; the config was not changed.",
+            "Sway rechazó el cambio; la configuración no se modificó.",
+            "Sway hat die Änderung abgelehnt; die Konfiguration wurde nicht geändert.",
+            "Sway a rejeté la modification ; la configuration n'a pas été modifiée.",
+        ],
+    ),
+    (
+        "usage-position",
+        [
+            "usage: position <output> <x>,<y>\n       position <output> --relative-to <other> left|right|above|below",
+            "uso: position <salida> <x>,<y>\n     position <salida> --relative-to <otra> left|right|above|below",
+            "Verwendung: position <Ausgabe> <x>,<y>\n            position <Ausgabe> --relative-to <andere> left|right|above|below",
+            "utilisation : position <sortie> <x>,<y>\n               position <sortie> --relative-to <autre> left|right|above|below",
+        ],
+    ),
+    (
+        "size-unknown",
+        [
+            "cannot determine the size of '{}' or '{}'.",
+            "no se puede determinar el tamaño de '{}' o '{}'.",
+            "Die Größe von '{}' oder '{}' kann nicht bestimmt werden.",
+            "impossible de déterminer la taille de '{}' ou '{}'.",
+        ],
+    ),
+    (
+        "moved",
+        [
+            "Moved {} to {},{}.",
+            "{} movida a {},{}.",
+            "{} nach {},{} verschoben.",
+            "{} déplacée en {},{}.",
+        ],
+    ),
+    (
+        "usage-refresh",
+        [
+            "usage: refresh <output> [<hz>]",
+            "uso: refresh <salida> [<hz>]",
+            "Verwendung: refresh <Ausgabe> [<hz>]",
+            "utilisation : refresh <sortie> [<hz>]",
+        ],
+    ),
+    (
+        "not-connected",
+        [
+            "'{}' is not connected.",
+            "'{}' no está conectada.",
+            "'{}' ist nicht angeschlossen.",
+            "'{}' n'est pas connectée.",
+        ],
+    ),
+    (
+        "not-refresh-rate",
+        [
+            "'{}' is not a refresh rate.",
+            "'{}' no es una frecuencia de actualización.",
+            "'{}' ist keine Bildwiederholrate.",
+            "'{}' n'est pas une fréquence de rafraîchissement.",
+        ],
+    ),
+    (
+        "rate-not-advertised",
+        [
+            "{} does not advertise {} Hz at {}x{}; it offers {}.",
+            "{} no anuncia {} Hz a {}x{}; ofrece {}.",
+            "{} bietet {} Hz bei {}x{} nicht an; verfügbar sind {}.",
+            "{} ne propose pas {} Hz en {}x{} ; elle propose {}.",
+        ],
+    ),
+    (
+        "none",
+        [
+            "none",
+            "ninguna",
+            "keine",
+            "aucune",
+        ],
+    ),
+    (
+        "set-mode",
+        [
+            "Set {} to {}.",
+            "{} configurada a {}.",
+            "{} auf {} gesetzt.",
+            "{} réglée sur {}.",
+        ],
+    ),
+    (
+        "usage-tearing",
+        [
+            "usage: tearing [<output>] [on|off|toggle]",
+            "uso: tearing [<salida>] [on|off|toggle]",
+            "Verwendung: tearing [<Ausgabe>] [on|off|toggle]",
+            "utilisation : tearing [<sortie>] [on|off|toggle]",
+        ],
+    ),
+    (
+        "tearing-allowed",
+        [
+            "{}: tearing allowed",
+            "{}: tearing permitido",
+            "{}: Tearing erlaubt",
+            "{} : tearing autorisé",
+        ],
+    ),
+    (
+        "tearing-not-allowed",
+        [
+            "{}: tearing not allowed",
+            "{}: tearing no permitido",
+            "{}: Tearing nicht erlaubt",
+            "{} : tearing non autorisé",
+        ],
+    ),
+    (
+        "tearing-updated",
+        [
+            "Updated tearing for {} in '{}'.",
+            "Tearing actualizado para {} en '{}'.",
+            "Tearing für {} in '{}' aktualisiert.",
+            "Tearing mis à jour pour {} dans '{}'.",
+        ],
+    ),
+    (
+        "needs-sway",
+        [
+            "{} needs Sway {}.{} or newer; this is Sway {}.",
+            "{} requiere Sway {}.{} o posterior; esta es Sway {}.",
+            "{} erfordert Sway {}.{} oder neuer; dies ist Sway {}.",
+            "{} nécessite Sway {}.{} ou plus récent ; ceci est Sway {}.",
+        ],
+    ),
+    (
+        "icc-missing",
+        [
+            "ICC profile '{}' does not exist.",
+            "el perfil ICC '{}' no existe.",
+            "Das ICC-Profil '{}' existiert nicht.",
+            "le profil ICC '{}' n'existe pas.",
+        ],
+    ),
+    (
+        "icc-note",
+        [
+            "Note: ICC profiles need Sway 1.10 or newer and the Vulkan renderer.",
+            "Nota: los perfiles ICC requieren Sway 1.10 o posterior y el renderizador Vulkan.",
+            "Hinweis: ICC-Profile erfordern Sway 1.10 oder neuer und den Vulkan-Renderer.",
+            "Remarque : les profils ICC nécessitent Sway 1.10 ou plus récent et le moteur de rendu Vulkan.",
+        ],
+    ),
+    (
+        "icc-set",
+        [
+            "Set the color profile of {}.",
+            "Perfil de color de {} configurado.",
+            "Farbprofil von {} gesetzt.",
+            "Profil colorimétrique de {} défini.",
+        ],
+    ),
+    (
+        "usage-icc",
+        [
+            "usage: icc set <output> <path> | icc show",
+            "uso: icc set <salida> <ruta> | icc show",
+            "Verwendung: icc set <Ausgabe> <Pfad> | icc show",
+            "utilisation : icc set <sortie> <chemin> | icc show",
+        ],
+    ),
+    (
+        "all-outputs-known",
+        [
+            "Every connected output already appears in a profile.",
+            "Todas las salidas conectadas ya aparecen en un perfil.",
+            "Jeder angeschlossene Ausgang kommt bereits in einem Profil vor.",
+            "Chaque sortie connectée figure déjà dans un profil.",
+        ],
+    ),
+    (
+        "add-to-which",
+        [
+            "Add {} ({}) to which profile?",
+            "¿A qué perfil añadir {} ({})?",
+            "{} ({}) zu welchem Profil hinzufügen?",
+            "Ajouter {} ({}) à quel profil ?",
+        ],
+    ),
+    (
+        "new-profile-choice",
+        [
+            "A new profile",
+            "Un perfil nuevo",
+            "Ein neues Profil",
+            "Un nouveau profil",
+        ],
+    ),
+    (
+        "profile",
+        [
+            "Profile",
+            "Perfil",
+            "Profil",
+            "Profil",
+        ],
+    ),
+    (
+        "added-output",
+        [
+            "Added {} to '{}'.",
+            "{} añadida a '{}'.",
+            "{} zu '{}' hinzugefügt.",
+            "{} ajoutée à '{}'.",
+        ],
+    ),
+    // Profile views
+    (
+        "column-output",
+        [
+            "OUTPUT",
+            "SALIDA",
+            "AUSGABE",
+            "SORTIE",
+        ],
+    ),
+    (
+        "column-mode",
+        [
+            "MODE",
+            "MODO",
+            "MODUS",
+            "MODE",
+        ],
+    ),
+    (
+        "column-position",
+        [
+            "POSITION",
+            "POSICIÓN",
+            "POSITION",
+            "POSITION",
+        ],
+    ),
+    (
+        "column-scale",
+        [
+            "SCALE",
+            "ESCALA",
+            "SKALIERUNG",
+            "ÉCHELLE",
+        ],
+    ),
+    (
+        "column-transform",
+        [
+            "TRANSFORM",
+            "TRANSFORMACIÓN",
+            "TRANSFORMATION",
+            "TRANSFORMATION",
+        ],
+    ),
+    (
+        "outputs-header",
+        [
+            "Outputs:",
+            "Salidas:",
+            "Ausgaben:",
+            "Sorties :",
+        ],
+    ),
+    (
+        "all-outputs",
+        [
+            "all outputs",
+            "todas las salidas",
+            "alle Ausgaben",
+            "toutes les sorties",
+        ],
+    ),
+    (
+        "not-connected-short",
+        [
+            "not connected",
+            "no conectada",
+            "nicht angeschlossen",
+            "non connectée",
+        ],
+    ),
+    (
+        "connected",
+        [
+            "connected",
+            "conectada",
+            "angeschlossen",
+            "connectée",
+        ],
+    ),
+    (
+        "connected-as",
+        [
+            "connected as {}",
+            "conectada como {}",
+            "angeschlossen als {}",
+            "connectée en tant que {}",
+        ],
+    ),
+    (
+        "matches-several",
+        [
+            "matches several outputs",
+            "coincide con varias salidas",
+            "passt zu mehreren Ausgaben",
+            "correspond à plusieurs sorties",
+        ],
+    ),
+    (
+        "disabled",
+        [
+            "disabled",
+            "desactivada",
+            "deaktiviert",
+            "désactivée",
+        ],
+    ),
+    (
+        "enabled",
+        [
+            "enabled",
+            "activada",
+            "aktiviert",
+            "activée",
+        ],
+    ),
+    (
+        "show-mode",
+        [
+            "mode {}",
+            "modo {}",
+            "Modus {}",
+            "mode {}",
+        ],
+    ),
+    (
+        "show-position",
+        [
+            "position {},{}",
+            "posición {},{}",
+            "Position {},{}",
+            "position {},{}",
+        ],
+    ),
+    (
+        "show-scale",
+        [
+            "scale {}",
+            "escala {}",
+            "Skalierung {}",
+            "échelle {}",
+        ],
+    ),
+    (
+        "show-transform",
+        [
+            "transform {}",
+            "transformación {}",
+            "Transformation {}",
+            "transformation {}",
+        ],
+    ),
+    (
+        "metadata-header",
+        [
+            "Metadata:",
+            "Metadatos:",
+            "Metadaten:",
+            "Métadonnées :",
+        ],
+    ),
+    (
+        "other-lines-header",
+        [
+            "Other lines:",
+            "Otras líneas:",
+            "Weitere Zeilen:",
+            "Autres lignes :",
+        ],
+    ),
+    (
+        "layout-at",
+        [
+            "{}x{} at {},{}",
+            "{}x{} en {},{}",
+            "{}x{} bei {},{}",
+            "{}x{} en {},{}",
+        ],
+    ),
+    (
+        "only-in",
+        [
+            "(only in '{}')",
+            "(solo en '{}')",
+            "(nur in '{}')",
+            "(seulement dans '{}')",
+        ],
+    ),
+    // Watch daemon
+    (
+        "events-not-serving",
+        [
+            "not broadcasting profile changes: {}",
+            "no se difunden los cambios de perfil: {}",
+            "Profilwechsel werden nicht verbreitet: {}",
+            "les changements de profil ne sont pas diffusés : {}",
+        ],
+    ),
+    (
+        "subscribe-failed",
+        [
+            "failed to subscribe to Sway output events: {}",
+            "no se pudo suscribir a los eventos de salida de Sway: {}",
+            "Sway-Ausgabeereignisse konnten nicht abonniert werden: {}",
+            "impossible de s'abonner aux événements de sortie de Sway : {}",
+        ],
+    ),
+    (
+        "subscription-no-stdout",
+        [
+            "the output event subscription has no stdout",
+            "la suscripción a eventos de salida no tiene stdout",
+            "das Abonnement der Ausgabeereignisse hat kein stdout",
+            "l'abonnement aux événements de sortie n'a pas de stdout",
+        ],
+    ),
+    (
+        "resume-not-watched",
+        [
+            "not re-checking the outputs after suspend: {}",
+            "no se volverán a comprobar las salidas tras la suspensión: {}",
+            "Ausgaben werden nach dem Ruhezustand nicht erneut geprüft: {}",
+            "les sorties ne seront pas revérifiées après la mise en veille : {}",
+        ],
+    ),
+    (
+        "outputs-settled",
+        [
+            "Outputs settled after {} event(s).",
+            "Salidas estabilizadas tras {} evento(s).",
+            "Ausgaben nach {} Ereignis(sen) stabil.",
+            "Sorties stabilisées après {} événement(s).",
+        ],
+    ),
+    (
+        "outputs-changed",
+        [
+            "Output state changed externally; re-applying Enabled profile.",
+            "El estado de las salidas cambió externamente; se vuelve a aplicar el perfil Enabled.",
+            "Ausgabezustand wurde extern geändert; das Profil mit Enabled wird erneut angewendet.",
+            "L'état des sorties a changé de l'extérieur ; réapplication du profil Enabled.",
+        ],
+    ),
+    (
+        "config-changed",
+        [
+            "Config file changed; re-applying Enabled profile.",
+            "El archivo de configuración cambió; se vuelve a aplicar el perfil Enabled.",
+            "Konfigurationsdatei geändert; das Profil mit Enabled wird erneut angewendet.",
+            "Le fichier de configuration a changé ; réapplication du profil Enabled.",
+        ],
+    ),
+    (
+        "subscription-ended",
+        [
+            "the Sway event subscription ended.",
+            "la suscripción a eventos de Sway terminó.",
+            "das Sway-Ereignisabonnement wurde beendet.",
+            "l'abonnement aux événements de Sway s'est terminé.",
+        ],
+    ),
+    (
+        "resumed",
+        [
+            "Resumed from suspend; checking the outputs.",
+            "Reanudado tras la suspensión; comprobando las salidas.",
+            "Aus dem Ruhezustand fortgesetzt; Ausgaben werden geprüft.",
+            "Reprise après mise en veille ; vérification des sorties.",
+        ],
+    ),
+    (
+        "still-in-effect",
+        [
+            "'{}' is still in effect.",
+            "'{}' sigue vigente.",
+            "'{}' ist weiterhin wirksam.",
+            "'{}' est toujours en vigueur.",
+        ],
+    ),
+    (
+        "no-longer-match",
+        [
+            "The outputs no longer match '{}'; re-applying it.",
+            "Las salidas ya no coinciden con '{}'; se vuelve a aplicar.",
+            "Die Ausgaben passen nicht mehr zu '{}'; es wird erneut angewendet.",
+            "Les sorties ne correspondent plus à '{}' ; réapplication.",
+        ],
+    ),
+    (
+        "not-restored",
+        [
+            "'{}' could not be restored after resume; the outputs still differ.",
+            "'{}' no se pudo restaurar tras la reanudación; las salidas siguen siendo distintas.",
+            "'{}' konnte nach dem Fortsetzen nicht wiederhergestellt werden; die Ausgaben weichen weiterhin ab.",
+            "'{}' n'a pas pu être rétabli après la reprise ; les sorties diffèrent toujours.",
+        ],
+    ),
+    (
+        "not-restored-title",
+        [
+            "Display profile not restored",
+            "Perfil de pantalla no restaurado",
+            "Anzeigeprofil nicht wiederhergestellt",
+            "Profil d'affichage non rétabli",
+        ],
+    ),
+    (
+        "rule-no-profile",
+        [
+            "Rule '{}' names no profile that can be applied.",
+            "La regla '{}' no nombra ningún perfil que se pueda aplicar.",
+            "Regel '{}' nennt kein Profil, das angewendet werden kann.",
+            "La règle '{}' ne désigne aucun profil applicable.",
+        ],
+    ),
+    (
+        "rule-skipped",
+        [
+            "Rule '{}' skipped: '{}' does not fit the connected outputs.",
+            "Regla '{}' omitida: '{}' no encaja con las salidas conectadas.",
+            "Regel '{}' übersprungen: '{}' passt nicht zu den angeschlossenen Ausgaben.",
+            "Règle '{}' ignorée : '{}' ne convient pas aux sorties connectées.",
+        ],
+    ),
+    (
+        "rule-would-switch",
+        [
+            "Rule '{0}' would switch to '{1}'.",
+            "La regla '{0}' cambiaría a '{1}'.",
+            "Zu '{1}' würde durch Regel '{0}' gewechselt.",
+            "'{1}' serait appliqué par la règle '{0}'.",
+        ],
+    ),
+    (
+        "would-offer-choice",
+        [
+            "It would offer a choice of {}.",
+            "Ofrecería elegir entre {}.",
+            "Es würde eine Auswahl aus {} anbieten.",
+            "Un choix entre {} serait proposé.",
+        ],
+    ),
+    (
+        "rule-fired",
+        [
+            "Rule '{}' fired; switching to '{}'.",
+            "La regla '{}' se activó; cambiando a '{}'.",
+            "Regel '{}' ausgelöst; wechsle zu '{}'.",
+            "La règle '{}' s'est déclenchée ; passage à '{}'.",
+        ],
+    ),
+    (
+        "switch-to-failed",
+        [
+            "Failed to switch to '{}': {}",
+            "No se pudo cambiar a '{}': {}",
+            "Wechsel zu '{}' fehlgeschlagen: {}",
+            "Échec du passage à '{}' : {}",
+        ],
+    ),
+    (
+        "tied-profiles",
+        [
+            "Applied '{0}'; {1} profiles fit the connected outputs equally well.",
+            "{1} perfiles encajan igual de bien con las salidas conectadas; se aplicó '{0}'.",
+            "{1} Profile passen gleich gut zu den angeschlossenen Ausgängen; '{0}' wurde angewendet.",
+            "{1} profils conviennent aussi bien aux sorties connectées ; '{0}' a été appliqué.",
+        ],
+    ),
+    (
+        "choose-profile-title",
+        [
+            "Choose a display profile",
+            "Elija un perfil de pantalla",
+            "Anzeigeprofil auswählen",
+            "Choisissez un profil d'affichage",
+        ],
+    ),
+    (
+        "chosen-in-notification",
+        [
+            "Switching to '{}' as chosen in the notification.",
+            "Cambiando a '{}' según lo elegido en la notificación.",
+            "Wechsle zu '{}', wie in der Benachrichtigung gewählt.",
+            "Passage à '{}' comme choisi dans la notification.",
+        ],
+    ),
+    (
+        "would-blackout",
+        [
+            "'{}' would turn off every connected output.",
+            "'{}' apagaría todas las salidas conectadas.",
+            "'{}' würde alle angeschlossenen Ausgänge ausschalten.",
+            "'{}' éteindrait toutes les sorties connectées.",
+        ],
+    ),
+    (
+        "switching-to",
+        [
+            "Switching to '{}'.",
+            "Cambiando a '{}'.",
+            "Wechsle zu '{}'.",
+            "Passage à '{}'.",
+        ],
+    ),
+    (
+        "nothing-fits",
+        [
+            "No profile fits; leaving the outputs as they are.",
+            "Ningún perfil encaja; las salidas se dejan como están.",
+            "Kein Profil passt; die Ausgaben bleiben, wie sie sind.",
+            "Aucun profil ne convient ; les sorties restent telles quelles.",
+        ],
+    ),
+    (
+        "apply-profile-failed",
+        [
+            "Failed to apply '{}'.",
+            "No se pudo aplicar '{}'.",
+            "'{}' konnte nicht angewendet werden.",
+            "Échec de l'application de '{}'.",
+        ],
+    ),
+    (
+        "output-line-rejected",
+        [
+            "Sway rejected an output line",
+            "Sway rechazó una línea de salida",
+            "Sway hat eine Ausgabezeile abgelehnt",
+            "Sway a rejeté une ligne de sortie",
+        ],
+    ),
+    (
+        "would-switch-instead",
+        [
+            "Would switch to '{}' instead.",
+            "Cambiaría a '{}' en su lugar.",
+            "Würde stattdessen zu '{}' wechseln.",
+            "Passerait à '{}' à la place.",
+        ],
+    ),
+    (
+        "nothing-fits-observe",
+        [
+            "No profile fits; would leave the outputs.",
+            "Ningún perfil encaja; dejaría las salidas.",
+            "Kein Profil passt; die Ausgaben würden bleiben.",
+            "Aucun profil ne convient ; les sorties resteraient.",
+        ],
+    ),
+    (
+        "would-apply",
+        [
+            "Would apply '{}':",
+            "Aplicaría '{}':",
+            "Würde '{}' anwenden:",
+            "Appliquerait '{}' :",
+        ],
+    ),
+    (
+        "event-logged",
+        [
+            "{} event: {}",
+            "{} evento: {}",
+            "{} Ereignis: {}",
+            "{} événement : {}",
+        ],
+    ),
+    (
+        "observed",
+        [
+            "[observe] {}",
+            "[observación] {}",
+            "[Beobachtung] {}",
+            "[observation] {}",
+        ],
+    ),
+    (
+        "bind-failed",
+        [
+            "Failed to bind {} for switching profiles.",
+            "No se pudo asignar {} para cambiar de perfil.",
+            "{} konnte nicht zum Wechseln von Profilen belegt werden.",
+            "Impossible d'associer {} au changement de profil.",
+        ],
+    ),
+    (
+        "output-unknown",
+        [
+            "{} ({}) is not in any profile. Run 'sway-display-switcher add-output' to add it.",
+            "{} ({}) no está en ningún perfil. Ejecute 'sway-display-switcher add-output' para añadirla.",
+            "{} ({}) ist in keinem Profil. Führen Sie 'sway-display-switcher add-output' aus, um es hinzuzufügen.",
+            "{} ({}) ne figure dans aucun profil. Lancez 'sway-display-switcher add-output' pour l'ajouter.",
+        ],
+    ),
+    (
+        "output-unknown-title",
+        [
+            "New output connected",
+            "Nueva salida conectada",
+            "Neuer Ausgang angeschlossen",
+            "Nouvelle sortie connectée",
+        ],
+    ),
+    (
+        "dbus-no-stdout",
+        [
+            "dbus-monitor has no stdout",
+            "dbus-monitor no tiene stdout",
+            "dbus-monitor hat kein stdout",
+            "dbus-monitor n'a pas de stdout",
+        ],
+    ),
+    // Profile effects
+    (
+        "invalid-dpi",
+        [
+            "ignoring invalid Xwayland DPI '{}'.",
+            "se ignora el DPI de Xwayland no válido '{}'.",
+            "Ungültiger Xwayland-DPI-Wert '{}' wird ignoriert.",
+            "DPI Xwayland invalide '{}' ignoré.",
+        ],
+    ),
+    (
+        "invalid-xwayland",
+        [
+            "ignoring invalid Xwayland setting '{}'.",
+            "se ignora el ajuste de Xwayland no válido '{}'.",
+            "Ungültige Xwayland-Einstellung '{}' wird ignoriert.",
+            "réglage Xwayland invalide '{}' ignoré.",
+        ],
+    ),
+    (
+        "xrdb-failed",
+        [
+            "failed to set Xft.dpi with xrdb.",
+            "no se pudo establecer Xft.dpi con xrdb.",
+            "Xft.dpi konnte mit xrdb nicht gesetzt werden.",
+            "impossible de définir Xft.dpi avec xrdb.",
+        ],
+    ),
+    (
+        "invalid-environment",
+        [
+            "ignoring invalid environment variable '{}'.",
+            "se ignora la variable de entorno no válida '{}'.",
+            "Ungültige Umgebungsvariable '{}' wird ignoriert.",
+            "variable d'environnement invalide '{}' ignorée.",
+        ],
+    ),
+    (
+        "update-failed",
+        [
+            "failed to update {}: {}",
+            "no se pudo actualizar {}: {}",
+            "{} konnte nicht aktualisiert werden: {}",
+            "impossible de mettre à jour {} : {}",
+        ],
+    ),
+    (
+        "invalid-tearing",
+        [
+            "ignoring tearing criteria '{}'.",
+            "se ignoran los criterios de tearing '{}'.",
+            "Tearing-Kriterien '{}' werden ignoriert.",
+            "critères de tearing '{}' ignorés.",
+        ],
+    ),
+    (
+        "invalid-bar-mode",
+        [
+            "ignoring invalid bar mode '{}'.",
+            "se ignora el modo de barra no válido '{}'.",
+            "Ungültiger Leistenmodus '{}' wird ignoriert.",
+            "mode de barre invalide '{}' ignoré.",
+        ],
+    ),
+    (
+        "brightness-no-outputs",
+        [
+            "cannot set brightness without the list of outputs.",
+            "no se puede ajustar el brillo sin la lista de salidas.",
+            "Helligkeit kann ohne die Liste der Ausgaben nicht gesetzt werden.",
+            "impossible de régler la luminosité sans la liste des sorties.",
+        ],
+    ),
+    (
+        "invalid-brightness",
+        [
+            "ignoring invalid brightness '{}'.",
+            "se ignora el brillo no válido '{}'.",
+            "Ungültige Helligkeit '{}' wird ignoriert.",
+            "luminosité invalide '{}' ignorée.",
+        ],
+    ),
+    (
+        "brightness-failed",
+        [
+            "failed to run {} for the brightness of {}.",
+            "no se pudo ejecutar {} para el brillo de {}.",
+            "{} konnte für die Helligkeit von {} nicht ausgeführt werden.",
+            "impossible de lancer {} pour la luminosité de {}.",
+        ],
+    ),
+    (
+        "remove-failed",
+        [
+            "failed to remove {}: {}",
+            "no se pudo eliminar {}: {}",
+            "{} konnte nicht entfernt werden: {}",
+            "impossible de supprimer {} : {}",
+        ],
+    ),
+    (
+        "indicator-disabled",
+        [
+            "the lock indicator output {} is not enabled in '{}'.",
+            "la salida del indicador de bloqueo {} no está activada en '{}'.",
+            "Der Ausgang {} für die Sperranzeige ist in '{}' nicht aktiviert.",
+            "la sortie de l'indicateur de verrouillage {} n'est pas activée dans '{}'.",
+        ],
+    ),
+    (
+        "invalid-idle",
+        [
+            "ignoring invalid idle timeout '{}'.",
+            "se ignora el tiempo de inactividad no válido '{}'.",
+            "Ungültige Leerlaufzeit '{}' wird ignoriert.",
+            "délai d'inactivité invalide '{}' ignoré.",
+        ],
+    ),
+    (
+        "swayidle-failed",
+        [
+            "failed to start swayidle.",
+            "no se pudo iniciar swayidle.",
+            "swayidle konnte nicht gestartet werden.",
+            "impossible de démarrer swayidle.",
+        ],
+    ),
+    (
+        "night-light-failed",
+        [
+            "neither wlsunset nor gammastep could be started.",
+            "no se pudo iniciar ni wlsunset ni gammastep.",
+            "Weder wlsunset noch gammastep konnte gestartet werden.",
+            "ni wlsunset ni gammastep n'ont pu être démarrés.",
+        ],
+    ),
+    (
+        "start-failed",
+        [
+            "failed to start '{}'.",
+            "no se pudo iniciar '{}'.",
+            "'{}' konnte nicht gestartet werden.",
+            "impossible de démarrer '{}'.",
+        ],
+    ),
+    (
+        "webhook-failed",
+        [
+            "failed to run curl for webhook {}.",
+            "no se pudo ejecutar curl para el webhook {}.",
+            "curl konnte für den Webhook {} nicht ausgeführt werden.",
+            "impossible de lancer curl pour le webhook {}.",
+        ],
+    ),
+    // Config file
+    (
+        "read-failed",
+        [
+            "failed to read {}: {}",
+            "no se pudo leer {}: {}",
+            "{} konnte nicht gelesen werden: {}",
+            "impossible de lire {} : {}",
+        ],
+    ),
+    (
+        "recover-failed",
+        [
+            "failed to recover {}: {}",
+            "no se pudo recuperar {}: {}",
+            "{} konnte nicht wiederhergestellt werden: {}",
+            "impossible de récupérer {} : {}",
+        ],
+    ),
+    (
+        "no-line-for-output",
+        [
+            "'{}' has no line for output '{}'.",
+            "'{}' no tiene ninguna línea para la salida '{}'.",
+            "'{}' hat keine Zeile für den Ausgang '{}'.",
+            "'{}' n'a aucune ligne pour la sortie '{}'.",
+        ],
+    ),
+    (
+        "not-utf8",
+        [
+            "the file is not valid UTF-8, and rewriting it would change the bytes that are not; nothing was written.",
+            "el archivo no es UTF-8 válido y reescribirlo cambiaría los bytes que no lo son; no se escribió nada.",
+            "Die Datei ist kein gültiges UTF-8, und ein Neuschreiben würde die ungültigen Bytes verändern; nichts wurde geschrieben.",
+            "le fichier n'est pas en UTF-8 valide, et le réécrire modifierait les octets qui ne le sont pas ; rien n'a été écrit.",
+        ],
+    ),
+    (
+        "merge-conflict",
+        [
+            "the file changed while profile '{}' was being changed, and the change touches the same settings; nothing was written, run the command again.",
+            "el archivo cambió mientras se modificaba el perfil '{}', y el cambio afecta a los mismos ajustes; no se escribió nada, vuelva a ejecutar la orden.",
+            "Die Datei wurde geändert, während Profil '{}' bearbeitet wurde, und die Änderung betrifft dieselben Einstellungen; nichts wurde geschrieben, führen Sie den Befehl erneut aus.",
+            "le fichier a changé pendant la modification du profil '{}', et le changement touche les mêmes réglages ; rien n'a été écrit, relancez la commande.",
+        ],
+    ),
+    (
+        "merged-note",
+        [
+            "Note: {} changed since it was read; the changes were made to its new contents.",
+            "Nota: {} cambió desde que se leyó; los cambios se aplicaron a su nuevo contenido.",
+            "Hinweis: {} wurde seit dem Lesen geändert; die Änderungen wurden auf den neuen Inhalt angewendet.",
+            "Remarque : {} a changé depuis sa lecture ; les modifications ont été appliquées à son nouveau contenu.",
+        ],
+    ),
+    (
+        "changed-since-read",
+        [
+            "the file changed since it was read; nothing was written, run the command again.",
+            "el archivo cambió desde que se leyó; no se escribió nada, vuelva a ejecutar la orden.",
+            "Die Datei wurde seit dem Lesen geändert; nichts wurde geschrieben, führen Sie den Befehl erneut aus.",
+            "le fichier a changé depuis sa lecture ; rien n'a été écrit, relancez la commande.",
+        ],
+    ),
+    (
+        "outside-profile",
+        [
+            "outside any profile",
+            "fuera de cualquier perfil",
+            "außerhalb jedes Profils",
+            "en dehors de tout profil",
+        ],
+    ),
+    (
+        "invalid-condition",
+        [
+            "not a valid ?if-present, ?if-absent or ?identity line",
+            "no es una línea ?if-present, ?if-absent o ?identity válida",
+            "keine gültige ?if-present-, ?if-absent- oder ?identity-Zeile",
+            "pas une ligne ?if-present, ?if-absent ou ?identity valide",
+        ],
+    ),
+    (
+        "missing-output-name",
+        [
+            "missing output name",
+            "falta el nombre de la salida",
+            "Ausgangsname fehlt",
+            "nom de sortie manquant",
+        ],
+    ),
+    (
+        "header-without-description",
+        [
+            "looks like a profile header, but headers start with 'Description ='",
+            "parece una cabecera de perfil, pero las cabeceras empiezan por 'Description ='",
+            "sieht wie ein Profilkopf aus, aber Profilköpfe beginnen mit 'Description ='",
+            "ressemble à un en-tête de profil, mais les en-têtes commencent par 'Description ='",
+        ],
+    ),
+    (
+        "not-sway-command",
+        [
+            "'{}' is not a Sway command",
+            "'{}' no es una orden de Sway",
+            "'{}' ist kein Sway-Befehl",
+            "'{}' n'est pas une commande Sway",
+        ],
+    ),
+    (
+        "backup-failed",
+        [
+            "failed to back up {}: {}",
+            "no se pudo hacer una copia de seguridad de {}: {}",
+            "Sicherung von {} fehlgeschlagen: {}",
+            "impossible de sauvegarder {} : {}",
+        ],
+    ),
+    (
+        "root-write-failed",
+        [
+            "{} could not be written as root",
+            "{} no se pudo escribir como root",
+            "{} konnte nicht als root geschrieben werden",
+            "{} n'a pas pu être écrit en tant que root",
+        ],
+    ),
+    (
+        "no-privilege-tool",
+        [
+            "{} is read-only and neither pkexec nor sudo is available",
+            "{} es de solo lectura y no hay ni pkexec ni sudo",
+            "{} ist schreibgeschützt und weder pkexec noch sudo ist verfügbar",
+            "{} est en lecture seule et ni pkexec ni sudo n'est disponible",
+        ],
+    ),
+    (
+        "system-path-only",
+        [
+            "only {} can be written this way",
+            "solo {} se puede escribir de esta forma",
+            "Nur {} kann auf diese Weise geschrieben werden",
+            "seul {} peut être écrit de cette façon",
+        ],
+    ),
+    (
+        "setting-ignored",
+        [
+            "{}:{}: ignoring '{}'",
+            "{}:{}: se ignora '{}'",
+            "{}:{}: '{}' wird ignoriert",
+            "{}:{} : '{}' ignoré",
+        ],
+    ),
+    (
+        "daemon-listening",
+        [
+            "another daemon is listening on {}",
+            "otro demonio está escuchando en {}",
+            "Ein anderer Dienst lauscht bereits auf {}",
+            "un autre démon écoute sur {}",
+        ],
+    ),
+    (
+        "alias-is-number",
+        [
+            "alias '{}' of '{}' is a number and selects by position instead.",
+            "el alias '{}' de '{}' es un número y selecciona por posición.",
+            "Alias '{}' von '{}' ist eine Zahl und wählt stattdessen nach Position.",
+            "l'alias '{}' de '{}' est un nombre et sélectionne plutôt par position.",
+        ],
+    ),
+    (
+        "alias-is-profile",
+        [
+            "alias '{}' of '{}' is the name of another profile.",
+            "el alias '{}' de '{}' es el nombre de otro perfil.",
+            "Alias '{}' von '{}' ist der Name eines anderen Profils.",
+            "l'alias '{}' de '{}' est le nom d'un autre profil.",
+        ],
+    ),
+    (
+        "alias-shared",
+        [
+            "alias '{}' is used by both '{}' and '{}'.",
+            "el alias '{}' lo usan tanto '{}' como '{}'.",
+            "Alias '{}' wird von '{}' und '{}' verwendet.",
+            "l'alias '{}' est utilisé à la fois par '{}' et '{}'.",
+        ],
+    ),
+    (
+        "unsupported-schema",
+        [
+            "unsupported schema_version {} (newest known is {})",
+            "schema_version {} no admitida (la más reciente conocida es {})",
+            "Nicht unterstützte schema_version {} (neueste bekannte ist {})",
+            "schema_version {} non prise en charge (la plus récente connue est {})",
+        ],
+    ),
+    (
+        "metadata-not-string",
+        [
+            "metadata '{}' must be a string",
+            "el metadato '{}' debe ser una cadena",
+            "Metadatum '{}' muss eine Zeichenkette sein",
+            "la métadonnée '{}' doit être une chaîne",
+        ],
+    ),
+    // Imports, remote hosts and overrides
+    (
+        "unusable-name",
+        [
+            "profile '{}': not a usable profile name",
+            "perfil '{}': no es un nombre de perfil utilizable",
+            "Profil '{}': kein verwendbarer Profilname",
+            "profil '{}' : nom de profil inutilisable",
+        ],
+    ),
+    (
+        "invalid-json",
+        [
+            "not a profile exported as JSON: {}",
+            "no es un perfil exportado como JSON: {}",
+            "Kein als JSON exportiertes Profil: {}",
+            "pas un profil exporté en JSON : {}",
+        ],
+    ),
+    (
+        "no-search",
+        [
+            "output without 'search' or 'match'",
+            "salida sin 'search' ni 'match'",
+            "Ausgang ohne 'search' oder 'match'",
+            "sortie sans 'search' ni 'match'",
+        ],
+    ),
+    (
+        "pattern-search",
+        [
+            "substring or regex search '{}'",
+            "búsqueda por subcadena o expresión regular '{}'",
+            "Teilzeichenketten- oder Regex-Suche '{}'",
+            "recherche par sous-chaîne ou expression régulière '{}'",
+        ],
+    ),
+    (
+        "search-not-single",
+        [
+            "search '{}' names no single output",
+            "la búsqueda '{}' no nombra una única salida",
+            "Suche '{}' benennt keinen einzelnen Ausgang",
+            "la recherche '{}' ne désigne pas une sortie unique",
+        ],
+    ),
+    (
+        "regular-expression",
+        [
+            "regular expression '{}'",
+            "expresión regular '{}'",
+            "regulärer Ausdruck '{}'",
+            "expression régulière '{}'",
+        ],
+    ),
+    (
+        "position-not-connected",
+        [
+            "position of {} (not connected)",
+            "posición de {} (no conectada)",
+            "Position von {} (nicht angeschlossen)",
+            "position de {} (non connectée)",
+        ],
+    ),
+    (
+        "cannot-read-line",
+        [
+            "line {}: cannot read '{}'",
+            "línea {}: no se puede leer '{}'",
+            "Zeile {}: '{}' kann nicht gelesen werden",
+            "ligne {} : impossible de lire '{}'",
+        ],
+    ),
+    (
+        "no-host",
+        [
+            "no host selected",
+            "no se ha seleccionado ningún host",
+            "kein Host ausgewählt",
+            "aucun hôte sélectionné",
+        ],
+    ),
+    (
+        "ssh-failed",
+        [
+            "failed to run ssh: {}",
+            "no se pudo ejecutar ssh: {}",
+            "ssh konnte nicht ausgeführt werden: {}",
+            "impossible de lancer ssh : {}",
+        ],
+    ),
+    (
+        "remote-read-failed",
+        [
+            "could not read the Sway config on {}",
+            "no se pudo leer la configuración de Sway en {}",
+            "Die Sway-Konfiguration auf {} konnte nicht gelesen werden",
+            "impossible de lire la configuration Sway sur {}",
+        ],
+    ),
+    (
+        "remote-write-failed",
+        [
+            "could not write the Sway config on {}",
+            "no se pudo escribir la configuración de Sway en {}",
+            "Die Sway-Konfiguration auf {} konnte nicht geschrieben werden",
+            "impossible d'écrire la configuration Sway sur {}",
+        ],
+    ),
+    (
+        "override-form",
+        [
+            "'{}' is not of the form OUTPUT.KEY=VALUE.",
+            "'{}' no tiene la forma SALIDA.CLAVE=VALOR.",
+            "'{}' hat nicht die Form AUSGANG.SCHLÜSSEL=WERT.",
+            "'{}' n'est pas de la forme SORTIE.CLÉ=VALEUR.",
+        ],
+    ),
+    (
+        "override-key",
+        [
+            "'{}' is not an output setting --set can change.",
+            "'{}' no es un ajuste de salida que --set pueda cambiar.",
+            "'{}' ist keine Ausgabeeinstellung, die --set ändern kann.",
+            "'{}' n'est pas un réglage de sortie que --set peut modifier.",
+        ],
+    ),
+    (
+        "not-a-rate",
+        [
+            "'{}' is not a refresh rate.",
+            "'{}' no es una frecuencia de actualización.",
+            "'{}' ist keine Bildwiederholrate.",
+            "'{}' n'est pas une fréquence de rafraîchissement.",
+        ],
+    ),
+    (
+        "no-mode-for-rate",
+        [
+            "{} has no mode to change the refresh rate of.",
+            "{} no tiene ningún modo cuya frecuencia cambiar.",
+            "{} hat keinen Modus, dessen Bildwiederholrate geändert werden könnte.",
+            "{} n'a aucun mode dont changer la fréquence.",
+        ],
+    ),
+    // Matching, backups and layout
+    (
+        "match-connected",
+        [
+            "{} is connected and enabled",
+            "{} está conectada y activada",
+            "{} ist angeschlossen und aktiviert",
+            "{} est connectée et activée",
+        ],
+    ),
+    (
+        "match-by-description",
+        [
+            "{} is connected and enabled (by description)",
+            "{} está conectada y activada (por descripción)",
+            "{} ist angeschlossen und aktiviert (per Beschreibung)",
+            "{} est connectée et activée (par description)",
+        ],
+    ),
+    (
+        "match-gpu",
+        [
+            "GPU {}",
+            "GPU {}",
+            "GPU {}",
+            "GPU {}",
+        ],
+    ),
+    (
+        "match-unconfigured",
+        [
+            "{} is connected but not configured",
+            "{} está conectada pero no configurada",
+            "{} ist angeschlossen, aber nicht konfiguriert",
+            "{} est connectée mais non configurée",
+        ],
+    ),
+    (
+        "no-backups",
+        [
+            "there are no backups of {}.",
+            "no hay copias de seguridad de {}.",
+            "Es gibt keine Sicherungen von {}.",
+            "il n'y a aucune sauvegarde de {}.",
+        ],
+    ),
+    (
+        "not-a-timestamp",
+        [
+            "'{}' is not a backup timestamp; see 'backups list'.",
+            "'{}' no es una marca de tiempo de copia de seguridad; consulte 'backups list'.",
+            "'{}' ist kein Sicherungszeitstempel; siehe 'backups list'.",
+            "'{}' n'est pas un horodatage de sauvegarde ; voir 'backups list'.",
+        ],
+    ),
+    (
+        "no-backup-from",
+        [
+            "there is no backup from {}; see 'backups list'.",
+            "no hay ninguna copia de seguridad de {}; consulte 'backups list'.",
+            "Es gibt keine Sicherung von {}; siehe 'backups list'.",
+            "il n'y a aucune sauvegarde de {} ; voir 'backups list'.",
+        ],
+    ),
+    (
+        "layout-overlap",
+        [
+            "{} and {} overlap by {}x{}.",
+            "{} y {} se solapan {}x{}.",
+            "{} und {} überlappen sich um {}x{}.",
+            "{} et {} se chevauchent de {}x{}.",
+        ],
+    ),
+    (
+        "layout-gap-one",
+        [
+            "{} is not adjacent to {}; the cursor cannot move between them.",
+            "{} no es contigua a {}; el cursor no puede pasar de una a otra.",
+            "{} grenzt nicht an {}; der Mauszeiger kann nicht zwischen ihnen wechseln.",
+            "{} n'est pas adjacente à {} ; le curseur ne peut pas passer de l'une à l'autre.",
+        ],
+    ),
+    (
+        "layout-gap-many",
+        [
+            "{} are not adjacent to {}; the cursor cannot move between them.",
+            "{} no son contiguas a {}; el cursor no puede pasar entre ellas.",
+            "{} grenzen nicht an {}; der Mauszeiger kann nicht zwischen ihnen wechseln.",
+            "{} ne sont pas adjacentes à {} ; le curseur ne peut pas passer de l'une à l'autre.",
+        ],
+    ),
+    // Switch log
+    (
+        "log-transient",
+        [
+            " (transient)",
+            " (transitorio)",
+            " (vorübergehend)",
+            " (temporaire)",
+        ],
+    ),
+    (
+        "log-ok",
+        [
+            "ok",
+            "correcto",
+            "ok",
+            "ok",
+        ],
+    ),
+    (
+        "log-failed",
+        [
+            "failed: {}",
+            "fallido: {}",
+            "fehlgeschlagen: {}",
+            "échec : {}",
+        ],
+    ),
+    (
+        "log-line",
+        [
+            "{}  {} -> {}{}  by {}  {} in {} ms",
+            "{}  {} -> {}{}  por {}  {} en {} ms",
+            "{}  {} -> {}{}  durch {}  {} in {} ms",
+            "{}  {} -> {}{}  par {}  {} en {} ms",
+        ],
+    ),
+    // Usage and help
+    (
+        "help-usage",
+        [
+            "Usage: sway-display-switcher [COMMAND] [OPTIONS]",
+            "Uso: sway-display-switcher [ORDEN] [OPCIONES]",
+            "Verwendung: sway-display-switcher [BEFEHL] [OPTIONEN]",
+            "Utilisation : sway-display-switcher [COMMANDE] [OPTIONS]",
+        ],
+    ),
+    (
+        "help-commands",
+        [
+            "Commands:",
+            "Órdenes:",
+            "Befehle:",
+            "Commandes :",
+        ],
+    ),
+    (
+        "help-options",
+        [
+            "Options:",
+            "Opciones:",
+            "Optionen:",
+            "Options :",
+        ],
+    ),
+    (
+        "built-without",
+        [
+            "Built without: {}",
+            "Compilado sin: {}",
+            "Gebaut ohne: {}",
+            "Compilé sans : {}",
+        ],
+    ),
+    (
+        "option-needs-value",
+        [
+            "option '{}' requires a value.",
+            "la opción '{}' requiere un valor.",
+            "Option '{}' benötigt einen Wert.",
+            "l'option '{}' nécessite une valeur.",
+        ],
+    ),
+    (
+        "unknown-option",
+        [
+            "unknown option '{}'.",
+            "opción desconocida '{}'.",
+            "Unbekannte Option '{}'.",
+            "option inconnue '{}'.",
+        ],
+    ),
+    (
+        "feature-left-out",
+        [
+            "{} needs the '{}' feature, which this build leaves out.",
+            "{} necesita la característica '{}', que esta compilación no incluye.",
+            "{} benötigt das Feature '{}', das in diesem Build fehlt.",
+            "{} nécessite la fonctionnalité '{}', absente de cette compilation.",
+        ],
+    ),
+    (
+        "not-seconds",
+        [
+            "'{}' is not a number of seconds.",
+            "'{}' no es un número de segundos.",
+            "'{}' ist keine Anzahl von Sekunden.",
+            "'{}' n'est pas un nombre de secondes.",
+        ],
+    ),
+    (
+        "not-count",
+        [
+            "'{}' is not a count.",
+            "'{}' no es una cantidad.",
+            "'{}' ist keine Anzahl.",
+            "'{}' n'est pas un nombre.",
+        ],
+    ),
+    (
+        "help-pick",
+        [
+            "Pick a profile interactively and activate it",
+            "Elegir un perfil de forma interactiva y activarlo",
+            "Ein Profil interaktiv auswählen und aktivieren",
+            "Choisir un profil de façon interactive et l'activer",
+        ],
+    ),
+    (
+        "help-switch",
+        [
+            "Activate a profile without prompting",
+            "Activar un perfil sin preguntar",
+            "Ein Profil ohne Rückfrage aktivieren",
+            "Activer un profil sans poser de question",
+        ],
+    ),
+    (
+        "help-list",
+        [
+            "List profiles (use --json for machine-readable output)",
+            "Listar los perfiles (use --json para una salida legible por máquinas)",
+            "Profile auflisten (--json für maschinenlesbare Ausgabe)",
+            "Lister les profils (--json pour une sortie lisible par machine)",
+        ],
+    ),
+    (
+        "help-show",
+        [
+            "Print a profile's outputs, settings and metadata",
+            "Mostrar las salidas, ajustes y metadatos de un perfil",
+            "Ausgänge, Einstellungen und Metadaten eines Profils ausgeben",
+            "Afficher les sorties, réglages et métadonnées d'un profil",
+        ],
+    ),
+    (
+        "help-diff",
+        [
+            "Compare two profiles output by output",
+            "Comparar dos perfiles salida por salida",
+            "Zwei Profile Ausgang für Ausgang vergleichen",
+            "Comparer deux profils sortie par sortie",
+        ],
+    ),
+    (
+        "help-lint",
+        [
+            "Check every profile line against Sway's output grammar",
+            "Comprobar cada línea de perfil con la gramática de salidas de Sway",
+            "Jede Profilzeile gegen Sways Ausgabegrammatik prüfen",
+            "Vérifier chaque ligne de profil selon la grammaire des sorties de Sway",
+        ],
+    ),
+    (
+        "help-format",
+        [
+            "Rewrite the managed section in canonical style (--check)",
+            "Reescribir la sección gestionada en estilo canónico (--check)",
+            "Den verwalteten Abschnitt im kanonischen Stil neu schreiben (--check)",
+            "Réécrire la section gérée dans le style canonique (--check)",
+        ],
+    ),
+    (
+        "help-compose",
+        [
+            "Merge profiles, later ones winning per output (--apply, --save <name>)",
+            "Combinar perfiles; los posteriores prevalecen por salida (--apply, --save <name>)",
+            "Profile zusammenführen, spätere gewinnen je Ausgang (--apply, --save <name>)",
+            "Fusionner des profils, les suivants l'emportant par sortie (--apply, --save <name>)",
+        ],
+    ),
+    (
+        "help-plan",
+        [
+            "Show the Sway commands and config diff for a switch",
+            "Mostrar las órdenes de Sway y el diff de configuración de un cambio",
+            "Sway-Befehle und Konfigurationsdiff für einen Wechsel anzeigen",
+            "Afficher les commandes Sway et le diff de configuration d'un changement",
+        ],
+    ),
+    (
+        "help-apply",
+        [
+            "Execute the most recently shown plan",
+            "Ejecutar el último plan mostrado",
+            "Den zuletzt angezeigten Plan ausführen",
+            "Exécuter le dernier plan affiché",
+        ],
+    ),
+    (
+        "help-export",
+        [
+            "Print a profile as a standalone snippet (--format sway|json)",
+            "Mostrar un perfil como fragmento independiente (--format sway|json)",
+            "Ein Profil als eigenständiges Snippet ausgeben (--format sway|json)",
+            "Afficher un profil comme extrait autonome (--format sway|json)",
+        ],
+    ),
+    (
+        "help-migrate-descriptions",
+        [
+            "Rewrite connector names to \"Make Model Serial\" form",
+            "Reescribir los nombres de conector en la forma \"Make Model Serial\"",
+            "Anschlussnamen in die Form \"Make Model Serial\" umschreiben",
+            "Réécrire les noms de connecteur sous la forme \"Make Model Serial\"",
+        ],
+    ),
+    (
+        "help-migrate-identities",
+        [
+            "Record connector and description of each output, so either one can be used when the other stops matching",
+            "Registrar el conector y la descripción de cada salida, para poder usar uno cuando el otro deje de coincidir",
+            "Anschluss und Beschreibung jedes Ausgangs festhalten, damit eines verwendet werden kann, wenn das andere nicht mehr passt",
+            "Noter le connecteur et la description de chaque sortie, pour utiliser l'un quand l'autre ne correspond plus",
+        ],
+    ),
+    (
+        "help-migrate-adopt",
+        [
+            "Move output lines from elsewhere in the config into a profile",
+            "Mover a un perfil las líneas output del resto de la configuración",
+            "Output-Zeilen von anderswo in der Konfiguration in ein Profil verschieben",
+            "Déplacer dans un profil les lignes output situées ailleurs dans la configuration",
+        ],
+    ),
+    (
+        "help-visual-edit",
+        [
+            "Run wdisplays or nwg-displays, then offer to save the new arrangement into the Enabled profile",
+            "Ejecutar wdisplays o nwg-displays y ofrecer guardar la nueva disposición en el perfil Enabled",
+            "wdisplays oder nwg-displays starten und anbieten, die neue Anordnung im Profil mit Enabled zu speichern",
+            "Lancer wdisplays ou nwg-displays, puis proposer d'enregistrer la nouvelle disposition dans le profil Enabled",
+        ],
+    ),
+    (
+        "help-import",
+        [
+            "Turn profiles exported as JSON, or another tool's saved layout, into profiles, replacing the lines of existing profiles of the same name",
+            "Convertir en perfiles los perfiles exportados como JSON o la disposición guardada por otra herramienta, reemplazando las líneas de los perfiles existentes con el mismo nombre",
+            "Als JSON exportierte Profile oder die gespeicherte Anordnung eines anderen Werkzeugs in Profile umwandeln und dabei die Zeilen gleichnamiger Profile ersetzen",
+            "Transformer des profils exportés en JSON, ou la disposition enregistrée par un autre outil, en profils, en remplaçant les lignes des profils existants du même nom",
+        ],
+    ),
+    (
+        "help-position",
+        [
+            "Move an output in the Enabled profile and apply it",
+            "Mover una salida en el perfil Enabled y aplicarlo",
+            "Einen Ausgang im Profil mit Enabled verschieben und anwenden",
+            "Déplacer une sortie dans le profil Enabled et l'appliquer",
+        ],
+    ),
+    (
+        "help-refresh",
+        [
+            "Set an output's refresh rate in the Enabled profile and apply it; without a rate, list the advertised ones",
+            "Fijar la frecuencia de una salida en el perfil Enabled y aplicarlo; sin frecuencia, listar las anunciadas",
+            "Die Bildwiederholrate eines Ausgangs im Profil mit Enabled setzen und anwenden; ohne Rate die angebotenen auflisten",
+            "Régler la fréquence d'une sortie dans le profil Enabled et l'appliquer ; sans fréquence, lister celles annoncées",
+        ],
+    ),
+    (
+        "help-tearing",
+        [
+            "Allow or forbid tearing on outputs of the Enabled profile",
+            "Permitir o prohibir el tearing en las salidas del perfil Enabled",
+            "Tearing auf Ausgängen des Profils mit Enabled erlauben oder verbieten",
+            "Autoriser ou interdire le tearing sur les sorties du profil Enabled",
+        ],
+    ),
+    (
+        "help-power",
+        [
+            "Switch an output's display power without changing profiles",
+            "Encender o apagar una salida sin cambiar de perfil",
+            "Die Anzeige eines Ausgangs ein- oder ausschalten, ohne das Profil zu wechseln",
+            "Allumer ou éteindre l'affichage d'une sortie sans changer de profil",
+        ],
+    ),
+    (
+        "help-blank",
+        [
+            "Turn every output off until the next input (needs swayidle)",
+            "Apagar todas las salidas hasta la siguiente entrada (necesita swayidle)",
+            "Alle Ausgänge bis zur nächsten Eingabe ausschalten (benötigt swayidle)",
+            "Éteindre toutes les sorties jusqu'à la prochaine saisie (nécessite swayidle)",
+        ],
+    ),
+    (
+        "help-lock",
+        [
+            "Lock the screen with the applied profile's Lock-image and Lock-indicator (runs swaylock)",
+            "Bloquear la pantalla con Lock-image y Lock-indicator del perfil aplicado (ejecuta swaylock)",
+            "Den Bildschirm mit Lock-image und Lock-indicator des angewendeten Profils sperren (startet swaylock)",
+            "Verrouiller l'écran avec Lock-image et Lock-indicator du profil appliqué (lance swaylock)",
+        ],
+    ),
+    (
+        "help-icc-set",
+        [
+            "Use an ICC color profile for an output in the Enabled profile",
+            "Usar un perfil de color ICC para una salida en el perfil Enabled",
+            "Ein ICC-Farbprofil für einen Ausgang im Profil mit Enabled verwenden",
+            "Utiliser un profil de couleur ICC pour une sortie du profil Enabled",
+        ],
+    ),
+    (
+        "help-icc-show",
+        [
+            "List the color profiles set in the Enabled profile",
+            "Listar los perfiles de color del perfil Enabled",
+            "Die im Profil mit Enabled gesetzten Farbprofile auflisten",
+            "Lister les profils de couleur définis dans le profil Enabled",
+        ],
+    ),
+    (
+        "help-generate-bindings",
+        [
+            "Print bindsym lines for switching profiles (--insert to write them into a managed block in the Sway config)",
+            "Mostrar líneas bindsym para cambiar de perfil (--insert para escribirlas en un bloque gestionado de la configuración de Sway)",
+            "bindsym-Zeilen zum Wechseln von Profilen ausgeben (--insert schreibt sie in einen verwalteten Block der Sway-Konfiguration)",
+            "Afficher des lignes bindsym pour changer de profil (--insert pour les écrire dans un bloc géré de la configuration Sway)",
+        ],
+    ),
+    (
+        "help-generate-matrix",
+        [
+            "Print a starter set of profiles for the connected outputs (--insert to add them to the managed section)",
+            "Mostrar un conjunto inicial de perfiles para las salidas conectadas (--insert para añadirlos a la sección gestionada)",
+            "Einen Grundstock an Profilen für die angeschlossenen Ausgänge ausgeben (--insert fügt sie dem verwalteten Abschnitt hinzu)",
+            "Afficher un jeu de profils de départ pour les sorties connectées (--insert pour les ajouter à la section gérée)",
+        ],
+    ),
+    (
+        "help-generate-completions",
+        [
+            "Print a shell completion script (with profile names)",
+            "Mostrar un script de autocompletado para la shell (con nombres de perfil)",
+            "Ein Shell-Vervollständigungsskript ausgeben (mit Profilnamen)",
+            "Afficher un script de complétion pour le shell (avec les noms de profil)",
+        ],
+    ),
+    (
+        "help-names",
+        [
+            "Print profile names and aliases, one per line",
+            "Mostrar los nombres y alias de perfil, uno por línea",
+            "Profilnamen und Aliase ausgeben, einen pro Zeile",
+            "Afficher les noms et alias de profil, un par ligne",
+        ],
+    ),
+    (
+        "help-add-output",
+        [
+            "Add a connected output that no profile mentions yet to a profile (or create a new one)",
+            "Añadir a un perfil (o a uno nuevo) una salida conectada que ningún perfil menciona aún",
+            "Einen angeschlossenen Ausgang, den noch kein Profil nennt, zu einem Profil hinzufügen (oder ein neues anlegen)",
+            "Ajouter à un profil (ou à un nouveau) une sortie connectée qu'aucun profil ne mentionne encore",
+        ],
+    ),
+    (
+        "help-wizard",
+        [
+            "Create a new profile step by step from the connected outputs",
+            "Crear un perfil nuevo paso a paso a partir de las salidas conectadas",
+            "Schritt für Schritt ein neues Profil aus den angeschlossenen Ausgängen erstellen",
+            "Créer pas à pas un nouveau profil à partir des sorties connectées",
+        ],
+    ),
+    (
+        "help-window",
+        [
+            "Open a window listing the profiles with a preview of each, to switch with a click",
+            "Abrir una ventana con los perfiles y una vista previa de cada uno, para cambiar con un clic",
+            "Ein Fenster mit den Profilen und einer Vorschau von jedem öffnen, um per Klick zu wechseln",
+            "Ouvrir une fenêtre listant les profils avec un aperçu de chacun, pour changer d'un clic",
+        ],
+    ),
+    (
+        "help-outputs",
+        [
+            "Tick the connected outputs to have on and apply them, then optionally save them as a new profile",
+            "Marcar las salidas conectadas que deben estar encendidas y aplicarlas, y opcionalmente guardarlas como perfil nuevo",
+            "Die einzuschaltenden angeschlossenen Ausgänge ankreuzen und anwenden, dann optional als neues Profil speichern",
+            "Cocher les sorties connectées à allumer et les appliquer, puis éventuellement les enregistrer comme nouveau profil",
+        ],
+    ),
+    (
+        "help-sockets",
+        [
+            "List the IPC sockets of running Sway instances",
+            "Listar los sockets IPC de las instancias de Sway en ejecución",
+            "Die IPC-Sockets laufender Sway-Instanzen auflisten",
+            "Lister les sockets IPC des instances Sway en cours d'exécution",
+        ],
+    ),
+    (
+        "help-recover",
+        [
+            "Resolve a config write interrupted by a crash",
+            "Resolver una escritura de la configuración interrumpida por un fallo",
+            "Einen durch einen Absturz unterbrochenen Konfigurationsschreibvorgang auflösen",
+            "Résoudre une écriture de configuration interrompue par un plantage",
+        ],
+    ),
+    (
+        "help-backups-list",
+        [
+            "List the copies of the config kept before each write",
+            "Listar las copias de la configuración guardadas antes de cada escritura",
+            "Die vor jedem Schreiben aufbewahrten Kopien der Konfiguration auflisten",
+            "Lister les copies de la configuration conservées avant chaque écriture",
+        ],
+    ),
+    (
+        "help-backups-diff",
+        [
+            "Show how the config differs from a backup (default: latest)",
+            "Mostrar en qué difiere la configuración de una copia (por defecto: la última)",
+            "Zeigen, wie sich die Konfiguration von einer Sicherung unterscheidet (Standard: neueste)",
+            "Montrer en quoi la configuration diffère d'une sauvegarde (par défaut : la plus récente)",
+        ],
+    ),
+    (
+        "help-backups-restore",
+        [
+            "Put a backup back in place of the config",
+            "Poner una copia de seguridad en lugar de la configuración",
+            "Eine Sicherung anstelle der Konfiguration zurücklegen",
+            "Remettre une sauvegarde à la place de la configuration",
+        ],
+    ),
+    (
+        "help-detect",
+        [
+            "Print the role of each connected output: internal, external, largest-external, primary (--json)",
+            "Mostrar el papel de cada salida conectada: internal, external, largest-external, primary (--json)",
+            "Die Rolle jedes angeschlossenen Ausgangs ausgeben: internal, external, largest-external, primary (--json)",
+            "Afficher le rôle de chaque sortie connectée : internal, external, largest-external, primary (--json)",
+        ],
+    ),
+    (
+        "help-test",
+        [
+            "Apply a profile over IPC for a while (--seconds, default 15), then put the outputs back as they were",
+            "Aplicar un perfil por IPC durante un tiempo (--seconds, por defecto 15) y después devolver las salidas a su estado anterior",
+            "Ein Profil eine Weile per IPC anwenden (--seconds, Standard 15) und die Ausgänge dann zurücksetzen",
+            "Appliquer un profil par IPC pendant un moment (--seconds, 15 par défaut), puis remettre les sorties comme avant",
+        ],
+    ),
+    (
+        "help-confirm",
+        [
+            "Keep a switch made with --revert-after",
+            "Conservar un cambio hecho con --revert-after",
+            "Einen mit --revert-after vorgenommenen Wechsel behalten",
+            "Conserver un changement fait avec --revert-after",
+        ],
+    ),
+    (
+        "help-next",
+        [
+            "Switch to the profile after the Enabled one, wrapping around (--tag <tag> to stay among tagged profiles)",
+            "Cambiar al perfil siguiente al Enabled, volviendo al principio al final (--tag <tag> para limitarse a perfiles etiquetados)",
+            "Zum Profil nach dem mit Enabled wechseln, am Ende von vorn (--tag <tag> bleibt bei markierten Profilen)",
+            "Passer au profil suivant le profil Enabled, en revenant au début (--tag <tag> pour rester parmi les profils étiquetés)",
+        ],
+    ),
+    (
+        "help-which",
+        [
+            "Print the profile that best fits the connected outputs",
+            "Mostrar el perfil que mejor encaja con las salidas conectadas",
+            "Das Profil ausgeben, das am besten zu den angeschlossenen Ausgängen passt",
+            "Afficher le profil qui convient le mieux aux sorties connectées",
+        ],
+    ),
+    (
+        "help-get",
+        [
+            "Print one value, e.g. current.name, current.outputs.target or profiles.count, without decoration",
+            "Mostrar un solo valor, p. ej. current.name, current.outputs.target o profiles.count, sin adornos",
+            "Einen einzelnen Wert ausgeben, z. B. current.name, current.outputs.target oder profiles.count, ohne Beiwerk",
+            "Afficher une seule valeur, p. ex. current.name, current.outputs.target ou profiles.count, sans décoration",
+        ],
+    ),
+    (
+        "help-capture",
+        [
+            "Switch to a profile with the output at 1920x1080@60 scale 1 for screen capture (--headless to add a headless mirror)",
+            "Cambiar a un perfil con la salida a 1920x1080@60 escala 1 para capturar la pantalla (--headless para añadir un espejo headless)",
+            "Für Bildschirmaufnahmen zu einem Profil mit dem Ausgang bei 1920x1080@60 Skalierung 1 wechseln (--headless fügt einen Headless-Spiegel hinzu)",
+            "Passer à un profil avec la sortie en 1920x1080@60 échelle 1 pour la capture d'écran (--headless pour ajouter un miroir headless)",
+        ],
+    ),
+    (
+        "help-capture-stop",
+        [
+            "Switch back to the profile active before capture",
+            "Volver al perfil activo antes de la captura",
+            "Zum vor der Aufnahme aktiven Profil zurückwechseln",
+            "Revenir au profil actif avant la capture",
+        ],
+    ),
+    (
+        "help-present",
+        [
+            "Mirror the built-in panel to a newly connected output until it is unplugged or 'present --end' is run",
+            "Duplicar la pantalla integrada en una salida recién conectada hasta que se desconecte o se ejecute 'present --end'",
+            "Das eingebaute Display auf einen neu angeschlossenen Ausgang spiegeln, bis er getrennt oder 'present --end' ausgeführt wird",
+            "Dupliquer l'écran intégré sur une sortie nouvellement connectée jusqu'à son débranchement ou 'present --end'",
+        ],
+    ),
+    (
+        "help-log",
+        [
+            "Print or empty the log of applied switches (--json)",
+            "Mostrar o vaciar el registro de cambios aplicados (--json)",
+            "Das Protokoll angewendeter Wechsel ausgeben oder leeren (--json)",
+            "Afficher ou vider le journal des changements appliqués (--json)",
+        ],
+    ),
+    (
+        "help-events",
+        [
+            "Print switches as JSON events (--follow to keep listening)",
+            "Mostrar los cambios como eventos JSON (--follow para seguir escuchando)",
+            "Wechsel als JSON-Ereignisse ausgeben (--follow hört weiter zu)",
+            "Afficher les changements comme événements JSON (--follow pour continuer à écouter)",
+        ],
+    ),
+    (
+        "help-restore",
+        [
+            "Silently re-apply the Enabled (or best matching) profile",
+            "Volver a aplicar en silencio el perfil Enabled (o el que mejor encaje)",
+            "Das Profil mit Enabled (oder das am besten passende) still erneut anwenden",
+            "Réappliquer silencieusement le profil Enabled (ou le plus adapté)",
+        ],
+    ),
+    (
+        "help-transient",
+        [
+            "Apply via swaymsg only, leaving the config untouched",
+            "Aplicar solo mediante swaymsg, sin tocar la configuración",
+            "Nur über swaymsg anwenden, die Konfiguration bleibt unberührt",
+            "Appliquer uniquement via swaymsg, sans toucher à la configuration",
+        ],
+    ),
+    (
+        "help-watch",
+        [
+            "Keep re-applying the Enabled profile when outputs or the config file change",
+            "Volver a aplicar el perfil Enabled cuando cambien las salidas o el archivo de configuración",
+            "Das Profil mit Enabled erneut anwenden, wenn sich Ausgänge oder die Konfigurationsdatei ändern",
+            "Réappliquer le profil Enabled quand les sorties ou le fichier de configuration changent",
+        ],
+    ),
+    (
+        "help-observe",
+        [
+            "Like --watch, but only log events and what would be applied",
+            "Como --watch, pero solo registrar los eventos y lo que se aplicaría",
+            "Wie --watch, aber nur Ereignisse und das, was angewendet würde, protokollieren",
+            "Comme --watch, mais seulement journaliser les événements et ce qui serait appliqué",
+        ],
+    ),
+    (
+        "help-follow",
+        [
+            "Keep printing events from the --watch daemon (for events)",
+            "Seguir mostrando los eventos del demonio --watch (para events)",
+            "Ereignisse des --watch-Dienstes fortlaufend ausgeben (für events)",
+            "Continuer à afficher les événements du démon --watch (pour events)",
+        ],
+    ),
+    (
+        "help-lines",
+        [
+            "Only print the last <n> logged events (for events)",
+            "Mostrar solo los últimos <n> eventos registrados (para events)",
+            "Nur die letzten <n> protokollierten Ereignisse ausgeben (für events)",
+            "N'afficher que les <n> derniers événements enregistrés (pour events)",
+        ],
+    ),
+    (
+        "help-since",
+        [
+            "Only print events of the last <secs> seconds (for events)",
+            "Mostrar solo los eventos de los últimos <secs> segundos (para events)",
+            "Nur Ereignisse der letzten <secs> Sekunden ausgeben (für events)",
+            "N'afficher que les événements des <secs> dernières secondes (pour events)",
+        ],
+    ),
+    (
+        "help-from-udev",
+        [
+            "Switch to the best matching profile without prompting (used by the helper from 'generate udev')",
+            "Cambiar al perfil que mejor encaje sin preguntar (lo usa el asistente de 'generate udev')",
+            "Ohne Rückfrage zum am besten passenden Profil wechseln (vom Helfer aus 'generate udev' verwendet)",
+            "Passer au profil le plus adapté sans poser de question (utilisé par l'assistant de 'generate udev')",
+        ],
+    ),
+    (
+        "help-json",
+        [
+            "Print machine-readable JSON",
+            "Mostrar JSON legible por máquinas",
+            "Maschinenlesbares JSON ausgeben",
+            "Afficher du JSON lisible par machine",
+        ],
+    ),
+    (
+        "help-format-option",
+        [
+            "Output format for export: sway (default) or json; for list: csv or tsv",
+            "Formato de salida para export: sway (por defecto) o json; para list: csv o tsv",
+            "Ausgabeformat für export: sway (Standard) oder json; für list: csv oder tsv",
+            "Format de sortie pour export : sway (par défaut) ou json ; pour list : csv ou tsv",
+        ],
+    ),
+    (
+        "help-sort",
+        [
+            "Order for list: file (default), name, recent or match",
+            "Orden para list: file (por defecto), name, recent o match",
+            "Reihenfolge für list: file (Standard), name, recent oder match",
+            "Ordre pour list : file (par défaut), name, recent ou match",
+        ],
+    ),
+    (
+        "help-favorites",
+        [
+            "Only show profiles marked Favorite (list and picker)",
+            "Mostrar solo los perfiles marcados como Favorite (list y selector)",
+            "Nur als Favorite markierte Profile anzeigen (list und Auswahl)",
+            "N'afficher que les profils marqués Favorite (list et sélecteur)",
+        ],
+    ),
+    (
+        "help-long",
+        [
+            "List each profile's outputs with mode, position, scale",
+            "Listar las salidas de cada perfil con modo, posición y escala",
+            "Die Ausgänge jedes Profils mit Modus, Position, Skalierung auflisten",
+            "Lister les sorties de chaque profil avec mode, position, échelle",
+        ],
+    ),
+    (
+        "help-output",
+        [
+            "Only list profiles that configure this output",
+            "Listar solo los perfiles que configuran esta salida",
+            "Nur Profile auflisten, die diesen Ausgang konfigurieren",
+            "Ne lister que les profils qui configurent cette sortie",
+        ],
+    ),
+    (
+        "help-verbose",
+        [
+            "Show the score breakdown of every profile (for which)",
+            "Mostrar el desglose de puntuación de cada perfil (para which)",
+            "Die Punkteaufschlüsselung jedes Profils anzeigen (für which)",
+            "Afficher le détail du score de chaque profil (pour which)",
+        ],
+    ),
+    (
+        "help-socket",
+        [
+            "Talk to the Sway instance listening on this socket",
+            "Hablar con la instancia de Sway que escucha en este socket",
+            "Mit der Sway-Instanz sprechen, die auf diesem Socket lauscht",
+            "Dialoguer avec l'instance Sway qui écoute sur ce socket",
+        ],
+    ),
+    (
+        "help-seat",
+        [
+            "Keep state for this seat separate from other seats",
+            "Mantener el estado de este seat separado de los demás",
+            "Den Zustand dieses Seats getrennt von anderen Seats halten",
+            "Garder l'état de ce seat séparé des autres seats",
+        ],
+    ),
+    (
+        "help-host",
+        [
+            "Manage the Sway session on another machine over SSH, e.g. --host me@media-center",
+            "Gestionar la sesión de Sway de otra máquina por SSH, p. ej. --host me@media-center",
+            "Die Sway-Sitzung auf einem anderen Rechner über SSH verwalten, z. B. --host me@media-center",
+            "Gérer la session Sway d'une autre machine via SSH, p. ex. --host me@media-center",
+        ],
+    ),
+    (
+        "help-insert",
+        [
+            "Insert generated lines into the Sway config",
+            "Insertar las líneas generadas en la configuración de Sway",
+            "Erzeugte Zeilen in die Sway-Konfiguration einfügen",
+            "Insérer les lignes générées dans la configuration Sway",
+        ],
+    ),
+    (
+        "help-check",
+        [
+            "Only report what format would change (exit 1 if anything)",
+            "Solo informar de lo que format cambiaría (sale con 1 si hay algo)",
+            "Nur melden, was format ändern würde (Exit-Status 1, falls etwas)",
+            "Seulement indiquer ce que format changerait (sortie 1 s'il y a quelque chose)",
+        ],
+    ),
+    (
+        "help-only",
+        [
+            "Apply only these outputs of the profile (for switch)",
+            "Aplicar solo estas salidas del perfil (para switch)",
+            "Nur diese Ausgänge des Profils anwenden (für switch)",
+            "N'appliquer que ces sorties du profil (pour switch)",
+        ],
+    ),
+    (
+        "help-set",
+        [
+            "Override a setting of the profile for this switch, e.g. DP-1.refresh=144 or DP-1.scale=1.25 (repeatable, for switch)",
+            "Sustituir un ajuste del perfil para este cambio, p. ej. DP-1.refresh=144 o DP-1.scale=1.25 (repetible, para switch)",
+            "Eine Einstellung des Profils für diesen Wechsel überschreiben, z. B. DP-1.refresh=144 oder DP-1.scale=1.25 (wiederholbar, für switch)",
+            "Remplacer un réglage du profil pour ce changement, p. ex. DP-1.refresh=144 ou DP-1.scale=1.25 (répétable, pour switch)",
+        ],
+    ),
+    (
+        "help-persist",
+        [
+            "Save the --set overrides into the profile",
+            "Guardar en el perfil los cambios de --set",
+            "Die --set-Überschreibungen im Profil speichern",
+            "Enregistrer dans le profil les remplacements de --set",
+        ],
+    ),
+    (
+        "help-revert-after",
+        [
+            "Switch back unless 'confirm' is run within <secs>",
+            "Volver atrás salvo que se ejecute 'confirm' en <secs>",
+            "Zurückwechseln, sofern 'confirm' nicht innerhalb von <secs> ausgeführt wird",
+            "Revenir en arrière sauf si 'confirm' est lancé dans les <secs>",
+        ],
+    ),
+    (
+        "help-timeout",
+        [
+            "Apply the default profile if the picker gets no answer",
+            "Aplicar el perfil predeterminado si el selector no recibe respuesta",
+            "Das Standardprofil anwenden, wenn die Auswahl keine Antwort erhält",
+            "Appliquer le profil par défaut si le sélecteur ne reçoit pas de réponse",
+        ],
+    ),
+    (
+        "help-default",
+        [
+            "Profile the picker applies on --timeout (default: the Enabled one)",
+            "Perfil que el selector aplica con --timeout (por defecto: el Enabled)",
+            "Profil, das die Auswahl bei --timeout anwendet (Standard: das mit Enabled)",
+            "Profil que le sélecteur applique avec --timeout (par défaut : le profil Enabled)",
+        ],
+    ),
+    (
+        "help-headless",
+        [
+            "Also create a headless output mirroring it (for capture)",
+            "Crear además una salida headless que la duplique (para capture)",
+            "Zusätzlich einen Headless-Ausgang anlegen, der ihn spiegelt (für capture)",
+            "Créer aussi une sortie headless qui la duplique (pour capture)",
+        ],
+    ),
+    (
+        "help-end",
+        [
+            "Stop presenting and restore the outputs (for present)",
+            "Dejar de presentar y restaurar las salidas (para present)",
+            "Präsentieren beenden und die Ausgänge wiederherstellen (für present)",
+            "Arrêter la présentation et rétablir les sorties (pour present)",
+        ],
+    ),
+    (
+        "help-apply-option",
+        [
+            "Apply the composed profile (for compose)",
+            "Aplicar el perfil combinado (para compose)",
+            "Das zusammengesetzte Profil anwenden (für compose)",
+            "Appliquer le profil composé (pour compose)",
+        ],
+    ),
+    (
+        "help-save",
+        [
+            "Save the composed profile under this name (for compose)",
+            "Guardar el perfil combinado con este nombre (para compose)",
+            "Das zusammengesetzte Profil unter diesem Namen speichern (für compose)",
+            "Enregistrer le profil composé sous ce nom (pour compose)",
+        ],
+    ),
+    (
+        "help-relative-to",
+        [
+            "Place relative to another output (for position)",
+            "Colocar respecto a otra salida (para position)",
+            "Relativ zu einem anderen Ausgang platzieren (für position)",
+            "Placer par rapport à une autre sortie (pour position)",
+        ],
+    ),
+    (
+        "help-tag",
+        [
+            "Only cycle among profiles with this tag (for next)",
+            "Recorrer solo los perfiles con esta etiqueta (para next)",
+            "Nur Profile mit diesem Tag durchlaufen (für next)",
+            "Ne parcourir que les profils avec cette étiquette (pour next)",
+        ],
+    ),
+    (
+        "help-auto-scale",
+        [
+            "Fill in the DPI-based scale suggestion (for wizard)",
+            "Rellenar la escala sugerida según los DPI (para wizard)",
+            "Den DPI-basierten Skalierungsvorschlag übernehmen (für wizard)",
+            "Remplir l'échelle suggérée d'après le DPI (pour wizard)",
+        ],
+    ),
+    (
+        "help-system",
+        [
+            "Manage /etc/sway/config instead of the user's config",
+            "Gestionar /etc/sway/config en lugar de la configuración del usuario",
+            "/etc/sway/config statt der Benutzerkonfiguration verwalten",
+            "Gérer /etc/sway/config au lieu de la configuration de l'utilisateur",
+        ],
+    ),
+    (
+        "help-force",
+        [
+            "Apply a profile even if it turns off every connected output",
+            "Aplicar un perfil aunque apague todas las salidas conectadas",
+            "Ein Profil auch dann anwenden, wenn es alle angeschlossenen Ausgänge ausschaltet",
+            "Appliquer un profil même s'il éteint toutes les sorties connectées",
+        ],
+    ),
+    (
+        "help-strict",
+        [
+            "Fail on malformed lines in the managed section",
+            "Fallar ante líneas mal formadas en la sección gestionada",
+            "Bei fehlerhaften Zeilen im verwalteten Abschnitt abbrechen",
+            "Échouer sur les lignes mal formées de la section gérée",
+        ],
+    ),
+    (
+        "help-help",
+        [
+            "Show this help",
+            "Mostrar esta ayuda",
+            "Diese Hilfe anzeigen",
+            "Afficher cette aide",
+        ],
+    ),
+    // Commands
+    (
+        "usage-write-config",
+        [
+            "usage: write-config <staged file> <config>",
+            "uso: write-config <archivo preparado> <configuración>",
+            "Verwendung: write-config <vorbereitete Datei> <Konfiguration>",
+            "utilisation : write-config <fichier préparé> <configuration>",
+        ],
+    ),
+    (
+        "host-conflict",
+        [
+            "--host cannot be combined with --watch, --observe or --from-udev.",
+            "--host no se puede combinar con --watch, --observe ni --from-udev.",
+            "--host kann nicht mit --watch, --observe oder --from-udev kombiniert werden.",
+            "--host ne peut pas être combiné avec --watch, --observe ou --from-udev.",
+        ],
+    ),
+    (
+        "no-tagged-profile",
+        [
+            "no profile has the tag '{}'.",
+            "ningún perfil tiene la etiqueta '{}'.",
+            "Kein Profil hat das Tag '{}'.",
+            "aucun profil n'a l'étiquette '{}'.",
+        ],
+    ),
+    (
+        "no-profiles",
+        [
+            "there are no profiles to choose from.",
+            "no hay perfiles entre los que elegir.",
+            "Es gibt keine Profile zur Auswahl.",
+            "il n'y a aucun profil parmi lesquels choisir.",
+        ],
+    ),
+    (
+        "run-apply",
+        [
+            "Run 'sway-display-switcher apply' to execute this plan.",
+            "Ejecute 'sway-display-switcher apply' para llevar a cabo este plan.",
+            "Führen Sie 'sway-display-switcher apply' aus, um diesen Plan auszuführen.",
+            "Lancez 'sway-display-switcher apply' pour exécuter ce plan.",
+        ],
+    ),
+    (
+        "plan-applied",
+        [
+            "Applied plan for '{}'.",
+            "Plan para '{}' aplicado.",
+            "Plan für '{}' angewendet.",
+            "Plan pour '{}' appliqué.",
+        ],
+    ),
+    (
+        "unknown-list-format",
+        [
+            "unknown list format '{}'; use csv or tsv.",
+            "formato de lista desconocido '{}'; use csv o tsv.",
+            "Unbekanntes Listenformat '{}'; verwenden Sie csv oder tsv.",
+            "format de liste inconnu '{}' ; utilisez csv ou tsv.",
+        ],
+    ),
+    (
+        "usage-diff",
+        [
+            "usage: diff <profile-a> <profile-b>",
+            "uso: diff <perfil-a> <perfil-b>",
+            "Verwendung: diff <Profil-a> <Profil-b>",
+            "utilisation : diff <profil-a> <profil-b>",
+        ],
+    ),
+    (
+        "profiles-same",
+        [
+            "'{}' and '{}' have the same outputs and metadata.",
+            "'{}' y '{}' tienen las mismas salidas y metadatos.",
+            "'{}' und '{}' haben dieselben Ausgänge und Metadaten.",
+            "'{}' et '{}' ont les mêmes sorties et métadonnées.",
+        ],
+    ),
+    (
+        "already-formatted",
+        [
+            "The managed section is already formatted.",
+            "La sección gestionada ya está formateada.",
+            "Der verwaltete Abschnitt ist bereits formatiert.",
+            "La section gérée est déjà formatée.",
+        ],
+    ),
+    (
+        "not-formatted",
+        [
+            "the managed section is not formatted; run 'format' to fix it.",
+            "la sección gestionada no está formateada; ejecute 'format' para corregirla.",
+            "Der verwaltete Abschnitt ist nicht formatiert; führen Sie 'format' aus, um das zu beheben.",
+            "la section gérée n'est pas formatée ; lancez 'format' pour la corriger.",
+        ],
+    ),
+    (
+        "formatted",
+        [
+            "Formatted the managed section of {}.",
+            "Sección gestionada de {} formateada.",
+            "Verwalteter Abschnitt von {} formatiert.",
+            "Section gérée de {} formatée.",
+        ],
+    ),
+    (
+        "did-you-mean",
+        [
+            "did you mean: {}",
+            "quiso decir: {}",
+            "meinten Sie: {}",
+            "vouliez-vous dire : {}",
+        ],
+    ),
+    (
+        "problems-found",
+        [
+            "{} problems found.",
+            "se encontraron {} problemas.",
+            "{} Probleme gefunden.",
+            "{} problèmes trouvés.",
+        ],
+    ),
+    (
+        "no-problems",
+        [
+            "No problems found.",
+            "No se encontraron problemas.",
+            "Keine Probleme gefunden.",
+            "Aucun problème trouvé.",
+        ],
+    ),
+    (
+        "unknown-export-format",
+        [
+            "unknown export format '{}'.",
+            "formato de exportación desconocido '{}'.",
+            "Unbekanntes Exportformat '{}'.",
+            "format d'export inconnu '{}'.",
+        ],
+    ),
+    (
+        "usage-migrate",
+        [
+            "usage: migrate descriptions|identities|adopt",
+            "uso: migrate descriptions|identities|adopt",
+            "Verwendung: migrate descriptions|identities|adopt",
+            "utilisation : migrate descriptions|identities|adopt",
+        ],
+    ),
+    (
+        "bindings-updated",
+        [
+            "Updated the display keybinding block in {}.",
+            "Bloque de atajos de pantalla actualizado en {}.",
+            "Block der Anzeige-Tastenkürzel in {} aktualisiert.",
+            "Bloc des raccourcis d'affichage mis à jour dans {}.",
+        ],
+    ),
+    (
+        "matrix-exists",
+        [
+            "Every generated profile already exists.",
+            "Todos los perfiles generados ya existen.",
+            "Alle erzeugten Profile existieren bereits.",
+            "Tous les profils générés existent déjà.",
+        ],
+    ),
+    (
+        "no-completions",
+        [
+            "no completions for shell '{}'.",
+            "no hay autocompletado para la shell '{}'.",
+            "Keine Vervollständigung für die Shell '{}'.",
+            "aucune complétion pour le shell '{}'.",
+        ],
+    ),
+    (
+        "usage-generate",
+        [
+            "usage: generate bindings|matrix|sway [--insert] | generate completions <shell> | generate udev [<dir>]",
+            "uso: generate bindings|matrix|sway [--insert] | generate completions <shell> | generate udev [<directorio>]",
+            "Verwendung: generate bindings|matrix|sway [--insert] | generate completions <Shell> | generate udev [<Verzeichnis>]",
+            "utilisation : generate bindings|matrix|sway [--insert] | generate completions <shell> | generate udev [<répertoire>]",
+        ],
+    ),
+    (
+        "usage-gpu",
+        [
+            "usage: gpu list|detach",
+            "uso: gpu list|detach",
+            "Verwendung: gpu list|detach",
+            "utilisation : gpu list|detach",
+        ],
+    ),
+    (
+        "keeping-settings",
+        [
+            "Keeping the new settings; '{}' will not come back.",
+            "Se conservan los nuevos ajustes; '{}' no volverá.",
+            "Die neuen Einstellungen bleiben; '{}' kommt nicht zurück.",
+            "Les nouveaux réglages sont conservés ; '{}' ne reviendra pas.",
+        ],
+    ),
+    (
+        "nothing-to-confirm",
+        [
+            "No switch is waiting to be confirmed.",
+            "No hay ningún cambio pendiente de confirmación.",
+            "Kein Wechsel wartet auf Bestätigung.",
+            "Aucun changement n'attend de confirmation.",
+        ],
+    ),
+    (
+        "selected-marker",
+        [
+            " (selected)",
+            " (seleccionado)",
+            " (ausgewählt)",
+            " (sélectionné)",
+        ],
+    ),
+    (
+        "no-daemon",
+        [
+            "no daemon is running to follow ({}); start one with --watch.",
+            "no hay ningún demonio en ejecución que seguir ({}); inicie uno con --watch.",
+            "Es läuft kein Dienst, dem gefolgt werden kann ({}); starten Sie einen mit --watch.",
+            "aucun démon à suivre n'est en cours d'exécution ({}) ; lancez-en un avec --watch.",
+        ],
+    ),
+    (
+        "daemon-stopped",
+        [
+            "the daemon stopped.",
+            "el demonio se detuvo.",
+            "Der Dienst wurde beendet.",
+            "le démon s'est arrêté.",
+        ],
+    ),
+    (
+        "log-empty",
+        [
+            "No switches have been logged yet.",
+            "Aún no se ha registrado ningún cambio.",
+            "Es wurden noch keine Wechsel protokolliert.",
+            "Aucun changement n'a encore été journalisé.",
+        ],
+    ),
+    (
+        "cleared",
+        [
+            "Cleared {}.",
+            "{} vaciado.",
+            "{} geleert.",
+            "{} vidé.",
+        ],
+    ),
+    (
+        "usage-log",
+        [
+            "usage: log show|clear",
+            "uso: log show|clear",
+            "Verwendung: log show|clear",
+            "utilisation : log show|clear",
+        ],
+    ),
+    (
+        "hand-edited",
+        [
+            "Note: the managed section was edited by hand; the edits were parsed and are kept.",
+            "Nota: la sección gestionada se editó a mano; los cambios se interpretaron y se conservan.",
+            "Hinweis: Der verwaltete Abschnitt wurde von Hand bearbeitet; die Änderungen wurden gelesen und bleiben erhalten.",
+            "Remarque : la section gérée a été modifiée à la main ; les modifications ont été lues et sont conservées.",
+        ],
+    ),
+    (
+        "hand-edited-lossy",
+        [
+            "the managed section was edited by hand, and rewriting it would change these lines:",
+            "la sección gestionada se editó a mano, y reescribirla cambiaría estas líneas:",
+            "Der verwaltete Abschnitt wurde von Hand bearbeitet, und ein Neuschreiben würde diese Zeilen ändern:",
+            "la section gérée a été modifiée à la main, et la réécrire modifierait ces lignes :",
+        ],
+    ),
+    (
+        "refusing-rewrite",
+        [
+            "refusing to rewrite the section; fix these lines or run interactively.",
+            "no se reescribe la sección; corrija estas líneas o ejecute de forma interactiva.",
+            "Der Abschnitt wird nicht neu geschrieben; beheben Sie diese Zeilen oder führen Sie den Befehl interaktiv aus.",
+            "refus de réécrire la section ; corrigez ces lignes ou lancez la commande de façon interactive.",
+        ],
+    ),
+    (
+        "rewrite-anyway",
+        [
+            "Rewrite the section anyway?",
+            "¿Reescribir la sección de todos modos?",
+            "Abschnitt trotzdem neu schreiben?",
+            "Réécrire la section quand même ?",
+        ],
+    ),
+    (
+        "malformed-count",
+        [
+            "{} malformed lines in the managed section:",
+            "{} líneas mal formadas en la sección gestionada:",
+            "{} fehlerhafte Zeilen im verwalteten Abschnitt:",
+            "{} lignes mal formées dans la section gérée :",
+        ],
+    ),
+    (
+        "malformed-line",
+        [
+            "line {}: {}: {}",
+            "línea {}: {}: {}",
+            "Zeile {}: {}: {}",
+            "ligne {} : {} : {}",
+        ],
+    ),
+    (
+        "kept-as-comment",
+        [
+            "kept as a comment",
+            "se conserva como comentario",
+            "als Kommentar beibehalten",
+            "conservée comme commentaire",
+        ],
+    ),
+    (
+        "kept-in-place",
+        [
+            "kept where it is",
+            "se deja donde está",
+            "bleibt, wo sie ist",
+            "laissée à sa place",
+        ],
+    ),
+    (
+        "malformed-warning",
+        [
+            "{}:{}: {}; {}: {}",
+            "{}:{}: {}; {}: {}",
+            "{}:{}: {}; {}: {}",
+            "{}:{} : {} ; {} : {}",
+        ],
+    ),
+    (
+        "interrupted-complete",
+        [
+            "a previous run was interrupted after writing the new config to {} but before moving it into place.",
+            "una ejecución anterior se interrumpió tras escribir la nueva configuración en {} pero antes de moverla a su sitio.",
+            "Ein vorheriger Lauf wurde unterbrochen, nachdem die neue Konfiguration nach {} geschrieben, aber bevor sie an ihren Platz verschoben wurde.",
+            "une exécution précédente a été interrompue après l'écriture de la nouvelle configuration dans {} mais avant sa mise en place.",
+        ],
+    ),
+    (
+        "finish-write",
+        [
+            "Finish the interrupted write?",
+            "¿Completar la escritura interrumpida?",
+            "Unterbrochenen Schreibvorgang abschließen?",
+            "Terminer l'écriture interrompue ?",
+        ],
+    ),
+    (
+        "interrupted-incomplete",
+        [
+            "a previous run was interrupted while writing {}; {} was not changed.",
+            "una ejecución anterior se interrumpió mientras escribía {}; {} no se modificó.",
+            "Ein vorheriger Lauf wurde beim Schreiben von {} unterbrochen; {} wurde nicht geändert.",
+            "une exécution précédente a été interrompue pendant l'écriture de {} ; {} n'a pas été modifié.",
+        ],
+    ),
+    (
+        "discard-partial",
+        [
+            "Discard the partial file?",
+            "¿Descartar el archivo parcial?",
+            "Unvollständige Datei verwerfen?",
+            "Supprimer le fichier partiel ?",
+        ],
+    ),
+    (
+        "run-recover",
+        [
+            "Run 'sway-display-switcher recover finish|discard' to resolve it.",
+            "Ejecute 'sway-display-switcher recover finish|discard' para resolverlo.",
+            "Führen Sie 'sway-display-switcher recover finish|discard' aus, um das zu beheben.",
+            "Lancez 'sway-display-switcher recover finish|discard' pour le résoudre.",
+        ],
+    ),
+    (
+        "usage-recover",
+        [
+            "usage: recover finish|discard",
+            "uso: recover finish|discard",
+            "Verwendung: recover finish|discard",
+            "utilisation : recover finish|discard",
+        ],
+    ),
+    (
+        "nothing-to-recover",
+        [
+            "No interrupted write to recover from.",
+            "No hay ninguna escritura interrumpida que recuperar.",
+            "Kein unterbrochener Schreibvorgang zum Wiederherstellen.",
+            "Aucune écriture interrompue à récupérer.",
+        ],
+    ),
+    (
+        "write-incomplete",
+        [
+            "the interrupted write is incomplete; it can only be discarded.",
+            "la escritura interrumpida está incompleta; solo se puede descartar.",
+            "Der unterbrochene Schreibvorgang ist unvollständig; er kann nur verworfen werden.",
+            "l'écriture interrompue est incomplète ; elle ne peut qu'être supprimée.",
+        ],
+    ),
+    (
+        "recovered",
+        [
+            "Recovered {}.",
+            "{} recuperado.",
+            "{} wiederhergestellt.",
+            "{} récupéré.",
+        ],
+    ),
+    (
+        "no-backups-yet",
+        [
+            "There are no backups of {} yet.",
+            "Aún no hay copias de seguridad de {}.",
+            "Es gibt noch keine Sicherungen von {}.",
+            "Il n'y a pas encore de sauvegarde de {}.",
+        ],
+    ),
+    (
+        "same-as-backup",
+        [
+            "The config is the same as the backup from {}.",
+            "La configuración es igual a la copia de seguridad de {}.",
+            "Die Konfiguration entspricht der Sicherung von {}.",
+            "La configuration est identique à la sauvegarde de {}.",
+        ],
+    ),
+    (
+        "restored-backup",
+        [
+            "Restored {} from the backup from {}; reload Sway to apply it.",
+            "{} restaurado a partir de la copia de seguridad de {}; recargue Sway para aplicarlo.",
+            "{} aus der Sicherung von {} wiederhergestellt; laden Sie Sway neu, um sie anzuwenden.",
+            "{} rétabli depuis la sauvegarde de {} ; rechargez Sway pour l'appliquer.",
+        ],
+    ),
+    (
+        "usage-backups",
+        [
+            "usage: backups list|diff [<timestamp>]|restore <timestamp>",
+            "uso: backups list|diff [<marca de tiempo>]|restore <marca de tiempo>",
+            "Verwendung: backups list|diff [<Zeitstempel>]|restore <Zeitstempel>",
+            "utilisation : backups list|diff [<horodatage>]|restore <horodatage>",
+        ],
+    ),
+    (
+        "picker-without-terminal",
+        [
+            "choosing a profile without a terminal",
+            "elegir un perfil sin terminal",
+            "Die Profilauswahl ohne Terminal",
+            "choisir un profil sans terminal",
+        ],
+    ),
+    (
+        "profile-picker",
+        [
+            "the profile picker",
+            "el selector de perfiles",
+            "Die Profilauswahl",
+            "le sélecteur de profils",
+        ],
+    ),
+    (
+        "unknown-output-notice",
+        [
+            "Notice: {} ({}) is not in any profile; run 'sway-display-switcher add-output' to add it.",
+            "Aviso: {} ({}) no está en ningún perfil; ejecute 'sway-display-switcher add-output' para añadirla.",
+            "Hinweis: {} ({}) ist in keinem Profil; führen Sie 'sway-display-switcher add-output' aus, um es hinzuzufügen.",
+            "Remarque : {} ({}) ne figure dans aucun profil ; lancez 'sway-display-switcher add-output' pour l'ajouter.",
+        ],
+    ),
+    (
+        "usage-compose",
+        [
+            "usage: compose <profile> <profile>... [--apply] [--save <name>]",
+            "uso: compose <perfil> <perfil>... [--apply] [--save <nombre>]",
+            "Verwendung: compose <Profil> <Profil>... [--apply] [--save <Name>]",
+            "utilisation : compose <profil> <profil>... [--apply] [--save <nom>]",
+        ],
+    ),
+    (
+        "profile-named-exists",
+        [
+            "a profile named '{}' already exists.",
+            "ya existe un perfil llamado '{}'.",
+            "Ein Profil namens '{}' existiert bereits.",
+            "un profil nommé '{}' existe déjà.",
+        ],
+    ),
+    (
+        "persist-transient",
+        [
+            "--persist cannot be combined with --transient.",
+            "--persist no se puede combinar con --transient.",
+            "--persist kann nicht mit --transient kombiniert werden.",
+            "--persist ne peut pas être combiné avec --transient.",
+        ],
+    ),
+    (
+        "applied-only",
+        [
+            "Enabled '{}'; applied only {}.",
+            "'{}' activado; solo se aplicó {}.",
+            "'{}' aktiviert; nur {} angewendet.",
+            "'{}' activé ; seul {} a été appliqué.",
+        ],
+    ),
+    (
+        "overrides-saved",
+        [
+            "Saved {} into '{}'.",
+            "{} guardado en '{}'.",
+            "{} in '{}' gespeichert.",
+            "{} enregistré dans '{}'.",
+        ],
+    ),
+    (
+        "overrides-applied",
+        [
+            "Applied {} until the next Sway reload.",
+            "{} aplicado hasta la próxima recarga de Sway.",
+            "{} bis zum nächsten Neuladen von Sway angewendet.",
+            "{} appliqué jusqu'au prochain rechargement de Sway.",
+        ],
+    ),
+    (
+        "no-previous",
+        [
+            "there is no previous profile to revert to.",
+            "no hay ningún perfil anterior al que volver.",
+            "Es gibt kein vorheriges Profil, zu dem zurückgekehrt werden kann.",
+            "il n'y a aucun profil précédent auquel revenir.",
+        ],
+    ),
+    (
+        "no-executable",
+        [
+            "cannot find this program to start the watchdog.",
+            "no se encuentra este programa para iniciar el vigilante.",
+            "Dieses Programm wurde nicht gefunden, um den Wächter zu starten.",
+            "impossible de trouver ce programme pour lancer le chien de garde.",
+        ],
+    ),
+    (
+        "watchdog-failed",
+        [
+            "failed to start the watchdog.",
+            "no se pudo iniciar el vigilante.",
+            "Der Wächter konnte nicht gestartet werden.",
+            "impossible de lancer le chien de garde.",
+        ],
+    ),
+    (
+        "run-confirm",
+        [
+            "Run 'sway-display-switcher confirm' within {} seconds to keep this; otherwise '{}' comes back.",
+            "Ejecute 'sway-display-switcher confirm' en {} segundos para conservarlo; si no, vuelve '{}'.",
+            "Führen Sie innerhalb von {} Sekunden 'sway-display-switcher confirm' aus, um dies zu behalten; sonst kommt '{}' zurück.",
+            "Lancez 'sway-display-switcher confirm' dans les {} secondes pour le conserver ; sinon '{}' revient.",
+        ],
+    ),
+    (
+        "usage-watchdog",
+        [
+            "usage: watchdog <deadline>",
+            "uso: watchdog <plazo>",
+            "Verwendung: watchdog <Frist>",
+            "utilisation : watchdog <échéance>",
+        ],
+    ),
+    (
+        "revert-countdown",
+        [
+            "'{}' comes back in {} seconds unless you keep the new settings.",
+            "'{}' vuelve en {} segundos salvo que conserve los nuevos ajustes.",
+            "'{}' kommt in {} Sekunden zurück, sofern Sie die neuen Einstellungen nicht behalten.",
+            "'{}' revient dans {} secondes sauf si vous conservez les nouveaux réglages.",
+        ],
+    ),
+    (
+        "keep-settings-title",
+        [
+            "Keep these display settings?",
+            "¿Conservar estos ajustes de pantalla?",
+            "Diese Anzeigeeinstellungen behalten?",
+            "Conserver ces réglages d'affichage ?",
+        ],
+    ),
+    (
+        "keep",
+        [
+            "Keep",
+            "Conservar",
+            "Behalten",
+            "Conserver",
+        ],
+    ),
+    (
+        "reverted-title",
+        [
+            "Display settings reverted",
+            "Ajustes de pantalla revertidos",
+            "Anzeigeeinstellungen zurückgesetzt",
+            "Réglages d'affichage rétablis",
+        ],
+    ),
+    (
+        "reverted",
+        [
+            "Went back to '{}' as the switch was not confirmed.",
+            "Se volvió a '{}' porque el cambio no se confirmó.",
+            "Zu '{}' zurückgekehrt, da der Wechsel nicht bestätigt wurde.",
+            "Retour à '{}' car le changement n'a pas été confirmé.",
+        ],
+    ),
+    (
+        "unknown-sort",
+        [
+            "unknown sort order '{}'.",
+            "orden desconocido '{}'.",
+            "Unbekannte Sortierung '{}'.",
+            "ordre de tri inconnu '{}'.",
+        ],
+    ),
+    (
+        "already-set-up",
+        [
+            "{} is already set up.",
+            "{} ya está configurado.",
+            "{} ist bereits eingerichtet.",
+            "{} est déjà configuré.",
+        ],
+    ),
+    (
+        "updated",
+        [
+            "Updated {}.",
+            "{} actualizado.",
+            "{} aktualisiert.",
+            "{} mis à jour.",
+        ],
+    ),
+    (
+        "no-user",
+        [
+            "USER is not set; cannot tell whose Sway session to manage.",
+            "USER no está definido; no se sabe qué sesión de Sway gestionar.",
+            "USER ist nicht gesetzt; es ist unklar, wessen Sway-Sitzung verwaltet werden soll.",
+            "USER n'est pas défini ; impossible de savoir quelle session Sway gérer.",
+        ],
+    ),
+    (
+        "udev-written",
+        [
+            "Wrote {} and {}. Install them with:",
+            "Se escribieron {} y {}. Instálelos con:",
+            "{} und {} geschrieben. Installieren Sie sie mit:",
+            "{} et {} écrits. Installez-les avec :",
+        ],
+    ),
+    (
+        "usage-capture",
+        [
+            "usage: capture <output> [--headless] | capture stop",
+            "uso: capture <salida> [--headless] | capture stop",
+            "Verwendung: capture <Ausgabe> [--headless] | capture stop",
+            "utilisation : capture <sortie> [--headless] | capture stop",
+        ],
+    ),
+    (
+        "not-capturing",
+        [
+            "not capturing; the Enabled profile is not '{}'.",
+            "no se está capturando; el perfil Enabled no es '{}'.",
+            "Keine Aufnahme aktiv; das Profil mit Enabled ist nicht '{}'.",
+            "aucune capture en cours ; le profil Enabled n'est pas '{}'.",
+        ],
+    ),
+    (
+        "waiting-for-editor",
+        [
+            "Waiting for {} to exit...",
+            "Esperando a que {} termine...",
+            "Warte auf das Beenden von {}...",
+            "En attente de la fin de {}...",
+        ],
+    ),
+    (
+        "run-failed",
+        [
+            "failed to run {}: {}",
+            "no se pudo ejecutar {}: {}",
+            "{} konnte nicht ausgeführt werden: {}",
+            "impossible de lancer {} : {}",
+        ],
+    ),
+    (
+        "or",
+        [
+            " or ",
+            " o ",
+            " oder ",
+            " ou ",
+        ],
+    ),
+    (
+        "not-installed",
+        [
+            "{} is not installed.",
+            "{} no está instalado.",
+            "{} ist nicht installiert.",
+            "{} n'est pas installé.",
+        ],
+    ),
+    (
+        "as-arranged",
+        [
+            "as arranged now",
+            "según la disposición actual",
+            "wie jetzt angeordnet",
+            "selon la disposition actuelle",
+        ],
+    ),
+    (
+        "arrangement-unchanged",
+        [
+            "The outputs still match '{}'; nothing to save.",
+            "Las salidas siguen coincidiendo con '{}'; no hay nada que guardar.",
+            "Die Ausgänge passen weiterhin zu '{}'; nichts zu speichern.",
+            "Les sorties correspondent toujours à '{}' ; rien à enregistrer.",
+        ],
+    ),
+    (
+        "save-changes",
+        [
+            "Save these changes into '{}'?",
+            "¿Guardar estos cambios en '{}'?",
+            "Diese Änderungen in '{}' speichern?",
+            "Enregistrer ces modifications dans '{}' ?",
+        ],
+    ),
+    (
+        "arrangement-saved",
+        [
+            "Saved the new arrangement into '{}'.",
+            "Nueva disposición guardada en '{}'.",
+            "Neue Anordnung in '{}' gespeichert.",
+            "Nouvelle disposition enregistrée dans '{}'.",
+        ],
+    ),
+    (
+        "no-presentation",
+        [
+            "no presentation is running.",
+            "no hay ninguna presentación en curso.",
+            "Es läuft keine Präsentation.",
+            "aucune présentation n'est en cours.",
+        ],
+    ),
+    (
+        "presentation-ended",
+        [
+            "Presentation ended; outputs restored.",
+            "Presentación terminada; salidas restauradas.",
+            "Präsentation beendet; Ausgänge wiederhergestellt.",
+            "Présentation terminée ; sorties rétablies.",
+        ],
+    ),
+    (
+        "before-presenting",
+        [
+            "Before presenting",
+            "Antes de presentar",
+            "Vor der Präsentation",
+            "Avant la présentation",
+        ],
+    ),
+    (
+        "already-presenting",
+        [
+            "already presenting; run 'sway-display-switcher present --end' first.",
+            "ya hay una presentación; ejecute antes 'sway-display-switcher present --end'.",
+            "Es läuft bereits eine Präsentation; führen Sie zuerst 'sway-display-switcher present --end' aus.",
+            "présentation déjà en cours ; lancez d'abord 'sway-display-switcher present --end'.",
+        ],
+    ),
+    (
+        "no-present-target",
+        [
+            "no single external output to present on; name one.",
+            "no hay una única salida externa en la que presentar; indique una.",
+            "Kein eindeutiger externer Ausgang zum Präsentieren; nennen Sie einen.",
+            "aucune sortie externe unique sur laquelle présenter ; indiquez-en une.",
+        ],
+    ),
+    (
+        "no-common-mode",
+        [
+            "the built-in panel and {} have no mode in common.",
+            "la pantalla integrada y {} no tienen ningún modo en común.",
+            "Das eingebaute Display und {} haben keinen gemeinsamen Modus.",
+            "l'écran intégré et {} n'ont aucun mode en commun.",
+        ],
+    ),
+    (
+        "presenting",
+        [
+            "Presenting on {}. Unplug it or run 'sway-display-switcher present --end' to stop.",
+            "Presentando en {}. Desconéctela o ejecute 'sway-display-switcher present --end' para terminar.",
+            "Präsentation auf {}. Trennen Sie den Ausgang oder führen Sie 'sway-display-switcher present --end' aus, um sie zu beenden.",
+            "Présentation sur {}. Débranchez-la ou lancez 'sway-display-switcher present --end' pour arrêter.",
+        ],
+    ),
+    (
+        "presenting-profile",
+        [
+            "Presenting on {}",
+            "Presentando en {}",
+            "Präsentation auf {}",
+            "Présentation sur {}",
+        ],
+    ),
+    (
+        "subscription-no-stdout-output",
+        [
+            "the output subscription has no stdout.",
+            "la suscripción a salidas no tiene stdout.",
+            "Das Ausgabeabonnement hat kein stdout.",
+            "l'abonnement aux sorties n'a pas de stdout.",
+        ],
+    ),
+    (
+        "unplugged",
+        [
+            "{} was unplugged; outputs restored.",
+            "{} se desconectó; salidas restauradas.",
+            "{} wurde getrennt; Ausgänge wiederhergestellt.",
+            "{} a été débranchée ; sorties rétablies.",
+        ],
+    ),
+    (
+        "testing",
+        [
+            "Testing '{}' for {} seconds; press Enter to go back sooner.",
+            "Probando '{}' durante {} segundos; pulse Intro para volver antes.",
+            "'{}' wird {} Sekunden getestet; drücken Sie die Eingabetaste, um früher zurückzukehren.",
+            "Test de '{}' pendant {} secondes ; appuyez sur Entrée pour revenir plus tôt.",
+        ],
+    ),
+    (
+        "test-rejected",
+        [
+            "Sway rejected '{}'; outputs restored.",
+            "Sway rechazó '{}'; salidas restauradas.",
+            "Sway hat '{}' abgelehnt; Ausgänge wiederhergestellt.",
+            "Sway a rejeté '{}' ; sorties rétablies.",
+        ],
+    ),
+    (
+        "test-over",
+        [
+            "Test over; outputs restored.",
+            "Prueba terminada; salidas restauradas.",
+            "Test beendet; Ausgänge wiederhergestellt.",
+            "Test terminé ; sorties rétablies.",
+        ],
+    ),
+    (
+        "before-testing",
+        [
+            "Before testing",
+            "Antes de probar",
+            "Vor dem Test",
+            "Avant le test",
+        ],
+    ),
+    (
+        "usage-power",
+        [
+            "usage: power <output> on|off|toggle",
+            "uso: power <salida> on|off|toggle",
+            "Verwendung: power <Ausgabe> on|off|toggle",
+            "utilisation : power <sortie> on|off|toggle",
+        ],
+    ),
+    (
+        "power-failed",
+        [
+            "Sway could not set the power of '{}'.",
+            "Sway no pudo cambiar el encendido de '{}'.",
+            "Sway konnte die Stromversorgung von '{}' nicht setzen.",
+            "Sway n'a pas pu régler l'alimentation de '{}'.",
+        ],
+    ),
+    (
+        "blank-failed",
+        [
+            "Sway could not turn the outputs off.",
+            "Sway no pudo apagar las salidas.",
+            "Sway konnte die Ausgänge nicht ausschalten.",
+            "Sway n'a pas pu éteindre les sorties.",
+        ],
+    ),
+    (
+        "swayidle-run-failed",
+        [
+            "could not run swayidle: {}",
+            "no se pudo ejecutar swayidle: {}",
+            "swayidle konnte nicht ausgeführt werden: {}",
+            "impossible de lancer swayidle : {}",
+        ],
+    ),
+    (
+        "swaylock-run-failed",
+        [
+            "could not run swaylock: {}",
+            "no se pudo ejecutar swaylock: {}",
+            "swaylock konnte nicht ausgeführt werden: {}",
+            "impossible de lancer swaylock : {}",
+        ],
+    ),
+    (
+        "lock-failed",
+        [
+            "swaylock could not lock the screen.",
+            "swaylock no pudo bloquear la pantalla.",
+            "swaylock konnte den Bildschirm nicht sperren.",
+            "swaylock n'a pas pu verrouiller l'écran.",
+        ],
+    ),
+    (
+        "no-gpus",
+        [
+            "No GPUs found in /sys/class/drm.",
+            "No se encontraron GPU en /sys/class/drm.",
+            "Keine GPUs in /sys/class/drm gefunden.",
+            "Aucun GPU trouvé dans /sys/class/drm.",
+        ],
+    ),
+    (
+        "gpu-built-in",
+        [
+            "built-in",
+            "integrada",
+            "eingebaut",
+            "intégré",
+        ],
+    ),
+    (
+        "gpu-external",
+        [
+            "external",
+            "externa",
+            "extern",
+            "externe",
+        ],
+    ),
+    (
+        "no-external-gpu",
+        [
+            "No connected output is driven by an external GPU.",
+            "Ninguna salida conectada depende de una GPU externa.",
+            "Kein angeschlossener Ausgang wird von einer externen GPU betrieben.",
+            "Aucune sortie connectée n'est pilotée par un GPU externe.",
+        ],
+    ),
+    (
+        "no-builtin-fit",
+        [
+            "no profile fits the outputs of the built-in GPU.",
+            "ningún perfil encaja con las salidas de la GPU integrada.",
+            "Kein Profil passt zu den Ausgängen der eingebauten GPU.",
+            "aucun profil ne convient aux sorties du GPU intégré.",
+        ],
+    ),
+    (
+        "disable-failed",
+        [
+            "failed to turn off {}.",
+            "no se pudo apagar {}.",
+            "{} konnte nicht ausgeschaltet werden.",
+            "impossible d'éteindre {}.",
+        ],
+    ),
+    (
+        "gpu-detached",
+        [
+            "Turned off {}; the external GPU can be unplugged now.",
+            "{} apagado; ya se puede desconectar la GPU externa.",
+            "{} ausgeschaltet; die externe GPU kann jetzt getrennt werden.",
+            "{} éteint ; le GPU externe peut maintenant être débranché.",
+        ],
+    ),
+    (
+        "best-marker",
+        [
+            " (best)",
+            " (mejor)",
+            " (beste)",
+            " (meilleur)",
+        ],
+    ),
+    (
+        "priority-suffix",
+        [
+            ", priority {}",
+            ", prioridad {}",
+            ", Priorität {}",
+            ", priorité {}",
+        ],
+    ),
+    (
+        "which-missing",
+        [
+            "{}. {}: not connected: {}",
+            "{}. {}: no conectadas: {}",
+            "{}. {}: nicht angeschlossen: {}",
+            "{}. {} : non connectées : {}",
+        ],
+    ),
+    (
+        "which-blackout",
+        [
+            "{}. {}: turns off every connected output",
+            "{}. {}: apaga todas las salidas conectadas",
+            "{}. {}: schaltet alle angeschlossenen Ausgänge aus",
+            "{}. {} : éteint toutes les sorties connectées",
+        ],
+    ),
+    (
+        "which-unconfigured",
+        [
+            "{}. {}: not configured: {}",
+            "{}. {}: sin configurar: {}",
+            "{}. {}: nicht konfiguriert: {}",
+            "{}. {} : non configurées : {}",
+        ],
+    ),
+    (
+        "tie-by-priority",
+        [
+            "{} profiles fit equally well; '{}' wins by its priority.",
+            "{} perfiles encajan igual de bien; '{}' gana por su prioridad.",
+            "{} Profile passen gleich gut; '{}' gewinnt durch seine Priorität.",
+            "{} profils conviennent aussi bien ; '{}' l'emporte par sa priorité.",
+        ],
+    ),
+    (
+        "tie-by-order",
+        [
+            "{} profiles fit equally well; '{}' wins by coming first in the config file.",
+            "{} perfiles encajan igual de bien; '{}' gana por aparecer primero en el archivo de configuración.",
+            "{} Profile passen gleich gut; '{}' gewinnt, weil es in der Konfigurationsdatei zuerst steht.",
+            "{} profils conviennent aussi bien ; '{}' l'emporte en apparaissant en premier dans le fichier de configuration.",
+        ],
+    ),
+    (
+        "usage-get",
+        [
+            "usage: get <field>, e.g. current.name or profiles.count",
+            "uso: get <campo>, p. ej. current.name o profiles.count",
+            "Verwendung: get <Feld>, z. B. current.name oder profiles.count",
+            "utilisation : get <champ>, p. ex. current.name ou profiles.count",
+        ],
+    ),
+    (
+        "no-value",
+        [
+            "'{}' has no value.",
+            "'{}' no tiene valor.",
+            "'{}' hat keinen Wert.",
+            "'{}' n'a pas de valeur.",
+        ],
+    ),
+    (
+        "not-migrated",
+        [
+            "'{}' is not connected; its lines were left unchanged.",
+            "'{}' no está conectada; sus líneas no se modificaron.",
+            "'{}' ist nicht angeschlossen; seine Zeilen blieben unverändert.",
+            "'{}' n'est pas connectée ; ses lignes n'ont pas été modifiées.",
+        ],
+    ),
+    (
+        "nothing-to-migrate",
+        [
+            "Nothing to migrate.",
+            "No hay nada que migrar.",
+            "Nichts zu migrieren.",
+            "Rien à migrer.",
+        ],
+    ),
+    (
+        "migrated-descriptions",
+        [
+            "Migrated profiles to description-based output names.",
+            "Perfiles migrados a nombres de salida basados en la descripción.",
+            "Profile auf beschreibungsbasierte Ausgangsnamen migriert.",
+            "Profils migrés vers des noms de sortie fondés sur la description.",
+        ],
+    ),
+    (
+        "identities-one",
+        [
+            "Recorded {} output identity.",
+            "Se registró {} identidad de salida.",
+            "{} Ausgangsidentität festgehalten.",
+            "{} identité de sortie enregistrée.",
+        ],
+    ),
+    (
+        "identities-many",
+        [
+            "Recorded {} output identities.",
+            "Se registraron {} identidades de salida.",
+            "{} Ausgangsidentitäten festgehalten.",
+            "{} identités de sortie enregistrées.",
+        ],
+    ),
+    (
+        "no-unmanaged",
+        [
+            "No output lines outside the managed section.",
+            "No hay líneas output fuera de la sección gestionada.",
+            "Keine Output-Zeilen außerhalb des verwalteten Abschnitts.",
+            "Aucune ligne output en dehors de la section gérée.",
+        ],
+    ),
+    (
+        "unmanaged-header",
+        [
+            "Output lines outside the managed section:",
+            "Líneas output fuera de la sección gestionada:",
+            "Output-Zeilen außerhalb des verwalteten Abschnitts:",
+            "Lignes output en dehors de la section gérée :",
+        ],
+    ),
+    (
+        "move-into-profile",
+        [
+            "Move them into a new profile?",
+            "¿Moverlas a un perfil nuevo?",
+            "In ein neues Profil verschieben?",
+            "Les déplacer dans un nouveau profil ?",
+        ],
+    ),
+    (
+        "new-name-without-commas",
+        [
+            "Please enter a new name without commas.",
+            "Introduzca un nombre nuevo sin comas.",
+            "Bitte geben Sie einen neuen Namen ohne Kommas ein.",
+            "Saisissez un nouveau nom sans virgule.",
+        ],
+    ),
+    (
+        "keep-originals",
+        [
+            "Keep the originals as comments?",
+            "¿Conservar los originales como comentarios?",
+            "Originale als Kommentare behalten?",
+            "Conserver les originaux en commentaire ?",
+        ],
+    ),
+    (
+        "adopted",
+        [
+            "Adopted {} output lines as '{}' [{}].",
+            "{} líneas output adoptadas como '{}' [{}].",
+            "{} Output-Zeilen als '{}' [{}] übernommen.",
+            "{} lignes output adoptées comme '{}' [{}].",
+        ],
+    ),
+    (
+        "usage-import",
+        [
+            "usage: import nwg-displays|way-displays|shikane [<profile>] | import json <file>",
+            "uso: import nwg-displays|way-displays|shikane [<perfil>] | import json <archivo>",
+            "Verwendung: import nwg-displays|way-displays|shikane [<Profil>] | import json <Datei>",
+            "utilisation : import nwg-displays|way-displays|shikane [<profil>] | import json <fichier>",
+        ],
+    ),
+    (
+        "not-imported",
+        [
+            "not imported: {}",
+            "no importado: {}",
+            "nicht importiert: {}",
+            "non importé : {}",
+        ],
+    ),
+    (
+        "no-output-lines",
+        [
+            "'{}' holds no output lines.",
+            "'{}' no contiene líneas output.",
+            "'{}' enthält keine Output-Zeilen.",
+            "'{}' ne contient aucune ligne output.",
+        ],
+    ),
+    (
+        "name-has-comma",
+        [
+            "profile names cannot contain commas.",
+            "los nombres de perfil no pueden contener comas.",
+            "Profilnamen dürfen keine Kommas enthalten.",
+            "les noms de profil ne peuvent pas contenir de virgule.",
+        ],
+    ),
+    (
+        "import-updated",
+        [
+            "Updated '{}'.",
+            "'{}' actualizado.",
+            "'{}' aktualisiert.",
+            "'{}' mis à jour.",
+        ],
+    ),
+    (
+        "import-added",
+        [
+            "Imported '{}' [{}].",
+            "'{}' importado [{}].",
+            "'{}' importiert [{}].",
+            "'{}' importé [{}].",
+        ],
+    ),
+    (
+        "nothing-to-import",
+        [
+            "nothing to import.",
+            "no hay nada que importar.",
+            "Nichts zu importieren.",
+            "rien à importer.",
+        ],
+    ),
+    (
+        "nwg-still-included",
+        [
+            "the Sway config includes the nwg-displays files after the managed section; remove those include lines so they do not override the switcher.",
+            "la configuración de Sway incluye los archivos de nwg-displays después de la sección gestionada; elimine esas líneas include para que no anulen el selector.",
+            "Die Sway-Konfiguration bindet die nwg-displays-Dateien nach dem verwalteten Abschnitt ein; entfernen Sie diese include-Zeilen, damit sie den Umschalter nicht überschreiben.",
+            "la configuration Sway inclut les fichiers de nwg-displays après la section gérée ; supprimez ces lignes include pour qu'elles ne prennent pas le pas sur le sélecteur.",
+        ],
+    ),
+    (
+        "backup-header",
+        [
+            "--- backup {}",
+            "--- copia {}",
+            "--- Sicherung {}",
+            "--- sauvegarde {}",
+        ],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders_in_order() {
+        assert_eq!(fill("'{}' on {}", &[&"Desk", &2]), "'Desk' on 2");
+    }
+
+    #[test]
+    fn fills_placeholders_by_position() {
+        assert_eq!(fill("{1} before {0}, {1}", &[&"a", &"b"]), "b before a, b");
+    }
+
+    #[test]
+    fn leaves_other_braces_alone() {
+        assert_eq!(fill("{} {2} {x} {", &[&1]), "1 {2} {x} {");
+        assert_eq!(fill("set {}", &[]), "set {}");
+    }
+
+    // Each translation takes the same arguments, as often, as the English text
+    #[test]
+    fn translations_use_the_same_placeholders() {
+        let markers: Vec<String> = (0..10).map(|i| format!("<{}>", i)).collect();
+        let args: Vec<&dyn Display> = markers.iter().map(|m| m as &dyn Display).collect();
+        let uses = |template: &str| {
+            let filled = fill(template, &args);
+            markers
+                .iter()
+                .map(|m| filled.matches(m.as_str()).count())
+                .collect::<Vec<_>>()
+        };
+        for (key, texts) in CATALOG {
+            for text in &texts[1..] {
+                assert_eq!(uses(text), uses(texts[0]), "{}: {}", key, text);
+            }
+        }
+    }
+
+    #[test]
+    fn catalog_keys_are_unique() {
+        for (i, (key, _)) in CATALOG.iter().enumerate() {
+            assert!(
+                !CATALOG[..i].iter().any(|(other, _)| other == key),
+                "{}",
+                key
+            );
+        }
+    }
+}
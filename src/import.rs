@@ -0,0 +1,744 @@
+// Profiles from other output managers, for moving over from them or keeping
+// their configs in sync: way-displays (YAML) and shikane (TOML). Both files
+// are read into a serde_json::Value, with just as much of each format as they
+// use. Profiles exported as JSON by this program are read back as well.
+
+use crate::config::DisplayConfig;
+use crate::layout::Rect;
+use crate::output;
+use crate::sway::{self, Mode, Output};
+use crate::tr;
+use serde_json::{Map, Value};
+
+// Where the two tools keep their configuration
+pub const WAY_DISPLAYS: &str = "~/.config/way-displays/cfg.yaml";
+pub const SHIKANE: &str = "~/.config/shikane/config.toml";
+
+// The profiles an import produced, and what could not be carried over
+#[derive(Debug, Default)]
+pub struct Import {
+    pub profiles: Vec<DisplayConfig>,
+    pub skipped: Vec<String>,
+}
+
+// The profiles in what `export --format json` (one profile) or `list --json`
+// (a document with `profiles`) printed
+pub fn json(text: &str) -> Result<Import, String> {
+    let document: Value = serde_json::from_str(text).map_err(|e| tr!("invalid-json", e))?;
+    let profiles = match document.get("profiles") {
+        Some(profiles) => serde_json::from_value(profiles.clone()),
+        None => serde_json::from_value(document).map(|profile| vec![profile]),
+    };
+    Ok(Import {
+        profiles: profiles.map_err(|e| tr!("invalid-json", e))?,
+        skipped: Vec::new(),
+    })
+}
+
+fn profile(description: &str, outputs: Vec<String>) -> DisplayConfig {
+    DisplayConfig {
+        description: description.to_string(),
+        outputs,
+        status: "Disabled".to_string(),
+        metadata: Vec::new(),
+    }
+}
+
+// shikane: one `[[profile]]` per profile with a `[[profile.output]]` table per
+// output. Outputs are found by `search` (shikane 1.x) or `match` (0.x); the
+// mode and position may be strings or tables depending on the version.
+pub fn shikane(text: &str) -> Result<Import, String> {
+    let document = parse_toml(text)?;
+    let mut import = Import::default();
+    for entry in document
+        .get("profile")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = str_field(entry, "name");
+        if name.is_empty() || name.contains(',') {
+            import.skipped.push(tr!("unusable-name", name));
+            continue;
+        }
+        let mut lines = Vec::new();
+        for output in entry
+            .get("output")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            match shikane_line(output) {
+                Ok(line) => lines.push(line),
+                Err(reason) => import.skipped.push(format!("{}: {}", name, reason)),
+            }
+        }
+        if !lines.is_empty() {
+            import.profiles.push(profile(name, lines));
+        }
+    }
+    Ok(import)
+}
+
+fn shikane_line(output: &Value) -> Result<String, String> {
+    let target = shikane_target(output)?;
+    let mut line = format!("output {}", output::quote(&target));
+    if output.get("enable").and_then(Value::as_bool) == Some(false) {
+        return Ok(line + " disable");
+    }
+    line.push_str(" enable");
+    match output.get("mode") {
+        Some(Value::String(mode)) if output::parse_mode(mode).is_some() => {
+            line.push_str(&format!(" mode {}", mode));
+        }
+        Some(mode @ Value::Object(_)) => {
+            let field = |key| mode.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+            line.push_str(&format!(" mode {}x{}", field("width"), field("height")));
+            if field("refresh") > 0.0 {
+                line.push_str(&format!("@{}Hz", field("refresh")));
+            }
+        }
+        _ => {}
+    }
+    let position = match output.get("position") {
+        Some(Value::String(position)) => position
+            .split_once(',')
+            .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?))),
+        Some(position @ Value::Object(_)) => position
+            .get("x")
+            .and_then(Value::as_i64)
+            .zip(position.get("y").and_then(Value::as_i64)),
+        _ => None,
+    };
+    if let Some((x, y)) = position {
+        line.push_str(&format!(" pos {} {}", x, y));
+    }
+    if let Some(scale) = output.get("scale").and_then(Value::as_f64) {
+        line.push_str(&format!(" scale {}", scale));
+    }
+    if let Some(transform) = output.get("transform").and_then(Value::as_str) {
+        line.push_str(&format!(" transform {}", transform));
+    }
+    // Left out for a Sway that would reject the whole line over it
+    let sync = output
+        .get("adaptive_sync")
+        .and_then(Value::as_bool)
+        .filter(|_| sway::supports("adaptive_sync"));
+    if let Some(sync) = sync {
+        line.push_str(if sync {
+            " adaptive_sync on"
+        } else {
+            " adaptive_sync off"
+        });
+    }
+    Ok(line)
+}
+
+// The Sway output name or description shikane's search terms pin down. Only
+// exact terms translate: `n=DP-1`, `d=<description>`, or vendor, model and
+// serial (`v=`, `m=`, `s=`) together.
+fn shikane_target(output: &Value) -> Result<String, String> {
+    let terms: Vec<&str> = match output.get("search").or_else(|| output.get("match")) {
+        Some(Value::String(term)) => vec![term.as_str()],
+        Some(Value::Array(terms)) => terms.iter().filter_map(Value::as_str).collect(),
+        _ => return Err(tr!("no-search")),
+    };
+    let (mut vendor, mut model, mut serial) = (None, None, None);
+    for term in &terms {
+        let mut chars = term.chars();
+        let (field, op) = (chars.next(), chars.next());
+        let value = term.get(2..).unwrap_or_default();
+        match (field, op) {
+            (Some('n' | 'd'), Some('=')) => return Ok(value.to_string()),
+            (Some('v'), Some('=')) => vendor = Some(value),
+            (Some('m'), Some('=')) => model = Some(value),
+            (Some('s'), Some('=')) => serial = Some(value),
+            (Some('d' | 'm' | 'n' | 's' | 'v'), Some('%' | '/')) => {
+                return Err(tr!("pattern-search", term));
+            }
+            // shikane 0.x matched a plain name or description
+            _ => return Ok(term.to_string()),
+        }
+    }
+    match (vendor, model) {
+        (Some(vendor), Some(model)) => Ok(format!(
+            "{} {} {}",
+            vendor,
+            model,
+            serial.unwrap_or("Unknown")
+        )),
+        _ => Err(tr!("search-not-single", terms.join(" "))),
+    }
+}
+
+// way-displays: a single layout that lines the outputs up in `ORDER` as a row
+// or column. Sizes that way-displays works out itself (preferred modes, auto
+// scale) are taken from the outputs as they are now, so positions come out
+// right for the outputs connected while importing.
+pub fn way_displays(text: &str, description: &str, connected: &[Output]) -> Result<Import, String> {
+    let document = parse_yaml(text)?;
+    let mut import = Import::default();
+    let upper = |key: &str| str_field(&document, key).to_ascii_uppercase();
+    let names = |key: &str| -> Vec<String> {
+        document
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let entries = |key: &str| -> Vec<&Value> {
+        document
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .collect()
+    };
+
+    // Every NAME_DESC mentioned, then the connected outputs, in ORDER first
+    let mut mentioned: Vec<String> = names("ORDER");
+    mentioned.extend(names("DISABLED"));
+    // Newer releases call ADAPTIVE_SYNC_OFF VRR_OFF
+    let sync_off: Vec<String> = names("ADAPTIVE_SYNC_OFF")
+        .into_iter()
+        .chain(names("VRR_OFF"))
+        .collect();
+    mentioned.extend(sync_off.iter().cloned());
+    for key in ["MODE", "SCALE", "TRANSFORM"] {
+        mentioned.extend(
+            entries(key)
+                .iter()
+                .map(|e| str_field(e, "NAME_DESC").to_string()),
+        );
+    }
+    let mut outputs: Vec<Target> = Vec::new();
+    for name_desc in &mentioned {
+        if name_desc.starts_with('!') {
+            import.skipped.push(tr!("regular-expression", name_desc));
+            continue;
+        }
+        let target = Target::find(name_desc, connected);
+        let known = outputs
+            .iter()
+            .any(|t| t.target == target.target || (t.live.is_some() && t.live == target.live));
+        if !known {
+            outputs.push(target);
+        }
+    }
+    for live in connected {
+        if !outputs.iter().any(|t| t.live == Some(live)) {
+            outputs.push(Target {
+                target: live.name.clone(),
+                live: Some(live),
+            });
+        }
+    }
+
+    let entry_for = |key: &str, target: &Target| {
+        entries(key)
+            .into_iter()
+            .find(|e| target.is(str_field(e, "NAME_DESC")))
+    };
+    let disabled = names("DISABLED");
+    let scaling = upper("SCALING") != "FALSE";
+
+    let mut lines = Vec::new();
+    let mut placed: Vec<(usize, Rect)> = Vec::new();
+    for target in &outputs {
+        let mut line = format!("output {}", output::quote(&target.target));
+        if disabled.iter().any(|name| target.is(name)) {
+            lines.push(line + " disable");
+            continue;
+        }
+        line.push_str(" enable");
+        let mode = entry_for("MODE", target).and_then(|entry| {
+            if str_field(entry, "MAX").eq_ignore_ascii_case("TRUE") {
+                return target
+                    .live?
+                    .modes
+                    .iter()
+                    .copied()
+                    .max_by_key(|mode| (mode.width * mode.height, mode.refresh));
+            }
+            Some(Mode {
+                width: str_field(entry, "WIDTH").parse().ok()?,
+                height: str_field(entry, "HEIGHT").parse().ok()?,
+                refresh: str_field(entry, "HZ")
+                    .parse::<f64>()
+                    .map_or(0, |hz| (hz * 1000.0).round() as i64),
+            })
+        });
+        if let Some(mode) = mode {
+            line.push_str(&format!(" mode {}", mode));
+        }
+        let scale = if !scaling {
+            Some(1.0)
+        } else {
+            entry_for("SCALE", target)
+                .and_then(|entry| str_field(entry, "SCALE").parse().ok())
+                .or(target.live.map(|live| live.scale))
+        };
+        if let Some(scale) = scale {
+            line.push_str(&format!(" scale {}", scale));
+        }
+        let transform = entry_for("TRANSFORM", target)
+            .map(|entry| str_field(entry, "TRANSFORM").to_ascii_lowercase());
+        if let Some(transform) = &transform {
+            line.push_str(&format!(" transform {}", transform));
+        }
+        if sync_off.iter().any(|name| target.is(name)) && sway::supports("adaptive_sync") {
+            line.push_str(" adaptive_sync off");
+        }
+        let size = mode.or(target.live.map(Output::current_mode));
+        match size {
+            Some(size) => {
+                let rotated = transform
+                    .as_deref()
+                    .or(target.live.map(|live| live.transform.as_str()))
+                    .is_some_and(|t| t.contains("90") || t.contains("270"));
+                let rect =
+                    Rect::logical(0, 0, size.width, size.height, scale.unwrap_or(1.0), rotated);
+                placed.push((lines.len(), rect));
+            }
+            None => import
+                .skipped
+                .push(tr!("position-not-connected", target.target)),
+        }
+        lines.push(line);
+    }
+
+    // Line the outputs up, aligned as ALIGN asks
+    let column = upper("ARRANGE").starts_with("COL");
+    let align = upper("ALIGN");
+    let extent = |rect: &Rect| if column { rect.width } else { rect.height };
+    let largest = placed
+        .iter()
+        .map(|(_, rect)| extent(rect))
+        .max()
+        .unwrap_or(0);
+    let mut along = 0;
+    for (index, rect) in &placed {
+        let across = match align.as_str() {
+            "MIDDLE" => (largest - extent(rect)) / 2,
+            "BOTTOM" | "RIGHT" => largest - extent(rect),
+            _ => 0,
+        };
+        let (x, y) = if column {
+            (across, along)
+        } else {
+            (along, across)
+        };
+        along += if column { rect.height } else { rect.width };
+        lines[*index].push_str(&format!(" pos {} {}", x, y));
+    }
+    import.profiles.push(profile(description, lines));
+    Ok(import)
+}
+
+// An output in a way-displays layout, with its live state when connected
+struct Target<'a> {
+    target: String,
+    live: Option<&'a Output>,
+}
+
+impl<'a> Target<'a> {
+    // way-displays matches a NAME_DESC against the name, or anywhere in the
+    // description, ignoring case. Sway needs the whole description, so a
+    // partial one is completed from the connected output it matches.
+    fn find(name_desc: &str, connected: &'a [Output]) -> Target<'a> {
+        let live = connected
+            .iter()
+            .find(|live| live.name == name_desc)
+            .or_else(|| connected.iter().find(|live| matches(name_desc, live)));
+        let target = match live {
+            Some(live) if live.name != name_desc => live.identifier(),
+            _ => name_desc.to_string(),
+        };
+        Target { target, live }
+    }
+
+    fn is(&self, name_desc: &str) -> bool {
+        match self.live {
+            Some(live) => matches(name_desc, live),
+            None => self.target.eq_ignore_ascii_case(name_desc),
+        }
+    }
+}
+
+fn matches(name_desc: &str, live: &Output) -> bool {
+    let wanted = name_desc.to_ascii_lowercase();
+    live.name.to_ascii_lowercase() == wanted
+        || live.identifier().to_ascii_lowercase().contains(&wanted)
+}
+
+// A string field, treating a missing or non-string value as empty
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+// The YAML way-displays writes: top-level keys holding a scalar or a list,
+// list items being scalars or `KEY: value` maps. Scalars stay strings.
+fn parse_yaml(text: &str) -> Result<Value, String> {
+    let mut document: Vec<(String, Value)> = Vec::new();
+    for (number, raw) in text.lines().enumerate() {
+        let line = strip_comment(raw);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indented = line.starts_with(char::is_whitespace);
+        let line = line.trim();
+        let error = || tr!("cannot-read-line", number + 1, line);
+        if !indented {
+            let (key, value) = line.split_once(':').ok_or_else(error)?;
+            let value = match unquote(value.trim()) {
+                "" => Value::Array(Vec::new()),
+                value => Value::String(value.to_string()),
+            };
+            document.push((key.trim().to_string(), value));
+            continue;
+        }
+        let Some((_, Value::Array(items))) = document.last_mut() else {
+            return Err(error());
+        };
+        let (item, new_item) = match line.strip_prefix('-') {
+            Some(item) => (item.trim(), true),
+            None => (line, false),
+        };
+        match item.split_once(':') {
+            // `- NAME_DESC: DP-1` starts a map, `  SCALE: 2` continues it
+            Some((key, value)) if !item.starts_with(['\'', '"']) => {
+                let entry = (
+                    key.trim().to_string(),
+                    Value::String(unquote(value.trim()).into()),
+                );
+                match items.last_mut() {
+                    Some(Value::Object(entries)) if !new_item => {
+                        entries.insert(entry.0, entry.1);
+                    }
+                    _ => items.push(Value::Object(Map::from_iter([entry]))),
+                }
+            }
+            _ if new_item => items.push(Value::String(unquote(item).to_string())),
+            _ => return Err(error()),
+        }
+    }
+    Ok(Value::Object(document.into_iter().collect()))
+}
+
+// The TOML shikane writes: `[[table]]` and `[[table.sub]]` array headers,
+// `key = value` pairs with strings, numbers, booleans, arrays and inline tables
+fn parse_toml(text: &str) -> Result<Value, String> {
+    let mut document = Map::new();
+    // Path of the array table keys are added to
+    let mut path: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    for (number, raw) in text.lines().enumerate() {
+        pending.push_str(strip_comment(raw));
+        pending.push(' ');
+        // Arrays may span several lines
+        if pending.matches('[').count() > pending.matches(']').count() {
+            continue;
+        }
+        let line = std::mem::take(&mut pending);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = || tr!("cannot-read-line", number + 1, line);
+        if let Some(header) = line.strip_prefix("[[").and_then(|h| h.strip_suffix("]]")) {
+            path = header
+                .split('.')
+                .map(|key| key.trim().to_string())
+                .collect();
+            let parent = table_at(&mut document, &path[..path.len() - 1]).ok_or_else(error)?;
+            let key = path.last().cloned().unwrap_or_default();
+            if let Value::Array(tables) = parent
+                .entry(key)
+                .or_insert_with(|| Value::Array(Vec::new()))
+            {
+                tables.push(Value::Object(Map::new()));
+            }
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(error)?;
+        let (value, rest) = toml_value(value.trim()).ok_or_else(error)?;
+        if !rest.trim().is_empty() {
+            return Err(error());
+        }
+        let table = table_at(&mut document, &path).ok_or_else(error)?;
+        table.insert(unquote(key.trim()).to_string(), value);
+    }
+    Ok(Value::Object(document))
+}
+
+// The entries of the table `path` leads to, following the last element of
+// each array of tables on the way
+fn table_at<'a>(
+    document: &'a mut Map<String, Value>,
+    path: &[String],
+) -> Option<&'a mut Map<String, Value>> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(document);
+    };
+    match document.get_mut(first)? {
+        Value::Array(tables) => match tables.last_mut()? {
+            Value::Object(entries) => table_at(entries, rest),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// One TOML value at the start of `text`, and what follows it
+fn toml_value(text: &str) -> Option<(Value, &str)> {
+    let text = text.trim_start();
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => return Some((Value::String(value), &rest[index + 1..])),
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    other => value.push(other),
+                },
+                c => value.push(c),
+            }
+        }
+        return None;
+    }
+    if let Some(rest) = text.strip_prefix('\'') {
+        let (value, rest) = rest.split_once('\'')?;
+        return Some((Value::String(value.to_string()), rest));
+    }
+    for (open, close) in [('[', ']'), ('{', '}')] {
+        let Some(mut rest) = text.strip_prefix(open) else {
+            continue;
+        };
+        let mut items = Vec::new();
+        let mut entries = Map::new();
+        loop {
+            rest = rest.trim_start().trim_start_matches(',').trim_start();
+            if let Some(after) = rest.strip_prefix(close) {
+                let value = if open == '[' {
+                    Value::Array(items)
+                } else {
+                    Value::Object(entries)
+                };
+                return Some((value, after));
+            }
+            if open == '[' {
+                let (item, after) = toml_value(rest)?;
+                items.push(item);
+                rest = after;
+            } else {
+                let (key, after) = rest.split_once('=')?;
+                let (value, after) = toml_value(after)?;
+                entries.insert(unquote(key.trim()).to_string(), value);
+                rest = after;
+            }
+        }
+    }
+    let end = text.find([',', ']', '}']).unwrap_or(text.len());
+    let (word, rest) = text.split_at(end);
+    let value = match word.trim() {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        number => Value::Number(number.replace('_', "").parse().ok()?),
+    };
+    Some((value, rest))
+}
+
+// A line without its `#` comment, leaving `#` inside quotes alone
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('#', None) => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn unquote(text: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile;
+
+    fn docked() -> DisplayConfig {
+        DisplayConfig {
+            description: "Docked".to_string(),
+            outputs: vec![
+                "output eDP-1 disable".to_string(),
+                "output DP-1 enable mode 2560x1440 pos 0 0".to_string(),
+            ],
+            status: "Enabled".to_string(),
+            metadata: vec![("Hotkey".to_string(), "$mod+F1".to_string())],
+        }
+    }
+
+    #[test]
+    fn json_reads_back_an_exported_profile() {
+        let exported = serde_json::to_string(&docked()).unwrap();
+        let imported = json(&exported).unwrap();
+        assert_eq!(imported.profiles, vec![docked()]);
+    }
+
+    #[test]
+    fn json_reads_back_a_listing() {
+        let listed = serde_json::to_string(&profile::profiles_document(&[docked()])).unwrap();
+        assert_eq!(json(&listed).unwrap().profiles, vec![docked()]);
+    }
+
+    #[test]
+    fn json_rejects_other_documents() {
+        assert!(json("[1, 2]").is_err());
+        assert!(json(r#"{"name": "X", "metadata": {"Hotkey": 1}}"#).is_err());
+        assert!(json(r#"{"schema_version": 99, "name": "X"}"#).is_err());
+    }
+
+    const SHIKANE_CONFIG: &str = r#"
+# Written by shikane 1.x
+[[profile]]
+name = "Desk"
+
+[[profile.output]]
+search = ["v=Dell Inc.", "m=DELL U2720Q", "s=ABC123"]
+mode = "3840x2160@60Hz"
+position = "0,0"
+scale = 1.5
+
+[[profile.output]]
+search = "n=eDP-1"
+enable = false
+
+[[profile]]
+name = "Old"
+
+[[profile.output]]
+match = "HDMI-A-1"
+mode = { width = 1920, height = 1080, refresh = 50 }
+position = { x = 1920, y = 0 }
+transform = "90"
+
+[[profile]]
+name = "Fuzzy"
+
+[[profile.output]]
+search = "m%U27"
+"#;
+
+    #[test]
+    fn shikane_profiles_become_output_lines() {
+        let import = shikane(SHIKANE_CONFIG).unwrap();
+        let lines: Vec<(&str, &[String])> = import
+            .profiles
+            .iter()
+            .map(|p| (p.description.as_str(), p.outputs.as_slice()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (
+                    "Desk",
+                    &[
+                        "output \"Dell Inc. DELL U2720Q ABC123\" enable mode 3840x2160@60Hz pos 0 0 scale 1.5".to_string(),
+                        "output eDP-1 disable".to_string(),
+                    ][..]
+                ),
+                (
+                    "Old",
+                    &["output HDMI-A-1 enable mode 1920x1080@50Hz pos 1920 0 transform 90".to_string()][..]
+                ),
+            ]
+        );
+        assert_eq!(
+            import.skipped,
+            [format!("Fuzzy: {}", tr!("pattern-search", "m%U27"))]
+        );
+    }
+
+    fn connected(name: &str, model: &str, width: i64, scale: f64) -> Output {
+        Output {
+            name: name.to_string(),
+            make: "Make".to_string(),
+            model: model.to_string(),
+            serial: "1".to_string(),
+            active: true,
+            width,
+            height: width * 9 / 16,
+            refresh: 60000,
+            x: 0,
+            y: 0,
+            scale,
+            transform: "normal".to_string(),
+            modes: Vec::new(),
+            physical_width: 0,
+            physical_height: 0,
+        }
+    }
+
+    #[test]
+    fn way_displays_lines_the_outputs_up() {
+        let config = "
+ARRANGE: ROW
+ALIGN: BOTTOM
+ORDER:
+  - 'big screen'
+  - eDP-1
+SCALE:
+  - NAME_DESC: eDP-1
+    SCALE: 2
+DISABLED:
+  - HDMI-A-1
+";
+        let outputs = [
+            connected("eDP-1", "Panel", 2560, 1.0),
+            connected("DP-1", "Big Screen", 1920, 1.0),
+        ];
+        let import = way_displays(config, "Imported", &outputs).unwrap();
+        assert_eq!(
+            import.profiles[0].outputs,
+            [
+                "output \"Make Big Screen 1\" enable scale 1 pos 0 0",
+                "output eDP-1 enable scale 2 pos 1920 360",
+                "output HDMI-A-1 disable",
+            ]
+        );
+        assert!(import.skipped.is_empty());
+    }
+
+    #[test]
+    fn way_displays_skips_what_it_cannot_place() {
+        let config = "
+ORDER:
+  - '!^DP-.*'
+  - HDMI-A-1
+";
+        let import = way_displays(config, "Imported", &[]).unwrap();
+        assert_eq!(import.profiles[0].outputs, ["output HDMI-A-1 enable"]);
+        assert_eq!(
+            import.skipped,
+            [
+                tr!("regular-expression", "!^DP-.*"),
+                tr!("position-not-connected", "HDMI-A-1"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Human-readable views of profiles
+
+use sway_display_switcher::config::DisplayConfig;
+use sway_display_switcher::output::OutputLine;
+use sway_display_switcher::sway::Output;
+use sway_display_switcher::tr;
+
+// Message ids of the columns of `list --long`
+pub const LONG_COLUMNS: [&str; 5] = [
+    "column-output",
+    "column-mode",
+    "column-position",
+    "column-scale",
+    "column-transform",
+];
+
+// Attributes that get their own column in `show`
+const SUMMARIZED: &[&str] = &["enable", "disable", "mode", "pos", "scale", "transform"];
+
+// Print a profile's outputs with their mode, position, scale and transform,
+// whether each is connected, then its metadata and any non-output lines
+pub fn show(config: &DisplayConfig, connected: &[Output]) {
+    println!("{} [{}]", config.description, config.status);
+    if let Some(notes) = config.notes() {
+        println!("{}", notes);
+    }
+
+    let mut other_lines = Vec::new();
+    println!("\n{}", tr!("outputs-header"));
+    for line in &config.outputs {
+        let Some(parsed) = OutputLine::parse(line) else {
+            other_lines.push(line);
+            continue;
+        };
+        let live: Vec<&Output> = connected
+            .iter()
+            .filter(|o| o.matches(&parsed.target))
+            .collect();
+        let presence = match live.as_slice() {
+            _ if parsed.is_wildcard() => tr!("all-outputs"),
+            [] => tr!("not-connected-short"),
+            [output] if output.name == parsed.target => tr!("connected"),
+            [output] => tr!("connected-as", output.name),
+            _ => tr!("matches-several"),
+        };
+        println!("  {} ({})", parsed.target, presence);
+
+        if parsed.is_disabled() {
+            println!("    {}", tr!("disabled"));
+            continue;
+        }
+        let mut summary = Vec::new();
+        if let Some(mode) = parsed.mode() {
+            summary.push(tr!("show-mode", mode));
+        }
+        if let Some((x, y)) = parsed.position() {
+            summary.push(tr!("show-position", x, y));
+        }
+        if let Some(scale) = parsed.scale() {
+            summary.push(tr!("show-scale", scale));
+        }
+        if let Some(transform) = parsed.transform() {
+            summary.push(tr!("show-transform", transform));
+        }
+        if summary.is_empty() {
+            summary.push(tr!("enabled"));
+        }
+        println!("    {}", summary.join("  "));
+
+        for attribute in &parsed.attributes {
+            if SUMMARIZED.contains(&attribute.canonical_key()) {
+                continue;
+            }
+            let mut words = vec![attribute.key.clone()];
+            words.extend(attribute.flags.iter().cloned());
+            words.extend(attribute.values.iter().cloned());
+            println!("    {}", words.join(" "));
+        }
+    }
+
+    // Notes are shown under the name already
+    let metadata: Vec<&(String, String)> = config
+        .metadata
+        .iter()
+        .filter(|(key, _)| !key.eq_ignore_ascii_case("Notes"))
+        .collect();
+    if !metadata.is_empty() {
+        println!("\n{}", tr!("metadata-header"));
+        for (key, value) in metadata {
+            println!("  {} = {}", key, value);
+        }
+    }
+
+    if !other_lines.is_empty() {
+        println!("\n{}", tr!("other-lines-header"));
+        for line in other_lines {
+            println!("  {}", line);
+        }
+    }
+}
+
+// One row per output line of a profile for `list --long`, in the order of
+// LONG_COLUMNS. Unset settings are shown as `-`; lines that are not output
+// lines are left out.
+pub fn output_rows(config: &DisplayConfig) -> Vec<[String; 5]> {
+    let unset = || "-".to_string();
+    config
+        .outputs
+        .iter()
+        .filter_map(|line| OutputLine::parse(line))
+        .map(|parsed| {
+            if parsed.is_disabled() {
+                return [parsed.target, tr!("disabled"), unset(), unset(), unset()];
+            }
+            [
+                parsed.target.clone(),
+                parsed.mode().map_or_else(unset, |mode| mode.to_string()),
+                parsed
+                    .position()
+                    .map_or_else(unset, |(x, y)| format!("{},{}", x, y)),
+                parsed.scale().map_or_else(unset, |scale| scale.to_string()),
+                parsed.transform().map_or_else(unset, str::to_string),
+            ]
+        })
+        .collect()
+}
+
+// Columns of `list --format csv|tsv`
+const TABLE_COLUMNS: [&str; 8] = [
+    "profile",
+    "status",
+    "output",
+    "enabled",
+    "mode",
+    "position",
+    "scale",
+    "transform",
+];
+
+// The profiles in `order` as CSV (`delimiter` ',') or TSV ('\t'), one row per
+// output line and a row with empty output columns for a profile without any.
+// Unset settings are left empty.
+pub fn table(configs: &[DisplayConfig], order: &[usize], delimiter: char) -> String {
+    let mut rows = vec![TABLE_COLUMNS.map(str::to_string).to_vec()];
+    for &index in order {
+        let config = &configs[index];
+        let profile = [config.description.clone(), config.status.clone()];
+        let lines: Vec<OutputLine> = config
+            .outputs
+            .iter()
+            .filter_map(|line| OutputLine::parse(line))
+            .collect();
+        if lines.is_empty() {
+            let mut row = profile.to_vec();
+            row.resize(TABLE_COLUMNS.len(), String::new());
+            rows.push(row);
+            continue;
+        }
+        for parsed in lines {
+            let enabled = if parsed.is_disabled() { "no" } else { "yes" };
+            let mut row = profile.to_vec();
+            row.extend([
+                parsed.target.clone(),
+                enabled.to_string(),
+                parsed
+                    .mode()
+                    .map(|mode| mode.to_string())
+                    .unwrap_or_default(),
+                parsed
+                    .position()
+                    .map(|(x, y)| format!("{},{}", x, y))
+                    .unwrap_or_default(),
+                parsed
+                    .scale()
+                    .map(|scale| scale.to_string())
+                    .unwrap_or_default(),
+                parsed.transform().unwrap_or_default().to_string(),
+            ]);
+            rows.push(row);
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row.iter().map(|cell| table_cell(cell, delimiter)).collect();
+            cells.join(&delimiter.to_string()) + "\n"
+        })
+        .collect()
+}
+
+// A cell quoted for CSV when it holds the delimiter, a quote or a line break;
+// TSV has no quoting, so tabs become spaces there
+fn table_cell(cell: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        return cell.replace('\t', " ");
+    }
+    if cell.contains([delimiter, '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+// Pad each cell to its column width, for aligned tables
+pub fn format_row(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
@@ -0,0 +1,244 @@
+// Geometry of output arrangements in layout (logical) coordinates
+
+use crate::config::DisplayConfig;
+use crate::output::OutputLine;
+use crate::sway::Output;
+use crate::tr;
+
+// Where to place an output relative to another one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+    Above,
+    Below,
+}
+
+impl Side {
+    pub fn parse(text: &str) -> Option<Side> {
+        match text.to_ascii_lowercase().as_str() {
+            "left" => Some(Side::Left),
+            "right" => Some(Side::Right),
+            "above" => Some(Side::Above),
+            "below" => Some(Side::Below),
+            _ => None,
+        }
+    }
+}
+
+// An output's rectangle in the layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Rect {
+    // The logical size of a mode once scaled and rotated
+    pub fn logical(x: i64, y: i64, width: i64, height: i64, scale: f64, rotated: bool) -> Rect {
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+        let (width, height) = if rotated {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        Rect {
+            x,
+            y,
+            width: (width as f64 / scale).round() as i64,
+            height: (height as f64 / scale).round() as i64,
+        }
+    }
+
+    // Position for an output of this size placed next to `anchor`, aligned to its top or left edge
+    pub fn placed(&self, anchor: &Rect, side: Side) -> (i64, i64) {
+        match side {
+            Side::Left => (anchor.x - self.width, anchor.y),
+            Side::Right => (anchor.x + anchor.width, anchor.y),
+            Side::Above => (anchor.x, anchor.y - self.height),
+            Side::Below => (anchor.x, anchor.y + anchor.height),
+        }
+    }
+}
+
+impl Rect {
+    fn right(&self) -> i64 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> i64 {
+        self.y + self.height
+    }
+
+    // Area shared by two rectangles, as (width, height) when positive
+    pub fn overlap(&self, other: &Rect) -> Option<(i64, i64)> {
+        let width = self.right().min(other.right()) - self.x.max(other.x);
+        let height = self.bottom().min(other.bottom()) - self.y.max(other.y);
+        (width > 0 && height > 0).then_some((width, height))
+    }
+
+    // Whether the cursor can cross directly between the two rectangles
+    pub fn touches(&self, other: &Rect) -> bool {
+        let shares_x = self.x.max(other.x) < self.right().min(other.right());
+        let shares_y = self.y.max(other.y) < self.bottom().min(other.bottom());
+        let vertical_edge = self.right() == other.x || other.right() == self.x;
+        let horizontal_edge = self.bottom() == other.y || other.bottom() == self.y;
+        (vertical_edge && shares_y)
+            || (horizontal_edge && shares_x)
+            || self.overlap(other).is_some()
+    }
+}
+
+// The layout rectangles of a profile's enabled outputs, with what `output *`
+// lines set for them
+pub fn profile_rects(config: &DisplayConfig, connected: &[Output]) -> Vec<(String, Rect)> {
+    config
+        .outputs
+        .iter()
+        .filter_map(|line| OutputLine::parse(line))
+        .filter(|line| !line.is_disabled() && !line.is_wildcard())
+        .filter_map(|line| {
+            let settings = config.settings_for(&line.target, connected)?;
+            if settings.is_disabled() {
+                return None;
+            }
+            let live = connected.iter().find(|o| o.matches(&line.target));
+            let rect = settings.rect(live)?;
+            Some((line.target, rect))
+        })
+        .collect()
+}
+
+// Describe overlapping outputs and groups of outputs the cursor cannot reach
+pub fn check(rects: &[(String, Rect)]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (i, (name, rect)) in rects.iter().enumerate() {
+        for (other_name, other) in &rects[i + 1..] {
+            // Outputs placed exactly on top of each other mirror on purpose
+            if rect == other {
+                continue;
+            }
+            if let Some((width, height)) = rect.overlap(other) {
+                warnings.push(tr!("layout-overlap", name, other_name, width, height));
+            }
+        }
+    }
+
+    // Flood-fill from the first output; anything not reached is cut off by a gap
+    if rects.len() > 1 {
+        let mut reached = vec![false; rects.len()];
+        let mut pending = vec![0];
+        reached[0] = true;
+        while let Some(index) = pending.pop() {
+            for (other, (_, rect)) in rects.iter().enumerate() {
+                if !reached[other] && rects[index].1.touches(rect) {
+                    reached[other] = true;
+                    pending.push(other);
+                }
+            }
+        }
+        let unreachable: Vec<&str> = rects
+            .iter()
+            .zip(&reached)
+            .filter(|(_, reached)| !**reached)
+            .map(|((name, _), _)| name.as_str())
+            .collect();
+        if !unreachable.is_empty() {
+            let id = if unreachable.len() == 1 {
+                "layout-gap-one"
+            } else {
+                "layout-gap-many"
+            };
+            warnings.push(tr!(id, unreachable.join(", "), rects[0].0));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i64, y: i64, width: i64, height: i64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn logical_size_is_scaled_and_rotated() {
+        assert_eq!(
+            Rect::logical(10, 20, 2256, 1504, 1.5, false),
+            rect(10, 20, 1504, 1003)
+        );
+        assert_eq!(
+            Rect::logical(0, 0, 1920, 1080, 1.0, true),
+            rect(0, 0, 1080, 1920)
+        );
+        assert_eq!(
+            Rect::logical(0, 0, 1920, 1080, 0.0, false),
+            rect(0, 0, 1920, 1080)
+        );
+    }
+
+    #[test]
+    fn places_next_to_an_anchor() {
+        let anchor = rect(1000, 500, 1920, 1080);
+        let monitor = rect(0, 0, 1280, 720);
+        assert_eq!(monitor.placed(&anchor, Side::Left), (-280, 500));
+        assert_eq!(monitor.placed(&anchor, Side::Right), (2920, 500));
+        assert_eq!(monitor.placed(&anchor, Side::Above), (1000, -220));
+        assert_eq!(monitor.placed(&anchor, Side::Below), (1000, 1580));
+        assert_eq!(Side::parse("Above"), Some(Side::Above));
+        assert_eq!(Side::parse("behind"), None);
+    }
+
+    fn named(rects: &[(&str, Rect)]) -> Vec<(String, Rect)> {
+        rects
+            .iter()
+            .map(|(name, rect)| (name.to_string(), *rect))
+            .collect()
+    }
+
+    #[test]
+    fn side_by_side_outputs_need_no_warning() {
+        let rects = named(&[
+            ("eDP-1", rect(0, 0, 1504, 1003)),
+            ("DP-1", rect(1504, 0, 2560, 1440)),
+            ("DP-2", rect(1504, 1440, 1920, 1080)),
+            ("Mirror", rect(0, 0, 1504, 1003)),
+        ]);
+        assert!(check(&rects).is_empty());
+    }
+
+    #[test]
+    fn warns_about_overlaps_and_gaps() {
+        let rects = named(&[
+            ("eDP-1", rect(0, 0, 1920, 1080)),
+            ("DP-1", rect(1800, 0, 1920, 1080)),
+            ("DP-2", rect(5000, 0, 1920, 1080)),
+            ("DP-3", rect(0, 1081, 1920, 1080)),
+        ]);
+        assert_eq!(
+            check(&rects),
+            [
+                tr!("layout-overlap", "eDP-1", "DP-1", 120, 1080),
+                tr!("layout-gap-many", "DP-2, DP-3", "eDP-1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn corners_do_not_connect() {
+        let rects = named(&[
+            ("eDP-1", rect(0, 0, 1920, 1080)),
+            ("DP-1", rect(1920, 1080, 1920, 1080)),
+        ]);
+        assert_eq!(check(&rects), [tr!("layout-gap-one", "DP-1", "eDP-1")]);
+    }
+}
@@ -0,0 +1,41 @@
+//! Core of sway-display-switcher: parsing the managed display section of a Sway
+//! config, matching profiles against connected outputs and applying them.
+//!
+//! Frontends load a config with [`config::SwayConfig::load`], read its
+//! profiles with `display_configs()` and activate one through [`switcher`].
+//! Failures that end a command are [`error::Error`]s.
+
+pub mod audit;
+pub mod backup;
+pub mod config;
+#[cfg(feature = "daemon")]
+pub mod daemon;
+pub mod diff;
+pub mod effects;
+pub mod error;
+pub mod events;
+pub mod generate;
+pub mod gpu;
+pub mod i18n;
+pub mod import;
+pub mod layout;
+pub mod lint;
+pub mod listing;
+pub mod matching;
+pub mod migrate;
+pub mod mqtt;
+pub mod output;
+pub mod plan;
+pub mod profile;
+pub mod query;
+pub mod remote;
+pub mod roles;
+pub mod rules;
+pub mod safemode;
+pub mod scale;
+pub mod section;
+pub mod settings;
+pub mod state;
+pub mod sway;
+pub mod switcher;
+pub mod windows;
@@ -0,0 +1,553 @@
+// Validation of profile lines against Sway's output command grammar
+
+use crate::config::DisplayConfig;
+use crate::output::{self, Attribute, OutputLine};
+use crate::sway::Version;
+use crate::tr;
+
+// A suspicious line, with a corrected line when one can be guessed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub profile: String,
+    pub line: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+const TRANSFORMS: &[&str] = &[
+    "normal",
+    "90",
+    "180",
+    "270",
+    "flipped",
+    "flipped-90",
+    "flipped-180",
+    "flipped-270",
+];
+
+// Allowed values of the subcommands that take a keyword
+const KEYWORDS: &[(&str, &[&str])] = &[
+    ("scale_filter", &["linear", "nearest", "smart"]),
+    ("subpixel", &["rgb", "bgr", "vrgb", "vbgr", "none"]),
+    ("power", &["on", "off", "toggle"]),
+    ("adaptive_sync", &["on", "off", "enable", "disable"]),
+    ("render_bit_depth", &["6", "8", "10"]),
+    ("allow_tearing", &["yes", "no"]),
+    ("hdr", &["on", "off", "toggle"]),
+];
+
+// Check every line of every profile
+pub fn check(configs: &[DisplayConfig]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for config in configs {
+        for line in &config.outputs {
+            // A conditional line is checked as the command it guards
+            let (condition, command) = match output::split_condition(line) {
+                Some((condition, command)) => (Some(condition), command),
+                None => (None, line.as_str()),
+            };
+            for (message, suggestion) in check_line(command) {
+                let suggestion = match &condition {
+                    Some(condition) => suggestion.map(|s| format!("{} {}", condition, s)),
+                    None => suggestion,
+                };
+                findings.push(Finding {
+                    profile: config.description.clone(),
+                    line: line.clone(),
+                    message,
+                    suggestion,
+                });
+            }
+        }
+        if config.metadata("Xwayland").is_some() {
+            findings.extend(check_xwayland_scales(config));
+        }
+        if let Some(priority) = config.metadata("Priority") {
+            if priority.parse::<i64>().is_err() {
+                findings.push(Finding {
+                    profile: config.description.clone(),
+                    line: String::new(),
+                    message: tr!("lint-priority", priority),
+                    suggestion: None,
+                });
+            }
+        }
+        findings.extend(check_wildcards(config));
+        findings.extend(check_repeats(config));
+    }
+    findings.extend(check_duplicate_profiles(configs));
+    findings
+}
+
+// Subcommands the running Sway is too old for. A profile written on a newer
+// machine otherwise fails when Sway reloads the config, with an error that
+// does not say why.
+pub fn check_support(configs: &[DisplayConfig], version: Version) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for config in configs {
+        for line in &config.outputs {
+            let command = output::split_condition(line).map_or(line.as_str(), |(_, c)| c);
+            let Some(parsed) = OutputLine::parse(command) else {
+                continue;
+            };
+            for attribute in &parsed.attributes {
+                let Some(since) = output::introduced_in(&attribute.key) else {
+                    continue;
+                };
+                if version.at_least(since) {
+                    continue;
+                }
+                let id = if attribute.key == "power" {
+                    "lint-needs-sway-dpms"
+                } else {
+                    "lint-needs-sway"
+                };
+                let message = tr!(id, attribute.key, since.0, since.1, version);
+                findings.push(Finding {
+                    profile: config.description.clone(),
+                    line: line.clone(),
+                    message,
+                    suggestion: None,
+                });
+            }
+        }
+    }
+    findings
+}
+
+// A second unconditional line for the same output: an exact repeat does
+// nothing, and otherwise the two are easier to read as one line. A wildcard
+// line in between may be what the second line overrides, so it starts afresh.
+fn check_repeats(config: &DisplayConfig) -> Vec<Finding> {
+    let mut seen: Vec<(String, String)> = Vec::new();
+    let mut findings = Vec::new();
+    for line in &config.outputs {
+        let Some(parsed) = OutputLine::parse(line) else {
+            continue;
+        };
+        if parsed.is_wildcard() {
+            seen.clear();
+            continue;
+        }
+        let normalized = output::normalize_line(line);
+        let message = match seen.iter().find(|(target, _)| *target == parsed.target) {
+            Some((_, earlier)) if *earlier == normalized => tr!("lint-repeat"),
+            Some(_) => tr!("lint-second-line", parsed.target),
+            None => {
+                seen.push((parsed.target, normalized));
+                continue;
+            }
+        };
+        findings.push(Finding {
+            profile: config.description.clone(),
+            line: line.clone(),
+            message,
+            suggestion: None,
+        });
+    }
+    findings
+}
+
+// Profiles with the same lines once formatted, which apply the same layout
+// under two names
+fn check_duplicate_profiles(configs: &[DisplayConfig]) -> Vec<Finding> {
+    let canonical = |config: &DisplayConfig| {
+        let normalized: Vec<String> = config
+            .outputs
+            .iter()
+            .map(|line| output::normalize_line(line))
+            .collect();
+        let mut sorted = output::sort_lines(&normalized);
+        sorted.dedup();
+        sorted
+    };
+    let forms: Vec<Vec<String>> = configs.iter().map(canonical).collect();
+    let mut findings = Vec::new();
+    for (index, form) in forms.iter().enumerate() {
+        if form.is_empty() {
+            continue;
+        }
+        if let Some(earlier) = forms[..index].iter().position(|other| other == form) {
+            findings.push(Finding {
+                profile: configs[index].description.clone(),
+                line: String::new(),
+                message: tr!("lint-duplicate", configs[earlier].description),
+                suggestion: None,
+            });
+        }
+    }
+    findings
+}
+
+// Xwayland renders at scale 1 and is stretched on other outputs, which turns
+// blurry at fractional scales, so profiles that tune Xwayland get the nearest
+// whole scale suggested
+fn check_xwayland_scales(config: &DisplayConfig) -> Vec<Finding> {
+    config
+        .outputs
+        .iter()
+        .filter_map(|line| {
+            let scale = OutputLine::parse(line)?.scale()?;
+            if scale.fract() == 0.0 {
+                return None;
+            }
+            let whole = scale.round().max(1.0).to_string();
+            Some(Finding {
+                profile: config.description.clone(),
+                line: line.clone(),
+                message: tr!("lint-xwayland", scale),
+                suggestion: Some(output::set_attribute(line, "scale", &[whole])),
+            })
+        })
+        .collect()
+}
+
+// An `output *` line applies to every output, so one placed after lines for
+// specific outputs overrides what they set, a wildcard `disable` turning off
+// outputs enabled earlier. Sway reads the lines in order; the fix is to move the
+// wildcard line first, where it only fills in what the others leave out.
+fn check_wildcards(config: &DisplayConfig) -> Vec<Finding> {
+    let parsed: Vec<Option<OutputLine>> = config
+        .outputs
+        .iter()
+        .map(|line| {
+            let command = output::split_condition(line).map_or(line.as_str(), |(_, c)| c);
+            OutputLine::parse(command)
+        })
+        .collect();
+    let mut findings = Vec::new();
+    for (index, wildcard) in parsed.iter().enumerate() {
+        let Some(wildcard) = wildcard.as_ref().filter(|line| line.is_wildcard()) else {
+            continue;
+        };
+        for earlier in parsed[..index].iter().flatten() {
+            if earlier.is_wildcard() {
+                continue;
+            }
+            let mut overridden: Vec<&str> = earlier
+                .attributes
+                .iter()
+                .filter(|attribute| attribute.is_known())
+                .map(|attribute| output::key_group(&attribute.key))
+                .filter(|group| {
+                    wildcard
+                        .attributes
+                        .iter()
+                        .any(|attribute| output::key_group(&attribute.key) == *group)
+                })
+                .collect();
+            overridden.sort_unstable();
+            overridden.dedup();
+            if overridden.is_empty() {
+                continue;
+            }
+            let message = if overridden.contains(&"enable") && wildcard.is_disabled() {
+                tr!("lint-wildcard-off", earlier.target)
+            } else {
+                tr!(
+                    "lint-wildcard-overrides",
+                    overridden.join(", "),
+                    earlier.target
+                )
+            };
+            findings.push(Finding {
+                profile: config.description.clone(),
+                line: config.outputs[index].clone(),
+                message,
+                suggestion: None,
+            });
+        }
+    }
+    findings
+}
+
+// Problems with one line, each with an optional corrected line
+fn check_line(line: &str) -> Vec<(String, Option<String>)> {
+    let words = output::tokenize(line);
+    let Some(first) = words.first() else {
+        return Vec::new();
+    };
+    if first != "output" {
+        // Other commands are allowed in profiles; only catch a misspelt `output`
+        if distance(first, "output") <= 2 {
+            return vec![(
+                tr!("lint-not-command", first),
+                Some(line.replacen(first.as_str(), "output", 1)),
+            )];
+        }
+        return Vec::new();
+    }
+    let Some(parsed) = OutputLine::parse(line) else {
+        return vec![(tr!("lint-no-name"), None)];
+    };
+    if parsed.attributes.is_empty() {
+        return vec![(tr!("lint-no-subcommand"), None)];
+    }
+
+    let mut problems = Vec::new();
+    for attribute in &parsed.attributes {
+        if !attribute.is_known() {
+            let closest = output::SUBCOMMANDS
+                .iter()
+                .min_by_key(|known| distance(&attribute.key, known))
+                .filter(|known| distance(&attribute.key, known) <= 2);
+            let suggestion = closest.map(|known| replace_word(line, &attribute.key, known));
+            problems.push((tr!("lint-unknown-subcommand", attribute.key), suggestion));
+            continue;
+        }
+        if let Some(problem) = check_attribute(line, attribute) {
+            problems.push(problem);
+        }
+    }
+    problems
+}
+
+// Check the values of a known subcommand
+fn check_attribute(line: &str, attribute: &Attribute) -> Option<(String, Option<String>)> {
+    let key = attribute.canonical_key();
+    let values = &attribute.values;
+    let first = values.first().map(String::as_str);
+    match key {
+        "mode" => {
+            let Some(mode) = first else {
+                return Some((tr!("lint-mode-missing"), None));
+            };
+            if output::parse_mode(mode).is_some() {
+                return None;
+            }
+            // Common slips: an uppercase X or another separator, a lowercase hz
+            let fixed = mode.replace(['X', '*', '×'], "x").replace("hz", "Hz");
+            let suggestion = output::parse_mode(&fixed).map(|_| replace_word(line, mode, &fixed));
+            Some((tr!("lint-mode-invalid", mode), suggestion))
+        }
+        "pos" => {
+            let numbers = values.iter().filter(|v| v.parse::<i64>().is_ok()).count();
+            if values.len() == 2 && numbers == 2 {
+                return None;
+            }
+            // `pos 1920,0` instead of `pos 1920 0`
+            let suggestion = first
+                .and_then(|v| v.split_once(','))
+                .filter(|(x, y)| x.parse::<i64>().is_ok() && y.parse::<i64>().is_ok())
+                .map(|(x, y)| replace_word(line, first.unwrap_or(""), &format!("{} {}", x, y)));
+            Some((tr!("lint-pos"), suggestion))
+        }
+        "scale" => match first.and_then(|v| v.parse::<f64>().ok()) {
+            Some(scale) if scale > 0.0 => None,
+            _ => {
+                let suggestion = first
+                    .filter(|v| v.contains(','))
+                    .map(|v| replace_word(line, v, &v.replace(',', ".")));
+                Some((tr!("lint-scale"), suggestion))
+            }
+        },
+        "transform" => {
+            let value = first.unwrap_or("");
+            if TRANSFORMS.contains(&value) {
+                return None;
+            }
+            let suggestion = TRANSFORMS
+                .iter()
+                .min_by_key(|known| distance(value, known))
+                .filter(|known| distance(value, known) <= 2)
+                .map(|known| replace_word(line, value, known));
+            Some((
+                tr!("lint-transform", value, TRANSFORMS.join(", ")),
+                suggestion,
+            ))
+        }
+        "max_render_time" => match first {
+            Some("off") => None,
+            Some(ms) if ms.parse::<u32>().is_ok() => None,
+            _ => Some((tr!("lint-max-render-time"), None)),
+        },
+        _ => {
+            let (_, allowed) = KEYWORDS.iter().find(|(k, _)| *k == key)?;
+            let value = first.unwrap_or("");
+            if allowed.contains(&value) {
+                return None;
+            }
+            let suggestion = allowed
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(value))
+                .map(|known| replace_word(line, value, known));
+            Some((
+                tr!("lint-value", value, attribute.key, allowed.join(", ")),
+                suggestion,
+            ))
+        }
+    }
+}
+
+// Replace the first whole-word occurrence of `word` in a line
+fn replace_word(line: &str, word: &str, replacement: &str) -> String {
+    for (token, span) in output::tokenize_spans(line) {
+        if token == word {
+            return format!(
+                "{}{}{}",
+                &line[..span.start],
+                replacement,
+                &line[span.end..]
+            );
+        }
+    }
+    line.to_string()
+}
+
+// Levenshtein distance, for suggesting the subcommand that was meant
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(row[j + 1])
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(lines: &[&str]) -> DisplayConfig {
+        DisplayConfig {
+            description: "Desk".to_string(),
+            outputs: lines.iter().map(|line| line.to_string()).collect(),
+            status: "Disabled".to_string(),
+            metadata: Vec::new(),
+        }
+    }
+
+    // The corrected line suggested for each finding in a one-line profile
+    fn suggestions(line: &str) -> Vec<Option<String>> {
+        check(&[profile(&[line])])
+            .into_iter()
+            .map(|finding| finding.suggestion)
+            .collect()
+    }
+
+    fn suggested(line: &str) -> Option<String> {
+        match suggestions(line).as_slice() {
+            [suggestion] => suggestion.clone(),
+            other => panic!("{} findings for {}", other.len(), line),
+        }
+    }
+
+    #[test]
+    fn valid_lines_have_no_findings() {
+        for line in [
+            "output eDP-1 enable mode 2256x1504@59.999Hz pos 0 0 scale 1.5",
+            "output \"Dell Inc. U2720Q ABC\" transform flipped-90 adaptive_sync on",
+            "output * bg ~/wall.png fill",
+            "workspace 1 output DP-1",
+        ] {
+            assert!(suggestions(line).is_empty(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn suggests_fixes_for_common_slips() {
+        let cases = [
+            ("outptu DP-1 enable", "output DP-1 enable"),
+            (
+                "output DP-1 mode 1920X1080@60hz",
+                "output DP-1 mode 1920x1080@60Hz",
+            ),
+            ("output DP-1 pos 1920,0", "output DP-1 pos 1920 0"),
+            ("output DP-1 scale 1,5", "output DP-1 scale 1.5"),
+            ("output DP-1 transfrom 90", "output DP-1 transform 90"),
+            (
+                "output DP-1 transform flipped90",
+                "output DP-1 transform flipped-90",
+            ),
+            ("output DP-1 subpixel RGB", "output DP-1 subpixel rgb"),
+        ];
+        for (line, fixed) in cases {
+            assert_eq!(suggested(line).as_deref(), Some(fixed), "{}", line);
+        }
+    }
+
+    #[test]
+    fn reports_what_cannot_be_fixed() {
+        let findings = check(&[profile(&["output", "output DP-1", "output DP-2 scale 0"])]);
+        let messages: Vec<String> = findings.into_iter().map(|f| f.message).collect();
+        assert_eq!(
+            messages,
+            [
+                tr!("lint-no-name"),
+                tr!("lint-no-subcommand"),
+                tr!("lint-scale")
+            ]
+        );
+    }
+
+    #[test]
+    fn conditional_lines_keep_their_condition() {
+        assert_eq!(
+            suggested("?if-present HDMI-A-1: output eDP-1 pos 0,0").as_deref(),
+            Some("?if-present HDMI-A-1: output eDP-1 pos 0 0")
+        );
+    }
+
+    #[test]
+    fn reports_repeated_lines() {
+        let config = profile(&[
+            "output DP-1 enable",
+            "output  DP-1 enable",
+            "output DP-1 scale 2",
+            "output * bg #000000 solid_color",
+            "output DP-1 enable",
+        ]);
+        let messages: Vec<String> = check(&[config]).into_iter().map(|f| f.message).collect();
+        assert_eq!(
+            messages,
+            [tr!("lint-repeat"), tr!("lint-second-line", "DP-1")]
+        );
+    }
+
+    #[test]
+    fn reports_profiles_with_the_same_lines() {
+        let mut mirror = profile(&["output eDP-1 enable", "output DP-1 enable"]);
+        mirror.description = "Mirror".to_string();
+        let copy = profile(&["output DP-1  enable", "output eDP-1 enable"]);
+        let findings = check(&[mirror, copy, profile(&[])]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].profile, "Desk");
+        assert_eq!(findings[0].message, tr!("lint-duplicate", "Mirror"));
+    }
+
+    #[test]
+    fn reports_wildcards_after_specific_lines() {
+        let config = profile(&[
+            "output * scale 1",
+            "output eDP-1 enable scale 2",
+            "output * bg #000000 solid_color",
+            "output * scale 1.5",
+            "output * disable",
+        ]);
+        let findings = check(&[config]);
+        let found: Vec<(&str, String)> = findings
+            .iter()
+            .map(|f| (f.line.as_str(), f.message.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "output * scale 1.5",
+                    tr!("lint-wildcard-overrides", "scale", "eDP-1")
+                ),
+                ("output * disable", tr!("lint-wildcard-off", "eDP-1")),
+            ]
+        );
+    }
+}
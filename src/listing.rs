@@ -0,0 +1,99 @@
+// Ordering of profiles in listings
+
+use crate::config::DisplayConfig;
+use crate::matching;
+use crate::output::{self, OutputLine};
+use crate::state::State;
+use crate::sway::Output;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    // The order of the profiles in the config file
+    File,
+    Name,
+    // Most recently applied first
+    Recent,
+    // Best fit for the connected outputs first
+    Match,
+}
+
+impl SortKey {
+    pub fn parse(value: &str) -> Option<SortKey> {
+        match value.to_ascii_lowercase().as_str() {
+            "file" => Some(SortKey::File),
+            "name" => Some(SortKey::Name),
+            "recent" => Some(SortKey::Recent),
+            "match" => Some(SortKey::Match),
+            _ => None,
+        }
+    }
+}
+
+// Indices of the configs in listing order, favorites first. Ties keep the file
+// order, so profiles that were never used or do not fit the outputs stay in
+// place at the end.
+pub fn order(
+    configs: &[DisplayConfig],
+    key: SortKey,
+    state: &State,
+    connected: &[Output],
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..configs.len()).collect();
+    match key {
+        SortKey::File => {}
+        SortKey::Name => indices.sort_by_cached_key(|&i| configs[i].description.to_lowercase()),
+        SortKey::Recent => {
+            indices.sort_by_key(|&i| std::cmp::Reverse(state.last_used(&configs[i].description)))
+        }
+        SortKey::Match => {
+            indices.sort_by_key(|&i| std::cmp::Reverse(matching::rank(&configs[i], connected)))
+        }
+    }
+    indices.sort_by_key(|&i| !configs[i].is_favorite());
+    indices
+}
+
+// The outputs a profile has lines for, counted once each whether named by
+// connector or description, conditional lines included and `output *` lines
+// left out, together with how many of them are connected now
+pub fn coverage(config: &DisplayConfig, connected: &[Output]) -> (usize, usize) {
+    let mut targets: Vec<String> = Vec::new();
+    for line in &config.reidentified(connected).outputs {
+        let command = output::split_condition(line).map_or(line.as_str(), |(_, c)| c);
+        let Some(parsed) = OutputLine::parse(command).filter(|p| !p.is_wildcard()) else {
+            continue;
+        };
+        // A description and the connector it is on now are the same output
+        let target = connected
+            .iter()
+            .find(|o| o.matches(&parsed.target))
+            .map_or(parsed.target, |o| o.name.clone());
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    let present = targets
+        .iter()
+        .filter(|target| connected.iter().any(|o| o.name == **target))
+        .count();
+    (present, targets.len())
+}
+
+// Whether a profile has a line for the output named by `query`: a connector
+// such as `DP-3` (also found through the description it is configured by when
+// that output is connected) or part of an output description, ignoring case.
+// Wildcard lines do not count.
+pub fn mentions(config: &DisplayConfig, query: &str, connected: &[Output]) -> bool {
+    let query_lower = query.to_lowercase();
+    config
+        .outputs
+        .iter()
+        .filter_map(|line| OutputLine::parse(line))
+        .filter(|line| !line.is_wildcard())
+        .any(|line| {
+            line.target.to_lowercase().contains(&query_lower)
+                || connected
+                    .iter()
+                    .any(|output| output.name == query && output.matches(&line.target))
+        })
+}
@@ -1,215 +1,2258 @@
-use expanduser::expanduser;
-use regex::Regex;
-use std::fs::{self, File, OpenOptions};
-use std::io::{self, BufRead, BufReader, BufWriter, Write};
-use std::path::Path;
+#[cfg(feature = "interactive")]
+mod checklist;
+mod cli;
+#[cfg(feature = "dialogs")]
+mod dialog;
+mod edit;
+mod inspect;
+mod prompt;
+#[cfg(feature = "gtk")]
+mod window;
+#[cfg(feature = "interactive")]
+mod wizard;
+
+use cli::Args;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use std::process;
-use text_io::read;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use sway_display_switcher::config::{self, DisplayConfig, Interrupted, SwayConfig};
+#[cfg(feature = "daemon")]
+use sway_display_switcher::daemon;
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::listing::SortKey;
+use sway_display_switcher::output::Override;
+use sway_display_switcher::plan::Plan;
+use sway_display_switcher::profile::Profile;
+use sway_display_switcher::{
+    audit, backup, diff, effects, events, generate, gpu, import, layout, lint, listing, matching,
+    migrate, mqtt, output, profile, query, remote, roles, settings, state, sway, switcher, tr,
+    windows,
+};
 
-#[derive(Debug, Clone)]
-struct DisplayConfig {
-    description: String,
-    outputs: Vec<String>,
-    status: String,
+fn main() {
+    let args = Args::parse().unwrap_or_else(|e| {
+        // --json may follow whatever could not be parsed
+        error::report(&e, env::args().any(|arg| arg == "--json"))
+    });
+    // The daemon keeps ignoring it: a subscriber that hangs up only fails the
+    // write to it
+    if !(args.watch || args.observe) {
+        default_sigpipe();
+    }
+    if let Err(e) = run(&args) {
+        error::report(&e, args.json);
+    }
+}
+
+// A reader that goes away early, as `head` does after `list --json`, ends the
+// command as it would any other Unix tool, instead of println! panicking on the
+// closed pipe. Rust starts programs with SIGPIPE ignored; this puts back the
+// default action.
+fn default_sigpipe() {
+    extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+    const SIGPIPE: i32 = 13;
+    const SIG_DFL: usize = 0;
+    // Called before any other thread is started
+    unsafe {
+        signal(SIGPIPE, SIG_DFL);
+    }
 }
 
-fn main() -> io::Result<()> {
-    let config_path = expanduser("~/.config/sway/config").expect("Failed to expand config path");
+// Every failure below comes back here as an Error, reported once by main
+fn run(args: &Args) -> error::Result<()> {
+    if args.help {
+        cli::print_usage();
+        return Ok(());
+    }
+
+    // Run as root by pkexec or sudo when the system config is read-only
+    if args.command.as_deref() == Some("write-config") {
+        let [staged, target] = args.positional.as_slice() else {
+            return Err(Error::Usage(tr!("usage-write-config")));
+        };
+        return config::write_staged(Path::new(staged), Path::new(target))
+            .map_err(|e| Error::file("write", target, e));
+    }
 
-    // Read all lines from the config file
-    let file = File::open(&config_path).expect("Failed to open config file");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader.lines().filter_map(Result::ok).collect();
+    select_session(args)?;
+    let settings = settings::load();
+    matching::set_policy(settings.matching.clone());
+    effects::set_webhooks(settings.webhooks.clone());
+    mqtt::set_broker(settings.mqtt.clone());
+    windows::set_remember(settings.remember_windows);
+    switcher::set_force(args.force);
 
-    // Identify the 'Display Start' and 'Display End' indices
-    let display_start = lines
-        .iter()
-        .position(|line| line.contains("Display Start"))
-        .unwrap_or_else(|| {
-            eprintln!("Error: 'Display Start' marker not found in the config file.");
-            process::exit(1);
+    if args.host.is_some() && (args.watch || args.observe || args.from_udev) {
+        return Err(Error::Usage(tr!("host-conflict")));
+    }
+    let config_path = if args.host.is_some() {
+        remote::fetch().map_err(Error::Failed)?
+    } else if args.system {
+        PathBuf::from(config::SYSTEM_PATH)
+    } else {
+        config::default_path()?
+    };
+    if args.command.as_deref() == Some("recover") {
+        return recover(args, &config_path);
+    }
+    // Before loading, so a config that no longer loads can still be put back
+    if args.command.as_deref() == Some("backups") {
+        return backups(args, &config_path);
+    }
+    check_interrupted_write(&config_path)?;
+    if args.watch || args.observe {
+        #[cfg(feature = "daemon")]
+        return Err(daemon::watch(&config_path, args.observe, &settings));
+        #[cfg(not(feature = "daemon"))]
+        return Err(cli::left_out(
+            if args.watch { "--watch" } else { "--observe" },
+            "daemon",
+        ));
+    }
+
+    if args.from_udev {
+        return from_udev(&config_path, settings.settle_delay);
+    }
+    // Works on configs that do not have the managed section yet
+    if args.command.as_deref() == Some("generate")
+        && args.positional.first().is_some_and(|p| p == "sway")
+    {
+        return generate_sway(args, &config_path);
+    }
+
+    let config = SwayConfig::load(&config_path)?;
+    check_malformed(args, &config)?;
+    let display_configs = config.display_configs();
+    for conflict in config::alias_conflicts(&display_configs) {
+        eprintln!("{}", tr!("warning", conflict));
+    }
+
+    // Commands that regenerate the managed section
+    let rewrites = match args.command.as_deref() {
+        None
+        | Some(
+            "switch" | "next" | "outputs" | "migrate" | "import" | "position" | "refresh"
+            | "tearing" | "icc" | "wizard" | "add-output" | "capture" | "visual-edit",
+        ) => true,
+        Some("format") => !args.check,
+        Some("gpu") => args.positional.first().is_some_and(|p| p == "detach"),
+        Some("compose") => args.save.is_some(),
+        Some("generate") => args.insert && args.positional.first().is_some_and(|p| p == "matrix"),
+        _ => false,
+    };
+    if rewrites && !(args.command.is_some() && args.transient) {
+        guard_hand_edits(&config)?;
+    }
+
+    match args.command.as_deref() {
+        None => run_interactive(args, &config, &display_configs),
+        Some("switch") => {
+            let selected_index = require_profile(args, &display_configs)?;
+            switch_to(args, &config, &display_configs, selected_index)
+        }
+        Some("next") => {
+            let current = display_configs.iter().position(DisplayConfig::is_enabled);
+            let index = config::next_config(&display_configs, current, args.tag.as_deref())
+                .ok_or_else(|| match &args.tag {
+                    Some(tag) => Error::Failed(tr!("no-tagged-profile", tag)),
+                    None => Error::Failed(tr!("no-profiles")),
+                })?;
+            switch_to(args, &config, &display_configs, index)
+        }
+        Some("compose") => compose(args, &config, &display_configs),
+        Some("plan") => {
+            let selected_index = require_profile(args, &display_configs)?;
+            let plan = Plan::build(&config, &display_configs, selected_index, args.transient);
+            plan.print(&config);
+            warn_layout(&display_configs[selected_index]);
+            plan.save()?;
+            println!("\n{}", tr!("run-apply"));
+            Ok(())
+        }
+        Some("apply") => {
+            let plan = Plan::load().map_err(|_| Error::Failed(tr!("no-plan")))?;
+            switcher::apply_plan(&plan, &config, &display_configs, &trigger(args))?;
+            println!("{}", tr!("plan-applied", plan.description));
+            Ok(())
+        }
+        Some("list") => {
+            let order = listing_order(args, &display_configs)?;
+            if let Some(format) = args.format.as_deref() {
+                let delimiter = match format {
+                    "csv" => ',',
+                    "tsv" => '\t',
+                    other => {
+                        return Err(Error::Usage(tr!("unknown-list-format", other)));
+                    }
+                };
+                print!("{}", inspect::table(&display_configs, &order, delimiter));
+            } else if args.json {
+                let sorted: Vec<DisplayConfig> =
+                    order.iter().map(|&i| display_configs[i].clone()).collect();
+                print_json(&profile::profiles_document(&sorted))?;
+            } else {
+                let connected = sway::get_outputs();
+                print_listing(&display_configs, &order, connected.as_deref(), args.long);
+            }
+            Ok(())
+        }
+        Some("show") => {
+            let selected = &display_configs[require_profile(args, &display_configs)?];
+            if args.json {
+                print_json(&Profile::from(selected))?;
+            } else {
+                inspect::show(selected, &sway::get_outputs().unwrap_or_default());
+            }
+            Ok(())
+        }
+        Some("diff") => {
+            let (Some(first), Some(second)) = (args.positional.first(), args.positional.get(1))
+            else {
+                return Err(Error::Usage(tr!("usage-diff")));
+            };
+            let find = |query: &String| {
+                config::find_config(&display_configs, query)
+                    .ok_or_else(|| Error::NoSuchProfile(query.to_string()))
+            };
+            let (a, b) = (
+                &display_configs[find(first)?],
+                &display_configs[find(second)?],
+            );
+            let changes = diff::diff_profiles(a, b);
+            if changes.is_empty() {
+                println!("{}", tr!("profiles-same", a.description, b.description));
+            } else {
+                println!("--- {}", a.description);
+                println!("+++ {}", b.description);
+                for line in changes {
+                    println!("{}", line);
+                }
+            }
+            Ok(())
+        }
+        Some("format") => {
+            let mut formatted = display_configs.clone();
+            for profile in formatted.iter_mut() {
+                let normalized: Vec<String> = profile
+                    .outputs
+                    .iter()
+                    .map(|line| output::normalize_line(line))
+                    .collect();
+                // A line repeated right after itself changes nothing
+                profile.outputs = output::sort_lines(&normalized);
+                profile.outputs.dedup();
+            }
+            let new_lines = config.with_formatted_section(&formatted);
+            let changes = diff::format_diff(&config.lines, &new_lines, 1);
+            if changes.is_empty() {
+                println!("{}", tr!("already-formatted"));
+                return Ok(());
+            }
+            for line in changes {
+                println!("{}", line);
+            }
+            if args.check {
+                return Err(Error::Failed(tr!("not-formatted")));
+            }
+            config.write(&new_lines)?;
+            println!("{}", tr!("formatted", config.path.display()));
+            Ok(())
+        }
+        Some("lint") => {
+            let mut findings = lint::check(&display_configs);
+            if let Some(version) = sway::version() {
+                findings.extend(lint::check_support(&display_configs, version));
+            }
+            for finding in &findings {
+                if finding.line.is_empty() {
+                    println!("{}", finding.profile);
+                } else {
+                    println!("{}: {}", finding.profile, finding.line);
+                }
+                println!("  {}", finding.message);
+                if let Some(suggestion) = &finding.suggestion {
+                    println!("  {}", tr!("did-you-mean", suggestion));
+                }
+            }
+            if !findings.is_empty() {
+                return Err(Error::Failed(tr!("problems-found", findings.len())));
+            }
+            println!("{}", tr!("no-problems"));
+            Ok(())
+        }
+        Some("export") => {
+            let selected = &display_configs[require_profile(args, &display_configs)?];
+            match args.format.as_deref().unwrap_or("sway") {
+                "sway" => {
+                    println!("# Display profile: {}", selected.description);
+                    for line in &selected.outputs {
+                        // Sway would reject the `?identity` records
+                        if output::parse_identity(line).is_some() {
+                            println!("# {}", line);
+                        } else {
+                            println!("{}", line);
+                        }
+                    }
+                }
+                "json" => print_json(&Profile::from(selected))?,
+                other => {
+                    return Err(Error::Usage(tr!("unknown-export-format", other)));
+                }
+            }
+            Ok(())
+        }
+        Some("migrate") => match args.positional.first().map(String::as_str) {
+            Some("descriptions") => migrate_descriptions(&config, &display_configs),
+            Some("adopt") => migrate_adopt(&config, &display_configs),
+            Some("identities") => migrate_identities(&config, &display_configs),
+            _ => Err(Error::Usage(tr!("usage-migrate"))),
+        },
+        Some("import") => import(args, &config, &display_configs),
+        Some("position") => edit::position(args, &config, &display_configs),
+        Some("refresh") => edit::refresh(args, &config, &display_configs),
+        Some("tearing") => edit::tearing(args, &config, &display_configs),
+        Some("icc") => edit::icc(args, &config, &display_configs),
+        Some("generate") => match args.positional.first().map(String::as_str) {
+            Some("bindings") => {
+                let bindings = generate::bindings(&display_configs);
+                if args.insert {
+                    let new_lines = config::replace_block(
+                        &config.lines,
+                        generate::BINDINGS_START,
+                        generate::BINDINGS_END,
+                        &bindings,
+                    );
+                    config.write(&new_lines)?;
+                    println!("{}", tr!("bindings-updated", config.path.display()));
+                } else {
+                    for line in bindings {
+                        println!("{}", line);
+                    }
+                }
+                Ok(())
+            }
+            Some("matrix") => {
+                let connected =
+                    sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+                let generated = generate::matrix(&connected, &display_configs);
+                if generated.is_empty() {
+                    println!("{}", tr!("matrix-exists"));
+                } else if args.insert {
+                    let mut updated = display_configs.clone();
+                    updated.extend(generated.iter().cloned());
+                    config.save(&updated)?;
+                    for profile in &generated {
+                        println!("{}", tr!("saved-profile", profile.description));
+                    }
+                } else {
+                    for line in config::render_section(&generated) {
+                        println!("{}", line);
+                    }
+                }
+                Ok(())
+            }
+            Some("completions") => {
+                let shell = args.positional.get(1).map_or("bash", String::as_str);
+                match generate::completions(shell) {
+                    Some(script) => print!("{}", script),
+                    None => {
+                        return Err(Error::Usage(tr!("no-completions", shell)));
+                    }
+                }
+                Ok(())
+            }
+            Some("udev") => generate_udev(args.positional.get(1).map(PathBuf::from)),
+            _ => Err(Error::Usage(tr!("usage-generate"))),
+        },
+        Some("gpu") => match args.positional.first().map(String::as_str) {
+            Some("list") | None => {
+                list_gpus();
+                Ok(())
+            }
+            Some("detach") => detach_gpu(args, &config, &display_configs),
+            _ => Err(Error::Usage(tr!("usage-gpu"))),
+        },
+        Some("detect") => detect(args),
+        Some("capture") => capture(args, &config, &display_configs),
+        #[cfg(feature = "dialogs")]
+        Some("visual-edit") => visual_edit(args, &config, &display_configs),
+        Some("confirm") => {
+            match state::confirm_pending(None) {
+                Some(revert) => println!("{}", tr!("keeping-settings", revert.profile)),
+                None => println!("{}", tr!("nothing-to-confirm")),
+            }
+            Ok(())
+        }
+        Some("watchdog") => watchdog(args, &config.path),
+        Some("test") => test(args, &display_configs),
+        Some("present") => present(args, &display_configs),
+        Some("power") => power(args),
+        Some("blank") => blank(),
+        Some("lock") => lock(),
+        #[cfg(feature = "interactive")]
+        Some("wizard") => create_profile(args, &config, &display_configs),
+        #[cfg(feature = "interactive")]
+        Some("add-output") => edit::add_output(args, &config, &display_configs),
+        #[cfg(feature = "interactive")]
+        Some("outputs") => checklist::run(args, &config, &display_configs),
+        Some("sockets") => {
+            for socket in sway::list_sockets() {
+                let marker = if Some(&socket) == sway::socket() {
+                    tr!("selected-marker")
+                } else {
+                    String::new()
+                };
+                println!("{}{}", socket.display(), marker);
+            }
+            Ok(())
+        }
+        Some("restore") => restore(args, &display_configs),
+        Some("which") => which(args, &display_configs),
+        Some("get") => get(args, &display_configs),
+        Some("events") => {
+            let since = args
+                .since
+                .map_or(0, |secs| state::now().saturating_sub(secs));
+            let entries: Vec<_> = audit::load()
+                .into_iter()
+                .filter(|entry| entry.time >= since)
+                .collect();
+            let skip = args.lines.map_or(0, |n| entries.len().saturating_sub(n));
+            for entry in &entries[skip..] {
+                println!("{}", entry.event());
+            }
+            if !args.follow {
+                return Ok(());
+            }
+            let stream = events::subscribe().map_err(|e| Error::Failed(tr!("no-daemon", e)))?;
+            for line in stream.lines().map_while(Result::ok) {
+                println!("{}", line);
+            }
+            Err(Error::Failed(tr!("daemon-stopped")))
+        }
+        Some("log") => match args.positional.first().map(String::as_str) {
+            Some("show") | None => {
+                let entries = audit::load();
+                if args.json {
+                    print_json(&entries)?;
+                } else if entries.is_empty() {
+                    println!("{}", tr!("log-empty"));
+                } else {
+                    for entry in &entries {
+                        println!("{}", audit::describe(entry));
+                    }
+                }
+                Ok(())
+            }
+            Some("clear") => {
+                audit::clear()?;
+                println!("{}", tr!("cleared", audit::log_path().display()));
+                Ok(())
+            }
+            _ => Err(Error::Usage(tr!("usage-log"))),
+        },
+        #[cfg(feature = "gtk")]
+        Some("window") => window::run(&config.path),
+        Some("names") => {
+            for config in &display_configs {
+                println!("{}", config.description);
+                for alias in config.aliases() {
+                    println!("{}", alias);
+                }
+            }
+            Ok(())
+        }
+        Some(other) => match cli::feature_for(other) {
+            Some(feature) => Err(cli::left_out(&format!("'{}'", other), feature)),
+            None => {
+                cli::print_usage();
+                Err(Error::Usage(tr!("unknown-command", other)))
+            }
+        },
+    }
+}
+
+// Print a document for scripts as one line of JSON
+fn print_json<T: serde::Serialize>(document: &T) -> error::Result<()> {
+    let line = serde_json::to_string(document).map_err(|e| Error::Failed(e.to_string()))?;
+    println!("{}", line);
+    Ok(())
+}
+
+// Before regenerating the managed section, warn when it was edited by hand and
+// refuse to silently drop lines the parser does not understand
+fn guard_hand_edits(config: &SwayConfig) -> error::Result<()> {
+    if !config.hand_edited() {
+        return Ok(());
+    }
+    let lossy = config.lossy_lines();
+    if lossy.is_empty() {
+        eprintln!("{}", tr!("hand-edited"));
+        return Ok(());
+    }
+    eprintln!("{}", tr!("warning", tr!("hand-edited-lossy")));
+    for line in &lossy {
+        eprintln!("  {}", line);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(Error::Failed(tr!("refusing-rewrite")));
+    }
+    if !prompt::confirm(&tr!("rewrite-anyway"), false)? {
+        return Err(Error::Cancelled);
+    }
+    Ok(())
+}
+
+// Warn about the lines of the managed section that are not Sway commands, or
+// with --strict refuse to go on while there are any
+fn check_malformed(args: &Args, config: &SwayConfig) -> error::Result<()> {
+    let malformed = config.malformed_lines();
+    if args.strict && !malformed.is_empty() {
+        let mut message = tr!("malformed-count", malformed.len());
+        for line in &malformed {
+            message.push_str(&format!(
+                "\n  {}",
+                tr!("malformed-line", line.number, line.reason, line.line.trim())
+            ));
+        }
+        return Err(Error::Config {
+            path: config.path.clone(),
+            message,
         });
-    let display_end = lines
+    }
+    for line in &malformed {
+        let outcome = if line.in_profile {
+            tr!("kept-as-comment")
+        } else {
+            tr!("kept-in-place")
+        };
+        let message = tr!(
+            "malformed-warning",
+            config.path.display(),
+            line.number,
+            line.reason,
+            outcome,
+            line.line.trim()
+        );
+        eprintln!("{}", tr!("warning", message));
+    }
+    Ok(())
+}
+
+// Offer to clean up after a previous run that stopped while writing the config
+fn check_interrupted_write(config_path: &Path) -> error::Result<()> {
+    let (question, finish) = match config::interrupted_write(config_path) {
+        None => return Ok(()),
+        // Only the journal was left behind; nothing to decide
+        Some(Interrupted::Finished) => {
+            let _ = config::recover(config_path, false);
+            return Ok(());
+        }
+        Some(Interrupted::Complete(temp)) => {
+            eprintln!(
+                "{}",
+                tr!("warning", tr!("interrupted-complete", temp.display()))
+            );
+            (tr!("finish-write"), true)
+        }
+        Some(Interrupted::Incomplete(temp)) => {
+            let message = tr!(
+                "interrupted-incomplete",
+                temp.display(),
+                config_path.display()
+            );
+            eprintln!("{}", tr!("warning", message));
+            (tr!("discard-partial"), false)
+        }
+    };
+    if !io::stdin().is_terminal() {
+        eprintln!("{}", tr!("run-recover"));
+        return Ok(());
+    }
+    if prompt::confirm(&question, true)? {
+        config::recover(config_path, finish).map_err(|e| Error::file("recover", config_path, e))?;
+    }
+    Ok(())
+}
+
+// Resolve an interrupted write non-interactively
+fn recover(args: &Args, config_path: &Path) -> error::Result<()> {
+    let finish = match args.positional.first().map(String::as_str) {
+        Some("finish") => true,
+        Some("discard") => false,
+        _ => {
+            return Err(Error::Usage(tr!("usage-recover")));
+        }
+    };
+    match config::interrupted_write(config_path) {
+        None => println!("{}", tr!("nothing-to-recover")),
+        Some(Interrupted::Incomplete(_)) if finish => {
+            return Err(Error::Failed(tr!("write-incomplete")));
+        }
+        Some(_) => {
+            config::recover(config_path, finish)?;
+            println!("{}", tr!("recovered", config_path.display()));
+        }
+    }
+    Ok(())
+}
+
+// List the copies kept before each write, compare one with the config, or put
+// one back
+fn backups(args: &Args, config_path: &Path) -> error::Result<()> {
+    let read_current = || -> error::Result<Vec<String>> {
+        match fs::read_to_string(config_path) {
+            Ok(text) => Ok(text.lines().map(String::from).collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(Error::file("read", config_path, e)),
+        }
+    };
+    let timestamp = args.positional.get(1).map_or("latest", String::as_str);
+    match args.positional.first().map(String::as_str) {
+        Some("list") | None => {
+            let backups = backup::list(config_path);
+            if backups.is_empty() {
+                println!("{}", tr!("no-backups-yet", config_path.display()));
+            }
+            for backup in &backups {
+                println!("{}", backup.describe());
+            }
+        }
+        Some("diff") => {
+            let backup = backup::find(config_path, timestamp).map_err(Error::Failed)?;
+            let old = backup
+                .lines()
+                .map_err(|e| Error::file("read", &backup.path, e))?;
+            let changes = diff::format_diff(&old, &read_current()?, 2);
+            if changes.is_empty() {
+                println!("{}", tr!("same-as-backup", backup.time));
+                return Ok(());
+            }
+            println!("{}", tr!("backup-header", backup.describe()));
+            println!("+++ {}", config_path.display());
+            for line in changes {
+                println!("{}", line);
+            }
+        }
+        Some("restore") if args.positional.len() == 2 => {
+            let backup = backup::find(config_path, timestamp).map_err(Error::Failed)?;
+            let lines = backup
+                .lines()
+                .map_err(|e| Error::file("read", &backup.path, e))?;
+            if lines == read_current()? {
+                println!("{}", tr!("same-as-backup", backup.time));
+                return Ok(());
+            }
+            // The config being replaced is backed up in turn
+            config::write_config(config_path, &lines)
+                .map_err(|e| Error::file("write", config_path, e))?;
+            println!(
+                "{}",
+                tr!("restored-backup", config_path.display(), backup.time)
+            );
+        }
+        _ => {
+            return Err(Error::Usage(tr!("usage-backups")));
+        }
+    }
+    Ok(())
+}
+
+// Pick the Sway instance and the per-seat state namespace for this invocation
+fn select_session(args: &Args) -> error::Result<()> {
+    // A remote host's instance is found on the host; its state is kept apart
+    if let Some(host) = &args.host {
+        remote::set_host(Some(host.clone()), args.system);
+        sway::set_socket(args.socket.as_ref().map(PathBuf::from));
+        state::set_seat(args.seat.clone().or_else(|| Some(host.clone())));
+        return Ok(());
+    }
+
+    // The second element tells whether one of several instances was singled out
+    let (socket, singled_out) = match &args.socket {
+        Some(socket) => (Some(PathBuf::from(socket)), true),
+        // swaymsg already knows which instance we are running under
+        None if env::var_os("SWAYSOCK").is_some() => (None, false),
+        None => {
+            let mut sockets = sway::list_sockets();
+            if sockets.len() <= 1 {
+                (sockets.pop(), false)
+            } else if args.command.is_none() && io::stdin().is_terminal() {
+                println!("{}", tr!("multiple-instances"));
+                for (i, socket) in sockets.iter().enumerate() {
+                    println!("{}. {}", i + 1, socket.display());
+                }
+                let selected = get_socket_selection(sockets.len())?;
+                (Some(sockets.swap_remove(selected)), true)
+            } else {
+                let mut message = tr!("multiple-instances-error");
+                for socket in &sockets {
+                    message.push_str(&format!("\n  {}", socket.display()));
+                }
+                return Err(Error::Usage(message));
+            }
+        }
+    };
+
+    // Without an explicit seat, keep a singled-out instance's state apart
+    let seat = args.seat.clone().or_else(|| {
+        socket
+            .as_ref()
+            .filter(|_| singled_out)
+            .and_then(|s| s.file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+    sway::set_socket(socket);
+    state::set_seat(seat);
+    Ok(())
+}
+
+fn run_interactive(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let order = listing_order(args, display_configs)?;
+    if order.is_empty() {
+        return Err(Error::Failed(tr!("no-profiles")));
+    }
+
+    // What to apply when nobody answers within --timeout: --default, or else
+    // the Enabled profile
+    let fallback = match &args.default {
+        Some(query) => Some(
+            config::find_config(display_configs, query)
+                .ok_or_else(|| Error::NoSuchProfile(query.to_string()))?,
+        ),
+        None => display_configs.iter().position(DisplayConfig::is_enabled),
+    };
+    let timed_out = || -> error::Result<()> {
+        let Some(index) = fallback else {
+            println!("{}", tr!("exiting"));
+            return Ok(());
+        };
+        let seconds = args.timeout.unwrap_or_default();
+        println!(
+            "{}",
+            tr!(
+                "prompt-timed-out",
+                seconds,
+                display_configs[index].description
+            )
+        );
+        switch_to(args, config, display_configs, index)
+    };
+
+    // Without a terminal to read from, take a selection piped in; when none
+    // comes (a bar click runs us with an empty or idle stdin), nobody can
+    // answer the prompt, so ask through a dialog instead of blocking
+    if !io::stdin().is_terminal() {
+        #[cfg(feature = "menu")]
+        if let Some(selection) = piped_selection() {
+            let index = config::find_config(display_configs, &selection)
+                .ok_or_else(|| Error::NoSuchProfile(selection.to_string()))?;
+            return switch_to(args, config, display_configs, index);
+        }
+        #[cfg(feature = "dialogs")]
+        match dialog::choose(display_configs, &order, args.timeout) {
+            dialog::Choice::Selected(index) => {
+                return switch_to(args, config, display_configs, index);
+            }
+            dialog::Choice::Cancelled => {
+                println!("{}", tr!("exiting"));
+                return Ok(());
+            }
+            dialog::Choice::TimedOut => return timed_out(),
+            dialog::Choice::Unavailable => {
+                return Err(Error::Failed(format!(
+                    "{}\n{}",
+                    tr!("no-dialog"),
+                    tr!("no-dialog-hint")
+                )));
+            }
+        }
+        #[cfg(not(feature = "dialogs"))]
+        return Err(cli::left_out(&tr!("picker-without-terminal"), "dialogs"));
+    }
+    match prompt_selection(display_configs, &order, args.timeout)? {
+        Some(selected_index) => switch_to(args, config, display_configs, selected_index),
+        None => timed_out(),
+    }
+}
+
+// List the configs at the given indices and ask on the terminal which one to
+// activate; None when nobody answered within `timeout` seconds
+#[cfg(feature = "interactive")]
+fn prompt_selection(
+    display_configs: &[DisplayConfig],
+    order: &[usize],
+    timeout: Option<u64>,
+) -> error::Result<Option<usize>> {
+    let connected = sway::get_outputs();
+    if let Some(connected) = &connected {
+        for output in matching::unknown_outputs(display_configs, connected) {
+            println!(
+                "{}",
+                tr!("unknown-output-notice", output.name, output.identifier())
+            );
+        }
+    }
+    print_listing(display_configs, order, connected.as_deref(), false);
+
+    // Prompt user to select a config
+    get_user_selection(order, timeout.map(Duration::from_secs))
+}
+
+#[cfg(not(feature = "interactive"))]
+fn prompt_selection(
+    _display_configs: &[DisplayConfig],
+    _order: &[usize],
+    _timeout: Option<u64>,
+) -> error::Result<Option<usize>> {
+    Err(cli::left_out(&tr!("profile-picker"), "interactive"))
+}
+
+// How long to wait for a selection piped into the picker before deciding
+// that none is coming
+#[cfg(feature = "menu")]
+const PIPED_SELECTION_WAIT: Duration = Duration::from_secs(1);
+
+// The first non-empty line on a non-terminal stdin, if one arrives within
+// PIPED_SELECTION_WAIT
+#[cfg(feature = "menu")]
+fn piped_selection() -> Option<String> {
+    let (sender, lines) = mpsc::channel();
+    thread::spawn(move || {
+        let line = io::stdin()
+            .lines()
+            .map_while(Result::ok)
+            .map(|line| line.trim().to_string())
+            .find(|line| !line.is_empty());
+        if let Some(line) = line {
+            let _ = sender.send(line);
+        }
+    });
+    lines.recv_timeout(PIPED_SELECTION_WAIT).ok()
+}
+
+// Run the wizard and append the result as a new profile, optionally activating it
+#[cfg(feature = "interactive")]
+fn create_profile(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let new_config = wizard::run(args.auto_scale)?;
+    let description = new_config.description.clone();
+    let mut updated = display_configs.to_vec();
+    updated.push(new_config);
+    config.save(&updated)?;
+    println!("{}", tr!("saved-profile", description));
+    if prompt::confirm(&tr!("activate-now"), false)? {
+        return switch_to_saved(args, &config.path, &description);
+    }
+    Ok(())
+}
+
+// Activate the profile just saved under `description`, as the file now has it
+fn switch_to_saved(args: &Args, config_path: &Path, description: &str) -> error::Result<()> {
+    let reloaded = SwayConfig::load(config_path)?;
+    let display_configs = reloaded.display_configs();
+    let index = config::find_config(&display_configs, description)
+        .ok_or_else(|| Error::NoSuchProfile(description.to_string()))?;
+    switch_to(args, &reloaded, &display_configs, index)
+}
+
+// Merge the named profiles into one, later ones winning per output, and print
+// it. --save adds it to the managed section; --apply activates it, transiently
+// unless it was saved.
+fn compose(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    if args.positional.len() < 2 {
+        return Err(Error::Usage(tr!("usage-compose")));
+    }
+    let parts: Vec<&DisplayConfig> = args
+        .positional
         .iter()
-        .position(|line| line.contains("Display End"))
-        .unwrap_or_else(|| {
-            eprintln!("Error: 'Display End' marker not found in the config file.");
-            process::exit(1);
-        });
+        .map(|query| match config::find_config(display_configs, query) {
+            Some(index) => Ok(&display_configs[index]),
+            None => Err(Error::NoSuchProfile(query.to_string())),
+        })
+        .collect::<error::Result<_>>()?;
+    let mut composed = config::compose(&parts);
 
-    // Extract the display section
-    let display_section = &lines[display_start..display_end];
+    let Some(name) = &args.save else {
+        for line in config::render_section(std::slice::from_ref(&composed)) {
+            println!("{}", line);
+        }
+        if args.apply {
+            warn_layout(&composed);
+            switcher::switch_transient(&composed, &trigger(args))?;
+            println!("{}", tr!("applied-transient", composed.description));
+        }
+        return Ok(());
+    };
+
+    if config::find_config(display_configs, name).is_some() {
+        return Err(Error::Failed(tr!("profile-named-exists", name)));
+    }
+    composed.description = name.clone();
+    let mut updated = display_configs.to_vec();
+    updated.push(composed);
+    config.save(&updated)?;
+    println!("{}", tr!("saved-profile", name));
+    if args.apply {
+        return switch_to_saved(args, &config.path, name);
+    }
+    Ok(())
+}
 
-    // Parse the display section into DisplayConfig structs
-    let desc_status_regex = Regex::new(r"# Description = ([^,]+), Status = ([^,]+)").unwrap();
-    let display_configs = parse_configs(display_section, &desc_status_regex);
-    let enabled_config = display_configs
+// Activate the selected config, persisting it unless --transient was given.
+// With --only just the lines for those outputs are applied, although the file
+// still records the whole profile as Enabled. --set overrides are applied on
+// top until the next reload, or written into the profile with --persist.
+// Fails with Error::Unchanged, touching nothing, when it is already in effect.
+fn switch_to(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    selected_index: usize,
+) -> error::Result<()> {
+    let selected = &display_configs[selected_index];
+    if args.persist && args.transient {
+        return Err(Error::Usage(tr!("persist-transient")));
+    }
+    // What --revert-after goes back to: the profile Sway showed last for a
+    // transient switch, the Enabled one otherwise
+    let previous = if args.transient {
+        state::State::load().last_profile
+    } else {
+        config::enabled_config(display_configs).map(|c| c.description.clone())
+    };
+    let overrides: Vec<Override> = args
+        .overrides
         .iter()
-        .position(|c| c.status.eq_ignore_ascii_case("Enabled"));
+        .map(|text| Override::parse(text).map_err(Error::Usage))
+        .collect::<error::Result<_>>()?;
+    let connected = if args.only.is_empty() && overrides.is_empty() {
+        Vec::new()
+    } else {
+        sway::get_outputs().unwrap_or_default()
+    };
+    let overridden = selected
+        .with_overrides(&overrides, &connected)
+        .map_err(Error::Failed)?;
+    warn_layout(&overridden);
+    let applied = if args.only.is_empty() {
+        overridden.clone()
+    } else {
+        overridden
+            .restricted_to(&args.only, &connected)
+            .map_err(Error::Failed)?
+    };
+
+    // Persisted overrides still have to be written even when Sway shows them
+    let rewrites_profile = args.persist && overridden.outputs != selected.outputs;
+    if !rewrites_profile && switcher::already_active(&applied, args.transient) {
+        return Err(Error::Unchanged(tr!(
+            "already-active",
+            selected.description
+        )));
+    }
+
+    if args.transient {
+        switcher::switch_transient(&applied, &trigger(args))?;
+        println!("{}", tr!("applied-transient", selected.description));
+        arm_watchdog(args, previous.as_deref());
+        return Ok(());
+    }
+
+    let mut updated = display_configs.to_vec();
+    if args.persist {
+        updated[selected_index] = overridden.clone();
+    }
+    let display_configs = &updated[..];
+
+    if args.only.is_empty() {
+        switcher::switch_persistent(config, display_configs, selected_index, &trigger(args))?;
+        if !overrides.is_empty() && !args.persist {
+            // Send just the lines the overrides changed on top of the reload
+            let mut changed = overridden.clone();
+            changed
+                .outputs
+                .retain(|line| !selected.outputs.contains(line));
+            state::record_live(&overridden.description, &overridden.outputs);
+            if !sway::apply_outputs(&changed) {
+                return Err(Error::Sway(tr!("apply-failed")));
+            }
+        }
+    } else {
+        switcher::switch_partial(
+            config,
+            display_configs,
+            selected_index,
+            &applied,
+            &trigger(args),
+        )?;
+        println!(
+            "{}",
+            tr!("applied-only", selected.description, args.only.join(", "))
+        );
+    }
+    if !overrides.is_empty() {
+        let list: Vec<String> = overrides.iter().map(Override::to_string).collect();
+        if args.persist {
+            println!(
+                "{}",
+                tr!("overrides-saved", list.join(", "), selected.description)
+            );
+        } else {
+            println!("{}", tr!("overrides-applied", list.join(", ")));
+        }
+    }
+    arm_watchdog(args, previous.as_deref());
+    Ok(())
+}
+
+// With --revert-after, start a detached copy of this program that switches back
+// to `previous` when the switch is not confirmed in time
+fn arm_watchdog(args: &Args, previous: Option<&str>) {
+    let Some(seconds) = args.revert_after else {
+        return;
+    };
+    let Some(previous) = previous else {
+        eprintln!("{}", tr!("warning", tr!("no-previous")));
+        return;
+    };
+    let deadline = state::now() + seconds;
+    let mut state = state::State::load();
+    state.pending_revert = Some(state::PendingRevert {
+        profile: previous.to_string(),
+        deadline,
+    });
+    if let Err(e) = state.save() {
+        eprintln!("{}", tr!("warning", tr!("state-write-failed", e)));
+        return;
+    }
+
+    // The watchdog talks to the same Sway instance and keeps the same state
+    let Ok(binary) = env::current_exe() else {
+        eprintln!("{}", tr!("warning", tr!("no-executable")));
+        return;
+    };
+    let mut command = vec![
+        binary.display().to_string(),
+        "watchdog".to_string(),
+        deadline.to_string(),
+    ];
+    if let Some(socket) = sway::socket() {
+        command.extend(["--socket".to_string(), socket.display().to_string()]);
+    }
+    if let Some(seat) = state::seat() {
+        command.extend(["--seat".to_string(), seat.to_string()]);
+    }
+    if let Some(host) = &args.host {
+        command.extend(["--host".to_string(), host.clone()]);
+    }
+    if args.system {
+        command.push("--system".to_string());
+    }
+    if args.transient {
+        command.push("--transient".to_string());
+    }
+    if !effects::spawn_detached(&command) {
+        eprintln!("{}", tr!("warning", tr!("watchdog-failed")));
+        return;
+    }
+    println!("{}", tr!("run-confirm", seconds, previous));
+}
+
+// The detached half of --revert-after: offer a Keep button in a notification,
+// wait for the deadline, then switch back unless `confirm` cleared the pending
+// revert or a later switch replaced it
+fn watchdog(args: &Args, config_path: &Path) -> error::Result<()> {
+    let Some(deadline) = args.positional.first().and_then(|d| d.parse::<u64>().ok()) else {
+        return Err(Error::Usage(tr!("usage-watchdog")));
+    };
+    let Some(pending) = state::State::load().pending_revert else {
+        return Ok(());
+    };
+    thread::spawn(move || {
+        let body = tr!(
+            "revert-countdown",
+            pending.profile,
+            deadline.saturating_sub(state::now())
+        );
+        if effects::ask(&tr!("keep-settings-title"), &body, &[tr!("keep")]) == Some(0) {
+            state::confirm_pending(Some(deadline));
+        }
+    });
+    thread::sleep(Duration::from_secs(deadline.saturating_sub(state::now())));
+
+    let mut state = state::State::load();
+    let Some(revert) = state
+        .pending_revert
+        .take()
+        .filter(|r| r.deadline == deadline)
+    else {
+        return Ok(());
+    };
+    state.save()?;
+    // The config may have changed while waiting
+    if args.host.is_some() {
+        remote::fetch().map_err(Error::Failed)?;
+    }
+    let config = SwayConfig::load(config_path)?;
+    let display_configs = config.display_configs();
+    let Some(index) = config::find_config(&display_configs, &revert.profile) else {
+        return Err(Error::NoSuchProfile(revert.profile));
+    };
+    if args.transient {
+        switcher::switch_transient(&display_configs[index], "watchdog")?;
+    } else {
+        switcher::switch_persistent(&config, &display_configs, index, "watchdog")?;
+    }
+    effects::notify(&tr!("reverted-title"), &tr!("reverted", revert.profile));
+    Ok(())
+}
+
+// What started a switch, for the switch log: the subcommand (the picker when
+// there is none) and whether it ran in a terminal, which tells a manual run
+// apart from a keybinding, dialog or script
+fn trigger(args: &Args) -> String {
+    let command = args.command.as_deref().unwrap_or("picker");
+    let origin = if io::stdin().is_terminal() {
+        "terminal"
+    } else {
+        "no terminal"
+    };
+    format!("{} ({})", command, origin)
+}
+
+// The profiles to show in the order requested with --sort (by default the
+// order in the config file), favorites first and only those with --favorites
+fn listing_order(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<Vec<usize>> {
+    let key = match args.sort.as_deref() {
+        None => SortKey::File,
+        Some(value) => {
+            SortKey::parse(value).ok_or_else(|| Error::Usage(tr!("unknown-sort", value)))?
+        }
+    };
+    let connected = if key == SortKey::Match {
+        sway::get_outputs().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let mut order = listing::order(display_configs, key, &state::State::load(), &connected);
+    if args.favorites {
+        order.retain(|&i| display_configs[i].is_favorite());
+    }
+    if let Some(query) = &args.output {
+        let connected = sway::get_outputs().unwrap_or_default();
+        order.retain(|&i| listing::mentions(&display_configs[i], query, &connected));
+    }
+    Ok(order)
+}
+
+// Print the connected outputs (when Sway could be asked), the active
+// configuration and all available configurations in the given order. Profiles
+// that enable an output which is not connected are marked. Numbers always refer
+// to the position in the config file.
+fn print_listing(
+    display_configs: &[DisplayConfig],
+    order: &[usize],
+    connected: Option<&[sway::Output]>,
+    long: bool,
+) {
+    if let Some(connected) = connected {
+        println!("{}", tr!("connected-header"));
+        for output in connected {
+            let mode = if output.active {
+                output.current_mode().to_string()
+            } else {
+                tr!("inactive")
+            };
+            println!("  {} ({}) {}", output.name, output.identifier(), mode);
+        }
+        println!();
+    }
+
+    let enabled_config = display_configs.iter().position(|c| c.is_enabled());
 
     // Display current active configuration
     if let Some(enabled_index) = enabled_config {
         println!(
-            "Current active configuration: {}",
-            display_configs[enabled_index].description
+            "{}",
+            tr!("current-active", display_configs[enabled_index].description)
         );
     } else {
-        println!("No configuration is currently enabled.");
+        println!("{}", tr!("none-enabled"));
+    }
+
+    // With --long, every output's settings in columns aligned across profiles
+    let rows: Vec<Vec<[String; 5]>> = if long {
+        display_configs.iter().map(inspect::output_rows).collect()
+    } else {
+        Vec::new()
+    };
+    let header = inspect::LONG_COLUMNS.map(|id| tr!(id));
+    let mut widths = vec![0; header.len()];
+    for row in order
+        .iter()
+        .flat_map(|&i| rows.get(i).into_iter().flatten())
+        .chain([&header])
+    {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
     }
 
     // List all available configurations
-    println!("\nAvailable display configurations:");
-    for (i, config) in display_configs.iter().enumerate() {
-        println!("{}. {} [{}]", i + 1, config.description, config.status);
+    println!("\n{}", tr!("available-header"));
+    if long {
+        println!("    {}", inspect::format_row(&header, &widths));
     }
+    for &i in order {
+        let config = &display_configs[i];
+        let mut marker = if config.is_favorite() { " *" } else { "" }.to_string();
+        let (present, total) = listing::coverage(config, connected.unwrap_or_default());
+        if total > 0 {
+            let summary = match connected {
+                Some(_) => tr!("outputs-connected", present, total),
+                None => tr!("outputs-configured", total),
+            };
+            marker.push_str(&format!(" ({})", summary));
+        }
+        println!(
+            "{}. {} [{}]{}",
+            i + 1,
+            config.description,
+            config.status,
+            marker
+        );
+        if long {
+            if let Some(notes) = config.notes() {
+                println!("    {}", notes);
+            }
+            for row in &rows[i] {
+                println!("    {}", inspect::format_row(row, &widths));
+            }
+        }
+    }
+}
 
-    // Prompt user to select a config
-    let selected_index = get_user_selection(display_configs.len());
+// Print the recommended Sway config integration, leaving out what the config
+// already has except the always up-to-date bindings. With --insert the missing
+// parts are appended and an existing bindings block is refreshed in place.
+fn generate_sway(args: &Args, config_path: &Path) -> error::Result<()> {
+    // A file that cannot be read as text is not written over
+    let lines: Vec<String> = match fs::read_to_string(config_path) {
+        Ok(contents) => contents.lines().map(str::to_string).collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(Error::file("read", config_path, e)),
+    };
+    let display_configs = SwayConfig::load(config_path)
+        .map(|config| config.display_configs())
+        .unwrap_or_default();
+
+    if !args.insert {
+        let without_bindings: Vec<String> = lines
+            .iter()
+            .filter(|line| !line.contains(generate::BINDINGS_START))
+            .cloned()
+            .collect();
+        for line in generate::integration(&without_bindings, &display_configs) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut new_lines = lines.clone();
+    if lines
+        .iter()
+        .any(|line| line.contains(generate::BINDINGS_START))
+    {
+        new_lines = config::replace_block(
+            &lines,
+            generate::BINDINGS_START,
+            generate::BINDINGS_END,
+            &generate::bindings(&display_configs),
+        );
+    }
+    let missing = generate::integration(&new_lines, &display_configs);
+    if missing.len() > 1 {
+        new_lines.push(String::new());
+        new_lines.extend(missing);
+    }
+    if new_lines == lines {
+        println!("{}", tr!("already-set-up", config_path.display()));
+        return Ok(());
+    }
+    config::write_config(config_path, &new_lines)?;
+    println!("{}", tr!("updated", config_path.display()));
+    Ok(())
+}
+
+// Print the udev rule and its helper script, or write them into `dir` with
+// instructions for installing them
+fn generate_udev(dir: Option<PathBuf>) -> error::Result<()> {
+    let binary = env::current_exe()?;
+    let user = env::var("USER").map_err(|_| Error::Failed(tr!("no-user")))?;
+    let helper_path = Path::new("/usr/local/bin").join(generate::UDEV_HELPER_FILE);
+    let rule = generate::udev_rule(&helper_path);
+    let helper = generate::udev_helper(&user, &binary);
+
+    let Some(dir) = dir else {
+        println!("# /etc/udev/rules.d/{}", generate::UDEV_RULE_FILE);
+        print!("{}", rule);
+        println!("\n# {}", helper_path.display());
+        print!("{}", helper);
+        return Ok(());
+    };
+    let rule_path = dir.join(generate::UDEV_RULE_FILE);
+    let written_helper = dir.join(generate::UDEV_HELPER_FILE);
+    fs::write(&rule_path, rule)?;
+    fs::write(&written_helper, helper)?;
+    fs::set_permissions(&written_helper, fs::Permissions::from_mode(0o755))?;
+    println!(
+        "{}",
+        tr!(
+            "udev-written",
+            rule_path.display(),
+            written_helper.display()
+        )
+    );
+    println!(
+        "  sudo install -m 755 {} {}",
+        written_helper.display(),
+        helper_path.display()
+    );
+    println!(
+        "  sudo install -m 644 {} /etc/udev/rules.d/",
+        rule_path.display()
+    );
+    println!("  sudo udevadm control --reload");
+    Ok(())
+}
+
+// Entry point for the udev helper: no terminal, nothing to prompt. DRM events
+// come in bursts and each starts a run, so every run records itself, waits for
+// things to settle and only the last one acts: it switches to the profile that
+// best fits the connected outputs, or re-applies the Enabled one if that is it.
+fn from_udev(config_path: &Path, settle: Duration) -> error::Result<()> {
+    let marker = state::state_dir().join("udev");
+    fs::create_dir_all(state::state_dir())?;
+    let token = process::id().to_string();
+    fs::write(&marker, &token)?;
+    thread::sleep(settle * 2);
+    if fs::read_to_string(&marker).ok().as_deref() != Some(token.as_str()) {
+        return Ok(());
+    }
+
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let config = SwayConfig::load(config_path)?;
+    let display_configs = config.display_configs();
+    let Some(best) = matching::best_match(&display_configs, &connected) else {
+        return Err(Error::Failed(tr!("no-fit")));
+    };
+    if switcher::already_active(&display_configs[best], false) {
+        return Ok(());
+    }
+    if display_configs[best].is_enabled() {
+        return switcher::switch_transient(&display_configs[best], "udev");
+    }
+    guard_hand_edits(&config)?;
+    switcher::switch_persistent(&config, &display_configs, best, "udev")
+}
+
+// `capture <output>` switches to a generated profile that suits screen capture,
+// replacing the previous one; `capture stop` switches back to the profile that
+// was active before and removes any headless output it created
+fn capture(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let Some(output) = args.positional.first() else {
+        return Err(Error::Usage(tr!("usage-capture")));
+    };
+    let enabled = &display_configs[require_enabled(display_configs)?];
+    let capturing = enabled.description == generate::CAPTURE_PROFILE;
+    let restore = enabled
+        .metadata("Restore")
+        .filter(|_| capturing)
+        .and_then(|name| config::find_config(display_configs, name));
+
+    if output == "stop" {
+        let Some(index) = restore else {
+            return Err(Error::Failed(tr!(
+                "not-capturing",
+                generate::CAPTURE_PROFILE
+            )));
+        };
+        if enabled
+            .outputs
+            .iter()
+            .any(|line| line.ends_with("create_output"))
+        {
+            for headless in sway::get_outputs()
+                .unwrap_or_default()
+                .iter()
+                .filter(|o| o.name.starts_with("HEADLESS-"))
+            {
+                sway::run_command(&format!("output {} unplug", headless.name));
+            }
+        }
+        return switch_to(args, config, display_configs, index);
+    }
+
+    // Capturing again starts over from the profile captured before
+    let base = restore.map_or(enabled, |index| &display_configs[index]);
+    let connected = sway::get_outputs().unwrap_or_default();
+    let profile = generate::capture(base, output, &connected, args.headless);
+    let mut updated = display_configs.to_vec();
+    match updated
+        .iter()
+        .position(|c| c.description == generate::CAPTURE_PROFILE)
+    {
+        Some(index) => updated[index] = profile,
+        None => updated.push(profile),
+    }
+    config.save(&updated)?;
+    switch_to_saved(args, &config.path, generate::CAPTURE_PROFILE)
+}
+
+// Graphical output editors tried by `visual-edit`, in order
+#[cfg(feature = "dialogs")]
+const VISUAL_EDITORS: &[&str] = &["wdisplays", "nwg-displays"];
+
+// Let a graphical editor rearrange the outputs, then offer to save the result
+// into the Enabled profile
+#[cfg(feature = "dialogs")]
+fn visual_edit(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let index = require_enabled(display_configs)?;
+    let editors: Vec<&str> = match args.positional.first() {
+        Some(editor) => vec![editor.as_str()],
+        None => VISUAL_EDITORS.to_vec(),
+    };
+    let ran = editors.iter().find_map(|editor| {
+        println!("{}", tr!("waiting-for-editor", editor));
+        match process::Command::new(editor).status() {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            result => Some((editor, result)),
+        }
+    });
+    match ran {
+        Some((_, Ok(_))) => {}
+        Some((editor, Err(e))) => {
+            return Err(Error::Failed(tr!("run-failed", editor, e)));
+        }
+        None => {
+            return Err(Error::Failed(tr!(
+                "not-installed",
+                editors.join(&tr!("or"))
+            )));
+        }
+    }
+
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let profile = &display_configs[index];
+    let updated = generate::recapture(profile, &connected);
+    let arranged = DisplayConfig {
+        description: tr!("as-arranged"),
+        ..updated.clone()
+    };
+    let changes = diff::diff_profiles(profile, &arranged);
+    if changes.is_empty() {
+        println!("{}", tr!("arrangement-unchanged", profile.description));
+        return Ok(());
+    }
+    println!("--- {}", profile.description);
+    println!("+++ {}", arranged.description);
+    for line in changes {
+        println!("{}", line);
+    }
+    if !prompt::confirm(&tr!("save-changes", profile.description), true)? {
+        println!("{}", tr!("exiting"));
+        return Ok(());
+    }
+    let mut configs = display_configs.to_vec();
+    configs[index] = updated;
+    config.save(&configs)?;
+    println!("{}", tr!("arrangement-saved", profile.description));
+    Ok(())
+}
+
+// Where a running `present` keeps the output it presents on, followed by the
+// lines restoring the outputs as they were before
+fn presentation_path() -> PathBuf {
+    state::state_dir().join("present")
+}
+
+// Mirror the built-in panel to an external output transiently, then stay in the
+// foreground until the output is unplugged or `present --end` is run, and put
+// the outputs back as they were
+fn present(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    if args.end {
+        if !end_presentation() {
+            return Err(Error::Failed(tr!("no-presentation")));
+        }
+        println!("{}", tr!("presentation-ended"));
+        return Ok(());
+    }
+    if presentation_path().exists() {
+        return Err(Error::Failed(tr!("already-presenting")));
+    }
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let enabled = config::enabled_config(display_configs);
+    let Some(external) = generate::presentation_target(
+        &connected,
+        enabled,
+        args.positional.first().map(String::as_str),
+    ) else {
+        return Err(Error::Failed(tr!("no-present-target")));
+    };
+    let Some(profile) = generate::presentation(&connected, external) else {
+        return Err(Error::Failed(tr!("no-common-mode", external.name)));
+    };
+
+    let mut saved = vec![external.name.clone()];
+    saved.extend(generate::snapshot(&connected));
+    fs::create_dir_all(state::state_dir())?;
+    fs::write(presentation_path(), saved.join("\n") + "\n")?;
+    state::record_live(&profile.description, &profile.outputs);
+    if !sway::apply_outputs(&profile) {
+        end_presentation();
+        return Err(Error::Sway(tr!("apply-failed")));
+    }
+    println!("{}", tr!("presenting", external.name));
+
+    let mut subscription = sway::subscribe("[\"output\"]")?;
+    let events = subscription
+        .stdout
+        .take()
+        .ok_or_else(|| Error::Sway(tr!("subscription-no-stdout-output")))?;
+    for _ in BufReader::new(events).lines().map_while(Result::ok) {
+        // Ended from elsewhere
+        if !presentation_path().exists() {
+            break;
+        }
+        let unplugged = sway::get_outputs()
+            .is_some_and(|now| !now.iter().any(|o| o.identifier() == external.identifier()));
+        if unplugged {
+            end_presentation();
+            println!("{}", tr!("unplugged", external.name));
+            break;
+        }
+    }
+    let _ = subscription.kill();
+    Ok(())
+}
+
+// How long `test` keeps a profile without --seconds
+const TEST_SECONDS: u64 = 15;
+
+// Try a profile over IPC for a while, then put the outputs back as they were.
+// The config file and the switch history are left alone; Enter ends the trial
+// early.
+fn test(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let selected = &display_configs[require_profile(args, display_configs)?];
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let restore = DisplayConfig {
+        description: tr!("before-testing"),
+        outputs: generate::snapshot(&connected),
+        status: "Disabled".to_string(),
+        metadata: Vec::new(),
+    };
+    let seconds = args.seconds.unwrap_or(TEST_SECONDS);
+
+    state::record_live(&selected.description, &selected.outputs);
+    let applied = sway::apply_outputs(selected);
+    if applied {
+        println!("{}", tr!("testing", selected.description, seconds));
+        let (sender, lines) = mpsc::channel();
+        if io::stdin().is_terminal() {
+            thread::spawn(move || {
+                let _ = io::stdin().lines().next();
+                let _ = sender.send(());
+            });
+        }
+        let _ = lines.recv_timeout(Duration::from_secs(seconds));
+    }
+    state::record_live(&restore.description, &restore.outputs);
+    if !sway::apply_outputs(&restore) {
+        return Err(Error::Sway(tr!("apply-failed")));
+    }
+    if !applied {
+        return Err(Error::Sway(tr!("test-rejected", selected.description)));
+    }
+    println!("{}", tr!("test-over"));
+    Ok(())
+}
+
+// Restore the outputs saved by `present` and forget the presentation. Returns
+// whether one was running.
+fn end_presentation() -> bool {
+    let Ok(contents) = fs::read_to_string(presentation_path()) else {
+        return false;
+    };
+    let _ = fs::remove_file(presentation_path());
+    let restore = DisplayConfig {
+        description: tr!("before-presenting"),
+        outputs: contents.lines().skip(1).map(String::from).collect(),
+        status: "Disabled".to_string(),
+        metadata: Vec::new(),
+    };
+    state::record_live(&restore.description, &restore.outputs);
+    if !sway::apply_outputs(&restore) {
+        eprintln!("{}", tr!("apply-failed"));
+    }
+    true
+}
+
+// `power <output> on|off|toggle`: switch an output's display power over IPC,
+// leaving the profiles alone
+fn power(args: &Args) -> error::Result<()> {
+    let (Some(name), Some(state @ ("on" | "off" | "toggle"))) = (
+        args.positional.first(),
+        args.positional.get(1).map(String::as_str),
+    ) else {
+        return Err(Error::Usage(tr!("usage-power")));
+    };
+    let command = format!(
+        "output {} {} {}",
+        output::quote(name),
+        sway::power_keyword(),
+        state
+    );
+    if !sway::run_command(&command) {
+        return Err(Error::Sway(tr!("power-failed", name)));
+    }
+    Ok(())
+}
+
+// Turn every output off until the next keyboard or pointer input. swayidle
+// reports input: its resume command only runs after a timeout has fired, so
+// the key press that started `blank` does not wake the outputs again.
+fn blank() -> error::Result<()> {
+    let power = sway::power_keyword();
+    if !sway::run_command(&format!("output * {} off", power)) {
+        return Err(Error::Sway(tr!("blank-failed")));
+    }
+    let idle = process::Command::new("swayidle")
+        .args(["timeout", "1", "echo idle", "resume", "echo input"])
+        .stdout(process::Stdio::piped())
+        .spawn();
+    let mut idle = match idle {
+        Ok(child) => child,
+        Err(e) => {
+            sway::run_command(&format!("output * {} on", power));
+            return Err(Error::Failed(tr!("swayidle-run-failed", e)));
+        }
+    };
+    if let Some(stdout) = idle.stdout.take() {
+        let _ = BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .find(|line| line == "input");
+    }
+    let _ = idle.kill();
+    let _ = idle.wait();
+    sway::run_command(&format!("output * {} on", power));
+    Ok(())
+}
 
-    // Update display_configs: set selected to Enabled, others to Disabled
-    let mut updated_display_configs = display_configs.clone();
-    for (i, config) in updated_display_configs.iter_mut().enumerate() {
-        if i == selected_index {
-            config.status = "Enabled".to_string();
+// Lock the screen with the applied profile's lock screen, for a keybinding
+fn lock() -> error::Result<()> {
+    let command = effects::lock_command();
+    let status = process::Command::new(&command[0])
+        .args(&command[1..])
+        .status()
+        .map_err(|e| Error::Failed(tr!("swaylock-run-failed", e)))?;
+    if !status.success() {
+        return Err(Error::Failed(tr!("lock-failed")));
+    }
+    Ok(())
+}
+
+// Print each GPU with the connected outputs it drives
+fn list_gpus() {
+    let gpus = gpu::gpus();
+    if gpus.is_empty() {
+        println!("{}", tr!("no-gpus"));
+        return;
+    }
+    let connected = sway::get_outputs().unwrap_or_default();
+    for gpu in &gpus {
+        let kind = if gpu.boot {
+            tr!("gpu-built-in")
         } else {
-            config.status = "Disabled".to_string();
+            tr!("gpu-external")
+        };
+        println!("{} {} {} ({})", gpu.card, gpu.slot, gpu.id, kind);
+        for output in &connected {
+            if gpu::card_of(output).as_deref() == Some(gpu.card.as_str()) {
+                println!("  {} ({})", output.name, output.identifier());
+            }
         }
     }
+}
 
-    // Reconstruct the display section with updated configs
-    let mut new_display_section = Vec::new();
+// Print each connected output with the roles it plays
+fn detect(args: &Args) -> error::Result<()> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    if args.json {
+        println!("{}", roles::document(&connected));
+        return Ok(());
+    }
+    for output in &connected {
+        let names: Vec<&str> = roles::roles_of(output, &connected)
+            .into_iter()
+            .map(roles::Role::name)
+            .collect();
+        println!(
+            "{} ({}): {}",
+            output.name,
+            output.identifier(),
+            names.join(", ")
+        );
+    }
+    Ok(())
+}
 
-    for config in &updated_display_configs {
-        // Write the description line with updated status
-        new_display_section.push(format!(
-            "# Description = {}, Status = {}",
-            config.description, config.status
-        ));
+// Prepare for unplugging an external GPU: switch to the profile that best fits
+// the outputs of the built-in GPU alone, then turn off every output still
+// driven by an external one
+fn detach_gpu(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let gpus = gpu::gpus();
+    let external: Vec<String> = gpu::external_outputs(&gpus, &connected)
+        .iter()
+        .map(|output| output.name.clone())
+        .collect();
+    if external.is_empty() {
+        println!("{}", tr!("no-external-gpu"));
+        return Ok(());
+    }
 
-        // Write the output lines, commented or uncommented based on status
-        for output_line in &config.outputs {
-            let line_to_write = if config.status.eq_ignore_ascii_case("Enabled") {
-                output_line.clone() // Uncommented
-            } else {
-                // Ensure only single '#' and space
-                if output_line.starts_with("# ") {
-                    output_line.clone()
-                } else if output_line.starts_with('#') {
-                    format!("# {}", output_line.trim_start_matches('#').trim_start())
-                } else {
-                    format!("# {}", output_line)
-                }
-            };
-            new_display_section.push(line_to_write);
+    // Profiles that need an external GPU are out, as it is about to go
+    let remaining: Vec<sway::Output> = connected
+        .iter()
+        .filter(|output| !external.contains(&output.name))
+        .cloned()
+        .collect();
+    let builtin: Vec<&gpu::Gpu> = gpus.iter().filter(|gpu| gpu.boot).collect();
+    let best = matching::best_match_where(display_configs, &remaining, |config| {
+        config
+            .metadata("Gpu")
+            .is_none_or(|query| builtin.iter().any(|gpu| gpu.matches(query)))
+    });
+    match best {
+        Some(index) if !display_configs[index].is_enabled() => {
+            println!(
+                "{}",
+                tr!("switching-to", display_configs[index].description)
+            );
+            switcher::switch_persistent(config, display_configs, index, &trigger(args))?;
         }
+        Some(_) => {}
+        None => eprintln!("{}", tr!("warning", tr!("no-builtin-fit"))),
+    }
 
-        // No blank lines between configurations to prevent extra space
+    let failed: Vec<&str> = external
+        .iter()
+        .filter(|name| !sway::run_command(&format!("output {} disable", output::quote(name))))
+        .map(String::as_str)
+        .collect();
+    if !failed.is_empty() {
+        return Err(Error::Sway(tr!("disable-failed", failed.join(", "))));
     }
+    println!("{}", tr!("gpu-detached", external.join(", ")));
+    Ok(())
+}
 
-    // Prepare the new lines by replacing the old display section
-    let mut new_lines = Vec::new();
+// Print the profile that best fits the connected outputs without applying it.
+// With --verbose every profile's score is itemized. Exits with status 1 when
+// no profile fits.
+fn which(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let Some(connected) = sway::get_outputs() else {
+        return Err(Error::Sway(tr!("outputs-query-failed")));
+    };
+    let best = matching::best_match(display_configs, &connected);
 
-    // Add lines before the display section
-    new_lines.extend_from_slice(&lines[..=display_start]);
+    if args.verbose {
+        for (index, config) in display_configs.iter().enumerate() {
+            let breakdown = matching::breakdown(config, &connected);
+            let marker = if best == Some(index) {
+                tr!("best-marker")
+            } else {
+                String::new()
+            };
+            match breakdown.score() {
+                Some(score) => {
+                    let priority = match matching::rank(config, &connected) {
+                        Some((_, priority)) if priority != 0 => {
+                            tr!("priority-suffix", priority)
+                        }
+                        _ => String::new(),
+                    };
+                    println!(
+                        "{}. {}: {}{}{}",
+                        index + 1,
+                        config.description,
+                        score,
+                        priority,
+                        marker
+                    )
+                }
+                None if !breakdown.missing.is_empty() => println!(
+                    "{}",
+                    tr!(
+                        "which-missing",
+                        index + 1,
+                        config.description,
+                        breakdown.missing.join(", ")
+                    )
+                ),
+                None if breakdown.blackout => {
+                    println!("{}", tr!("which-blackout", index + 1, config.description))
+                }
+                None => println!(
+                    "{}",
+                    tr!(
+                        "which-unconfigured",
+                        index + 1,
+                        config.description,
+                        breakdown.unconfigured.join(", ")
+                    )
+                ),
+            }
+            for (reason, points) in &breakdown.items {
+                println!("    {:+} {}", points, reason);
+            }
+        }
+        // Say what decided between profiles that fit equally well
+        if let Some(best) = best {
+            let best_rank = matching::rank(&display_configs[best], &connected);
+            let tied: Vec<&DisplayConfig> = display_configs
+                .iter()
+                .filter(|config| {
+                    matching::score(config, &connected) == best_rank.map(|(score, _)| score)
+                })
+                .collect();
+            if tied.len() > 1 {
+                let by_priority = tied
+                    .iter()
+                    .any(|config| matching::rank(config, &connected) != best_rank);
+                let id = if by_priority {
+                    "tie-by-priority"
+                } else {
+                    "tie-by-order"
+                };
+                println!("{}", tr!(id, tied.len(), display_configs[best].description));
+            }
+        }
+        println!();
+    }
 
-    // Add the new display section
-    new_lines.extend(new_display_section);
+    match best {
+        Some(index) => {
+            println!("{}", display_configs[index].description);
+            Ok(())
+        }
+        None => Err(Error::Failed(tr!("no-fit"))),
+    }
+}
 
-    // Add lines after the display section
-    if display_end < lines.len() {
-        new_lines.extend_from_slice(&lines[display_end..]);
+// Print one field, such as `current.name` or `profiles.count`, as plain text.
+// Exits with status 1 when the field does not exist or has no value.
+fn get(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let Some(path) = args.positional.first() else {
+        return Err(Error::Usage(tr!("usage-get")));
+    };
+    // Only ask Sway when the field depends on the connected outputs
+    let root = path.split('.').next().unwrap_or_default();
+    let connected = if root == "best" || root == "connected" {
+        sway::get_outputs()
+    } else {
+        None
+    };
+    let document = query::document(display_configs, connected.as_deref(), &state::State::load());
+    match query::lookup(&document, path).and_then(|value| query::raw(&value)) {
+        Some(text) => {
+            if !text.is_empty() {
+                println!("{}", text);
+            }
+            Ok(())
+        }
+        None => Err(Error::Failed(tr!("no-value", path))),
     }
+}
 
-    // Write all lines to a temporary file
-    let temp_path = Path::new("/home/fribbit/.config/sway/config_temp");
-    let temp_file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(temp_path)
-        .expect("Failed to create temporary config file");
-    let mut writer = BufWriter::new(temp_file);
+// Non-interactively re-apply the Enabled profile, or the best match for the
+// connected outputs when none is enabled. Meant for `exec` at session start.
+fn restore(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let target = config::enabled_config(display_configs).or_else(|| {
+        let connected = sway::get_outputs()?;
+        matching::best_match(display_configs, &connected).map(|index| &display_configs[index])
+    });
+    let pending = target.map(|target| {
+        let previous = config::enabled_config(display_configs).map(|c| c.description.as_str());
+        audit::start(&trigger(args), &target.description, previous, false)
+    });
+    match target {
+        Some(target) if !target.is_enabled() => {
+            state::record_live(&target.description, &target.outputs)
+        }
+        _ => state::clear_live(),
+    }
+    let applied = target.is_some_and(sway::apply_outputs);
+    if let Some(pending) = pending {
+        pending.finish((!applied).then(|| tr!("output-line-rejected")));
+    }
+    match target {
+        Some(target) if applied => {
+            effects::apply(target);
+            Ok(())
+        }
+        Some(target) => Err(Error::Failed(tr!("restore-failed", target.description))),
+        None => Err(Error::Failed(tr!("no-match"))),
+    }
+}
 
-    for line in new_lines {
-        writeln!(writer, "{}", line)?;
+// Rewrite connector names in every profile to description-based output matching
+fn migrate_descriptions(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    let mut migrated = display_configs.to_vec();
+    let unresolved = migrate::to_descriptions(&mut migrated, &connected);
+    for connector in &unresolved {
+        eprintln!("{}", tr!("warning", tr!("not-migrated", connector)));
     }
 
-    // Rename the temporary file to replace the old configuration
-    fs::rename(temp_path, &config_path).expect("Failed to replace the original config file");
+    let new_lines = config.with_display_configs(&migrated);
+    let changes = diff::format_diff(&config.lines, &new_lines, 0);
+    if changes.is_empty() {
+        println!("{}", tr!("nothing-to-migrate"));
+        return Ok(());
+    }
+    for line in changes {
+        println!("{}", line);
+    }
+    config.save(&migrated)?;
+    println!("{}", tr!("migrated-descriptions"));
+    Ok(())
+}
 
-    // Reload Sway configuration
-    if process::Command::new("swaymsg")
-        .arg("reload")
-        .spawn()
-        .is_ok()
-    {
-        println!("Successfully reloaded Sway configuration.");
+// Record the connector and description of every connected output the
+// profiles name, leaving the lines themselves alone
+fn migrate_identities(config: &SwayConfig, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    let mut migrated = display_configs.to_vec();
+    let added: usize = migrated
+        .iter_mut()
+        .map(|profile| migrate::add_identities(profile, &connected))
+        .sum();
+    if added == 0 {
+        println!("{}", tr!("nothing-to-migrate"));
+        return Ok(());
+    }
+    let new_lines = config.with_display_configs(&migrated);
+    for line in diff::format_diff(&config.lines, &new_lines, 0) {
+        println!("{}", line);
+    }
+    config.save(&migrated)?;
+    let id = if added == 1 {
+        "identities-one"
     } else {
-        eprintln!("Failed to reload Sway configuration.");
+        "identities-many"
+    };
+    println!("{}", tr!(id, added));
+    Ok(())
+}
+
+// Move `output` lines found elsewhere in the config into a new profile
+fn migrate_adopt(config: &SwayConfig, display_configs: &[DisplayConfig]) -> error::Result<()> {
+    let unmanaged = migrate::unmanaged_outputs(config);
+    if unmanaged.is_empty() {
+        println!("{}", tr!("no-unmanaged"));
+        return Ok(());
+    }
+    println!("{}", tr!("unmanaged-header"));
+    for found in &unmanaged {
+        println!("  {}: {}", found.lines.start + 1, found.command);
     }
+    if !prompt::confirm(&tr!("move-into-profile"), true)? {
+        println!("{}", tr!("exiting"));
+        return Ok(());
+    }
+    let description = loop {
+        let name = prompt::ask(&tr!("profile-name"), "Adopted")?;
+        if !name.contains(',') && config::find_config(display_configs, &name).is_none() {
+            break name;
+        }
+        println!("{}", tr!("new-name-without-commas"));
+    };
+    let remove = !prompt::confirm(&tr!("keep-originals"), true)?;
 
+    // The adopted lines are what Sway runs today, so keep them in effect when
+    // no other profile is
+    let status = if config::enabled_config(display_configs).is_some() {
+        "Disabled"
+    } else {
+        "Enabled"
+    };
+    let profile = DisplayConfig {
+        description: description.clone(),
+        outputs: unmanaged.iter().map(|u| u.command.clone()).collect(),
+        status: status.to_string(),
+        metadata: Vec::new(),
+    };
+    let new_lines = migrate::adopt(config, display_configs, &unmanaged, profile, remove);
+    for line in diff::format_diff(&config.lines, &new_lines, 1) {
+        println!("{}", line);
+    }
+    config.write(&new_lines)?;
+    println!("{}", tr!("adopted", unmanaged.len(), description, status));
     Ok(())
 }
 
-// Parse the display section into DisplayConfig structs
-fn parse_configs<'a, I>(lines: I, regex: &Regex) -> Vec<DisplayConfig>
-where
-    I: IntoIterator<Item = &'a String>,
-{
-    let mut configs = Vec::new();
-    let mut current_config = None;
+// Turn what another output manager saved into profiles: new ones, or those of
+// the same name with their lines replaced, so the other tool can serve as an
+// editor and be re-imported at any time
+fn import(
+    args: &Args,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+) -> error::Result<()> {
+    let read = |path: &str| {
+        let expanded = config::expand_home(path);
+        fs::read_to_string(&expanded).map_err(|e| Error::file("read", expanded, e))
+    };
+    let source = args.positional.first().map(String::as_str);
+    let name = args.positional.get(1).cloned();
+    let imported = match source {
+        Some("nwg-displays") => {
+            let outputs = read(migrate::NWG_OUTPUTS)?;
+            let workspaces = read(migrate::NWG_WORKSPACES).unwrap_or_default();
+            let lines = migrate::from_nwg_displays(&outputs, &workspaces);
+            let description = name.unwrap_or_else(|| "nwg-displays".to_string());
+            import::Import {
+                profiles: vec![DisplayConfig {
+                    description,
+                    outputs: lines,
+                    status: "Disabled".to_string(),
+                    metadata: Vec::new(),
+                }],
+                skipped: Vec::new(),
+            }
+        }
+        Some("way-displays") => {
+            let text = read(import::WAY_DISPLAYS)?;
+            let description = name.unwrap_or_else(|| "way-displays".to_string());
+            let connected = sway::get_outputs().unwrap_or_default();
+            import::way_displays(&text, &description, &connected).map_err(Error::Failed)?
+        }
+        Some("json") => {
+            let text = match args.positional.get(1).map(String::as_str) {
+                Some("-") => io::read_to_string(io::stdin())?,
+                Some(path) => read(path)?,
+                None => return Err(Error::Usage(tr!("usage-import"))),
+            };
+            import::json(&text).map_err(Error::Failed)?
+        }
+        Some("shikane") => {
+            let text = read(import::SHIKANE)?;
+            let mut imported = import::shikane(&text).map_err(Error::Failed)?;
+            if let Some(name) = &name {
+                imported.profiles.retain(|p| p.description == *name);
+                if imported.profiles.is_empty() {
+                    return Err(Error::NoSuchProfile(name.to_string()));
+                }
+            }
+            imported
+        }
+        _ => {
+            return Err(Error::Usage(tr!("usage-import")));
+        }
+    };
+    for skipped in &imported.skipped {
+        eprintln!("{}", tr!("warning", tr!("not-imported", skipped)));
+    }
 
-    for line in lines {
-        if let Some(captures) = regex.captures(line) {
-            // Push the previous config if it exists
-            if let Some(config) = current_config.take() {
-                configs.push(config);
+    let mut updated = display_configs.to_vec();
+    let mut summary = Vec::new();
+    for profile in imported.profiles {
+        if profile.outputs.is_empty() {
+            eprintln!(
+                "{}",
+                tr!("warning", tr!("no-output-lines", profile.description))
+            );
+            continue;
+        }
+        if profile.description.contains(',') {
+            return Err(Error::Usage(tr!("name-has-comma")));
+        }
+        match config::find_config(&updated, &profile.description) {
+            Some(index) => {
+                updated[index].outputs = profile.outputs;
+                summary.push(tr!("import-updated", profile.description));
             }
-            // Start a new config
-            current_config = Some(DisplayConfig {
-                description: captures[1].trim().to_string(),
-                status: captures[2].trim().to_string(),
-                outputs: Vec::new(),
-            });
-        } else if let Some(config) = current_config.as_mut() {
-            // Remove any leading '#' and spaces
-            let trimmed_line = line.trim_start_matches('#').trim_start();
-            if !trimmed_line.is_empty() {
-                config.outputs.push(trimmed_line.to_string());
+            None => {
+                // Like adopted lines, keep a layout in effect when no other profile is
+                let status = if config::enabled_config(&updated).is_some() {
+                    "Disabled"
+                } else {
+                    "Enabled"
+                };
+                summary.push(tr!("import-added", profile.description, status));
+                updated.push(DisplayConfig {
+                    status: status.to_string(),
+                    ..profile
+                });
             }
         }
     }
+    if summary.is_empty() {
+        return Err(Error::Failed(tr!("nothing-to-import")));
+    }
+    let new_lines = config.with_display_configs(&updated);
+    for line in diff::format_diff(&config.lines, &new_lines, 1) {
+        println!("{}", line);
+    }
+    config.save(&updated)?;
+    for line in summary {
+        println!("{}", line);
+    }
 
-    // Push the last config if it exists
-    if let Some(config) = current_config {
-        configs.push(config);
+    // An include after the managed section would put the nwg-displays layout
+    // back over every switch
+    let still_included = source == Some("nwg-displays")
+        && config.lines[config.display_end..].iter().any(|line| {
+            let line = line.trim();
+            line.starts_with("include ")
+                && (line.contains("sway/outputs") || line.contains("sway/workspaces"))
+        });
+    if still_included {
+        eprintln!("{}", tr!("warning", tr!("nwg-still-included")));
     }
+    Ok(())
+}
 
-    configs
+// Warn about overlapping outputs and gaps the cursor cannot cross
+fn warn_layout(selected: &DisplayConfig) {
+    let connected = sway::get_outputs().unwrap_or_default();
+    for warning in layout::check(&layout::profile_rects(selected, &connected)) {
+        eprintln!("{}", tr!("warning", warning));
+    }
+}
+
+// The index of the Enabled profile, exiting if none is enabled
+fn require_enabled(display_configs: &[DisplayConfig]) -> error::Result<usize> {
+    display_configs
+        .iter()
+        .position(|c| c.is_enabled())
+        .ok_or_else(|| Error::Failed(tr!("no-enabled")))
 }
 
-// Prompt the user for their configuration choice
-fn get_user_selection(total_configs: usize) -> usize {
+// Resolve the profile named by the first operand, exiting if it is missing or unknown
+fn require_profile(args: &Args, display_configs: &[DisplayConfig]) -> error::Result<usize> {
+    let query = args
+        .positional
+        .first()
+        .ok_or_else(|| Error::Usage(tr!("profile-required")))?;
+    config::find_config(display_configs, query)
+        .ok_or_else(|| Error::NoSuchProfile(query.to_string()))
+}
+
+// Prompt the user for the Sway instance to manage
+fn get_socket_selection(total_sockets: usize) -> error::Result<usize> {
     loop {
-        println!("Enter the number of the configuration you want to activate, or 'q' to quit:");
+        println!("{}", tr!("select-socket"));
         let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read input");
+        // A read error ends the input like end of file does
+        let read = io::stdin().read_line(&mut input).unwrap_or(0);
         let trimmed = input.trim();
-        if trimmed.eq_ignore_ascii_case("q") {
-            println!("Exiting without making changes.");
-            std::process::exit(0);
+        if read == 0 || trimmed.eq_ignore_ascii_case("q") {
+            return Err(Error::Cancelled);
         }
         if let Ok(choice) = trimmed.parse::<usize>() {
-            if choice > 0 && choice <= total_configs {
-                return choice - 1;
+            if choice > 0 && choice <= total_sockets {
+                return Ok(choice - 1);
             }
         }
-        println!(
-            "Invalid selection. Please enter a number between 1 and {}, or 'q' to quit.",
-            total_configs
-        );
+        println!("{}", tr!("invalid-selection"));
     }
 }
 
+// Read the profile number to activate. Only the first answer has to come
+// within `timeout`, as later ones mean someone is there; None when it did not.
+#[cfg(feature = "interactive")]
+fn get_user_selection(listed: &[usize], timeout: Option<Duration>) -> error::Result<Option<usize>> {
+    let total_configs = listed.iter().max().map_or(0, |i| i + 1);
+    let (sender, lines) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines() {
+            // A read error ends the input like end of file does
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let mut timeout = timeout;
+    loop {
+        println!("{}", tr!("select-profile"));
+        let input = match timeout.take() {
+            Some(limit) => match lines.recv_timeout(limit) {
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                received => received.ok(),
+            },
+            None => lines.recv().ok(),
+        };
+        let trimmed = input.as_deref().map(str::trim);
+        // End of input counts as quitting
+        let Some(trimmed) = trimmed.filter(|t| !t.eq_ignore_ascii_case("q")) else {
+            return Err(Error::Cancelled);
+        };
+        if let Ok(choice) = trimmed.parse::<usize>() {
+            if choice > 0 && listed.contains(&(choice - 1)) {
+                return Ok(Some(choice - 1));
+            }
+        }
+        println!("{}", tr!("invalid-profile-selection", total_configs));
+    }
+}
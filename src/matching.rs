@@ -0,0 +1,540 @@
+// Scoring profiles against the currently connected outputs
+
+use crate::config::DisplayConfig;
+use crate::gpu;
+use crate::output::OutputLine;
+use crate::sway::{Mode, Output};
+use crate::tr;
+use std::sync::OnceLock;
+
+impl Output {
+    // Sway's description-based identifier: "Make Model Serial"
+    pub fn identifier(&self) -> String {
+        // Sway substitutes "Unknown" for fields the EDID does not provide
+        let field = |value: &str| {
+            if value.is_empty() {
+                "Unknown".to_string()
+            } else {
+                value.to_string()
+            }
+        };
+        format!(
+            "{} {} {}",
+            field(&self.make),
+            field(&self.model),
+            field(&self.serial)
+        )
+    }
+
+    // Whether the output is on and running a mode; a mode without a refresh
+    // rate matches any, otherwise rates within 1 Hz count as the same
+    pub fn shows_mode(&self, mode: &Mode) -> bool {
+        self.active
+            && mode.width == self.width
+            && mode.height == self.height
+            && (mode.refresh == 0 || (mode.refresh - self.refresh).abs() < 1000)
+    }
+
+    // Whether an output line's target refers to this output
+    pub fn matches(&self, target: &str) -> bool {
+        target == "*" || target == self.name || target == self.identifier()
+    }
+}
+
+// Whether connected outputs a profile does not configure are tolerated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    // The profile may leave connected outputs alone, at a penalty
+    Subset,
+    // Every connected output must be configured by the profile
+    Exact,
+}
+
+// How profiles with the same score are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    // The earlier profile in the config file wins
+    File,
+    // The higher `Priority = N` header value wins, then the file order; as
+    // profiles without the key count as 0, this is the file order until a
+    // profile asks for more
+    Priority,
+}
+
+// The rules used to score profiles against the connected outputs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    pub mode: MatchMode,
+    // Points for an enabled output that is connected, by how the profile
+    // refers to it. A description names one monitor where a connector takes
+    // whatever is plugged into it, so it counts for more by default.
+    pub description_weight: i32,
+    pub connector_weight: i32,
+    // Points taken off for each connected output the profile does not configure
+    pub unconfigured_penalty: i32,
+    pub tie_break: TieBreak,
+}
+
+impl Default for Policy {
+    fn default() -> Policy {
+        Policy {
+            mode: MatchMode::Subset,
+            description_weight: 3,
+            connector_weight: 2,
+            unconfigured_penalty: 1,
+            tie_break: TieBreak::Priority,
+        }
+    }
+}
+
+static POLICY: OnceLock<Policy> = OnceLock::new();
+
+// Score profiles with this policy from now on (see `settings`)
+pub fn set_policy(policy: Policy) {
+    let _ = POLICY.set(policy);
+}
+
+// The policy in effect, the default one unless set_policy was called
+pub fn policy() -> &'static Policy {
+    POLICY.get_or_init(Policy::default)
+}
+
+// How a profile's score came about: the points for each output, the enabled
+// outputs that are not connected and, with an exact match policy, the
+// connected outputs the profile leaves out. Either of the latter rules the
+// profile out.
+#[derive(Debug, Clone, Default)]
+pub struct Breakdown {
+    pub items: Vec<(String, i32)>,
+    pub missing: Vec<String>,
+    pub unconfigured: Vec<String>,
+    // The profile would turn off every connected output
+    pub blackout: bool,
+}
+
+impl Breakdown {
+    pub fn score(&self) -> Option<i32> {
+        (self.missing.is_empty() && self.unconfigured.is_empty() && !self.blackout)
+            .then(|| self.items.iter().map(|(_, points)| points).sum())
+    }
+}
+
+// Whether applying a profile would leave no connected output on: each output
+// ends up as the profile's lines for it say, or as it is now when no line
+// applies to it. With nothing connected there is nothing to black out.
+pub fn leaves_all_off(config: &DisplayConfig, connected: &[Output]) -> bool {
+    let resolved = config.resolved(connected);
+    !connected.is_empty()
+        && connected.iter().all(
+            |output| match resolved.settings_for(&output.name, connected) {
+                Some(settings) => settings.is_disabled(),
+                None => !output.active,
+            },
+        )
+}
+
+// Score how well a profile fits the connected outputs; None when it enables an
+// output that is not connected
+pub fn score(config: &DisplayConfig, connected: &[Output]) -> Option<i32> {
+    breakdown(config, connected).score()
+}
+
+// The itemized score of a profile against the connected outputs
+pub fn breakdown(config: &DisplayConfig, connected: &[Output]) -> Breakdown {
+    let policy = policy();
+    let resolved = config.resolved(connected);
+    let all_lines: Vec<OutputLine> = resolved
+        .outputs
+        .iter()
+        .filter_map(|line| OutputLine::parse(line))
+        .collect();
+    let lines: Vec<&OutputLine> = all_lines
+        .iter()
+        .filter(|line| !line.is_wildcard())
+        .collect();
+
+    let mut breakdown = Breakdown::default();
+    for line in &lines {
+        let present = connected.iter().find(|output| output.matches(&line.target));
+        // A later `output *` line can still turn the output off
+        let disabled = resolved
+            .settings_for(&line.target, connected)
+            .is_some_and(|settings| settings.is_disabled());
+        match (present, disabled) {
+            (Some(output), false) if output.name == line.target => breakdown
+                .items
+                .push((tr!("match-connected", line.target), policy.connector_weight)),
+            (Some(_), false) => breakdown.items.push((
+                tr!("match-by-description", line.target),
+                policy.description_weight,
+            )),
+            (None, false) => breakdown.missing.push(line.target.clone()),
+            _ => {}
+        }
+    }
+
+    breakdown.blackout = leaves_all_off(config, connected);
+
+    // A profile scoped with `Gpu =` needs that GPU to be present
+    if let Some(query) = config.metadata("Gpu") {
+        if !gpu::gpus().iter().any(|gpu| gpu.matches(query)) {
+            breakdown.missing.push(tr!("match-gpu", query));
+        }
+    }
+
+    // Connected outputs the profile does not mention count against it; an
+    // `output *` line configures them all
+    for output in connected {
+        if all_lines.iter().any(|line| output.matches(&line.target)) {
+            continue;
+        }
+        match policy.mode {
+            MatchMode::Exact => breakdown.unconfigured.push(output.name.clone()),
+            MatchMode::Subset => breakdown.items.push((
+                tr!("match-unconfigured", output.name),
+                -policy.unconfigured_penalty,
+            )),
+        }
+    }
+    breakdown
+}
+
+// A profile's `Priority = N` header value, 0 when missing or not a whole number
+pub fn priority(config: &DisplayConfig) -> i64 {
+    config
+        .metadata("Priority")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+// The key profiles are ranked by, higher is better: the score, then the
+// priority when the policy breaks ties by it. None when the profile does not fit.
+pub fn rank(config: &DisplayConfig, connected: &[Output]) -> Option<(i32, i64)> {
+    let score = score(config, connected)?;
+    let priority = match policy().tie_break {
+        TieBreak::Priority => priority(config),
+        TieBreak::File => 0,
+    };
+    Some((score, priority))
+}
+
+// Index of the best-ranked profile; earlier profiles win remaining ties
+pub fn best_match(configs: &[DisplayConfig], connected: &[Output]) -> Option<usize> {
+    best_match_where(configs, connected, |_| true)
+}
+
+// Like best_match, but only among the profiles `eligible` accepts
+pub fn best_match_where(
+    configs: &[DisplayConfig],
+    connected: &[Output],
+    eligible: impl Fn(&DisplayConfig) -> bool,
+) -> Option<usize> {
+    let mut best: Option<(usize, (i32, i64))> = None;
+    for (index, config) in configs.iter().enumerate() {
+        if !eligible(config) {
+            continue;
+        }
+        if let Some(rank) = rank(config, connected) {
+            if best.is_none_or(|(_, best_rank)| rank > best_rank) {
+                best = Some((index, rank));
+            }
+        }
+    }
+    best.map(|(index, _)| index)
+}
+
+// Every profile sharing the best rank, in file order. More than one means only
+// the file order decides between them.
+pub fn best_matches(configs: &[DisplayConfig], connected: &[Output]) -> Vec<usize> {
+    let ranked: Vec<(usize, (i32, i64))> = configs
+        .iter()
+        .enumerate()
+        .filter_map(|(index, config)| Some((index, rank(config, connected)?)))
+        .collect();
+    let Some(best) = ranked.iter().map(|(_, rank)| *rank).max() else {
+        return Vec::new();
+    };
+    ranked
+        .into_iter()
+        .filter(|(_, rank)| *rank == best)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+// Connected outputs that no profile mentions by name or description
+pub fn unknown_outputs<'a>(configs: &[DisplayConfig], connected: &'a [Output]) -> Vec<&'a Output> {
+    let targets: Vec<String> = configs
+        .iter()
+        .flat_map(|config| config.outputs.iter())
+        .filter_map(|line| OutputLine::parse(line))
+        .filter(|line| !line.is_wildcard())
+        .map(|line| line.target)
+        .collect();
+    connected
+        .iter()
+        .filter(|output| !targets.iter().any(|target| output.matches(target)))
+        .collect()
+}
+
+// Whether the connected outputs already look the way a profile would leave
+// them: everything it turns off is off, and everything it turns on is on with
+// the mode, position, scale and transform it asks for. Outputs it does not
+// mention, and settings it leaves out, are not compared. Sway applies later
+// lines over earlier ones, so the last line setting a value wins.
+pub fn in_effect(config: &DisplayConfig, connected: &[Output]) -> bool {
+    let lines: Vec<OutputLine> = config
+        .resolved(connected)
+        .outputs
+        .iter()
+        .filter_map(|line| OutputLine::parse(line))
+        .collect();
+    connected.iter().all(|output| {
+        let applicable: Vec<&OutputLine> = lines
+            .iter()
+            .filter(|line| output.matches(&line.target))
+            .collect();
+        let last = |value: &dyn Fn(&OutputLine) -> bool| {
+            applicable.iter().rev().find(|line| value(line)).copied()
+        };
+        if last(&|line| line.is_disabled() || switches_on(line))
+            .is_some_and(OutputLine::is_disabled)
+        {
+            return !output.active;
+        }
+        if applicable.is_empty() {
+            return true;
+        }
+        let mode_matches = last(&|line| line.mode().is_some())
+            .and_then(OutputLine::mode)
+            .is_none_or(|mode| output.shows_mode(&mode));
+        let position_matches = last(&|line| line.position().is_some())
+            .and_then(OutputLine::position)
+            .is_none_or(|position| position == (output.x, output.y));
+        let scale_matches = last(&|line| line.scale().is_some())
+            .and_then(OutputLine::scale)
+            .is_none_or(|scale| (scale - output.scale).abs() < 0.001);
+        let transform_matches = last(&|line| line.transform().is_some())
+            .and_then(OutputLine::transform)
+            .is_none_or(|transform| transform == output.transform);
+        output.active && mode_matches && position_matches && scale_matches && transform_matches
+    })
+}
+
+// Whether a line explicitly turns its output on
+fn switches_on(line: &OutputLine) -> bool {
+    line.attributes.iter().any(|attribute| {
+        attribute.key == "enable"
+            || (attribute.canonical_key() == "power"
+                && attribute.values.first().is_some_and(|v| v == "on"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(name: &str, make: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            make: make.to_string(),
+            model: "Model".to_string(),
+            serial: "1".to_string(),
+            active: true,
+            width: 1920,
+            height: 1080,
+            refresh: 60000,
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            transform: "normal".to_string(),
+            modes: Vec::new(),
+            physical_width: 0,
+            physical_height: 0,
+        }
+    }
+
+    fn profile(description: &str, lines: &[&str]) -> DisplayConfig {
+        DisplayConfig {
+            description: description.to_string(),
+            outputs: lines.iter().map(|line| line.to_string()).collect(),
+            status: "Disabled".to_string(),
+            metadata: Vec::new(),
+        }
+    }
+
+    fn desk() -> Vec<Output> {
+        vec![connected("eDP-1", "BOE"), connected("DP-1", "Dell")]
+    }
+
+    #[test]
+    fn scores_each_connected_output() {
+        let config = profile(
+            "Desk",
+            &["output eDP-1 disable", "output \"Dell Model 1\" enable"],
+        );
+        let breakdown = breakdown(&config, &desk());
+        assert_eq!(breakdown.items.len(), 1);
+        assert_eq!(breakdown.score(), Some(3));
+    }
+
+    #[test]
+    fn unconfigured_outputs_cost_points() {
+        let config = profile("Laptop", &["output eDP-1 enable"]);
+        let breakdown = breakdown(&config, &desk());
+        assert!(breakdown.unconfigured.is_empty());
+        assert_eq!(breakdown.score(), Some(2 - 1));
+    }
+
+    #[test]
+    fn a_wildcard_configures_every_output() {
+        let config = profile("Laptop", &["output eDP-1 enable", "output * scale 1"]);
+        assert_eq!(score(&config, &desk()), Some(2));
+    }
+
+    #[test]
+    fn missing_outputs_rule_a_profile_out() {
+        let config = profile(
+            "Projector",
+            &["output eDP-1 enable", "output HDMI-A-1 enable"],
+        );
+        let breakdown = breakdown(&config, &desk());
+        assert_eq!(breakdown.missing, ["HDMI-A-1"]);
+        assert_eq!(breakdown.score(), None);
+    }
+
+    #[test]
+    fn disabled_outputs_need_not_be_connected() {
+        let config = profile("Desk", &["output eDP-1 enable", "output HDMI-A-1 disable"]);
+        assert!(breakdown(&config, &desk()).missing.is_empty());
+    }
+
+    #[test]
+    fn turning_everything_off_rules_a_profile_out() {
+        let config = profile("Dark", &["output eDP-1 disable", "output DP-1 disable"]);
+        let breakdown = breakdown(&config, &desk());
+        assert!(breakdown.blackout);
+        assert_eq!(breakdown.score(), None);
+    }
+
+    #[test]
+    fn a_description_outweighs_a_connector() {
+        let configs = [
+            profile(
+                "By connector",
+                &["output eDP-1 disable", "output DP-1 enable"],
+            ),
+            profile(
+                "By description",
+                &["output eDP-1 disable", "output \"Dell Model 1\" enable"],
+            ),
+        ];
+        assert_eq!(rank(&configs[0], &desk()), Some((2, 0)));
+        assert_eq!(rank(&configs[1], &desk()), Some((3, 0)));
+        assert_eq!(best_match(&configs, &desk()), Some(1));
+    }
+
+    #[test]
+    fn profiles_that_do_not_fit_have_no_rank() {
+        let config = profile("Projector", &["output HDMI-A-1 enable"]);
+        assert_eq!(rank(&config, &desk()), None);
+        assert_eq!(best_match(&[config], &desk()), None);
+    }
+
+    fn with_priority(mut config: DisplayConfig, priority: &str) -> DisplayConfig {
+        config
+            .metadata
+            .push(("Priority".to_string(), priority.to_string()));
+        config
+    }
+
+    #[test]
+    fn priority_is_a_whole_number() {
+        let laptop = profile("Laptop", &["output eDP-1 enable"]);
+        assert_eq!(priority(&laptop), 0);
+        assert_eq!(priority(&with_priority(laptop.clone(), "-3")), -3);
+        assert_eq!(priority(&with_priority(laptop, "high")), 0);
+    }
+
+    #[test]
+    fn the_higher_priority_breaks_a_tie() {
+        let configs = [
+            profile("Mirror", &["output eDP-1 enable", "output DP-1 enable"]),
+            with_priority(
+                profile(
+                    "Extend",
+                    &["output eDP-1 enable", "output DP-1 enable pos 1920 0"],
+                ),
+                "5",
+            ),
+        ];
+        assert_eq!(best_match(&configs, &desk()), Some(1));
+        assert_eq!(best_matches(&configs, &desk()), [1]);
+    }
+
+    #[test]
+    fn the_earlier_profile_breaks_a_remaining_tie() {
+        let configs = [
+            profile("Projector", &["output HDMI-A-1 enable"]),
+            profile("Mirror", &["output eDP-1 enable", "output DP-1 enable"]),
+            profile(
+                "Extend",
+                &["output eDP-1 enable", "output DP-1 enable pos 1920 0"],
+            ),
+        ];
+        assert_eq!(best_match(&configs, &desk()), Some(1));
+        assert_eq!(best_matches(&configs, &desk()), [1, 2]);
+    }
+
+    #[test]
+    fn score_comes_before_priority() {
+        let configs = [
+            with_priority(profile("Laptop", &["output eDP-1 enable"]), "9"),
+            profile("Desk", &["output eDP-1 enable", "output DP-1 enable"]),
+        ];
+        assert_eq!(best_match(&configs, &desk()), Some(1));
+        assert!(best_matches(&[], &desk()).is_empty());
+    }
+
+    #[test]
+    fn in_effect_when_the_outputs_show_the_settings() {
+        let mut outputs = desk();
+        outputs[0].active = false;
+        outputs[1].x = 1920;
+        let config = profile(
+            "Desk",
+            &[
+                "output eDP-1 disable",
+                "output DP-1 enable mode 1920x1080@60Hz pos 1920 0",
+            ],
+        );
+        assert!(in_effect(&config, &outputs));
+        outputs[1].scale = 2.0;
+        assert!(in_effect(&config, &outputs));
+        outputs[1].x = 0;
+        assert!(!in_effect(&config, &outputs));
+    }
+
+    #[test]
+    fn not_in_effect_while_an_output_to_turn_off_is_on() {
+        let config = profile("Desk", &["output eDP-1 disable", "output DP-1 enable"]);
+        assert!(!in_effect(&config, &desk()));
+    }
+
+    #[test]
+    fn the_last_line_for_an_output_wins() {
+        let mut outputs = desk();
+        outputs[1].active = false;
+        let config = profile(
+            "Laptop",
+            &[
+                "output DP-1 enable",
+                "output eDP-1 scale 1",
+                "output * disable",
+                "output eDP-1 enable",
+            ],
+        );
+        assert!(in_effect(&config, &outputs));
+        outputs[1].active = true;
+        assert!(!in_effect(&config, &outputs));
+    }
+}
@@ -0,0 +1,380 @@
+// One-off rewrites of the managed section
+
+use crate::config::{DisplayConfig, SwayConfig};
+use crate::output::{self, OutputLine};
+use crate::sway::Output;
+
+// Rewrite connector-name targets (DP-1) to sway's stable "Make Model Serial"
+// form, recording both in `?identity` lines. Returns the connectors that could
+// not be resolved because nothing with that name is currently connected.
+pub fn to_descriptions(configs: &mut [DisplayConfig], connected: &[Output]) -> Vec<String> {
+    let mut unresolved = Vec::new();
+    for config in configs.iter_mut() {
+        // Keep the connector on record, for when the description stops matching
+        add_identities(config, connected);
+        for line in config.outputs.iter_mut() {
+            let Some(parsed) = OutputLine::parse(line) else {
+                continue;
+            };
+            if parsed.is_wildcard() {
+                continue;
+            }
+            match connected.iter().find(|o| o.name == parsed.target) {
+                Some(output) => {
+                    if let Some(rewritten) = output::replace_target(line, &output.identifier()) {
+                        *line = rewritten;
+                    }
+                }
+                None => {
+                    let already_description = connected.iter().any(|o| o.matches(&parsed.target))
+                        || parsed.target.contains(' ');
+                    if !already_description && !unresolved.contains(&parsed.target) {
+                        unresolved.push(parsed.target);
+                    }
+                }
+            }
+        }
+    }
+    unresolved
+}
+
+// Record an `?identity` line for each connected output a profile's lines name,
+// by connector or by description, that has none yet, right before the first
+// line for that output. Returns how many were added.
+pub fn add_identities(config: &mut DisplayConfig, connected: &[Output]) -> usize {
+    let mut added = 0;
+    for output in connected {
+        let identifier = output.identifier();
+        let recorded = config.identities().iter().any(|(connector, description)| {
+            *connector == output.name || *description == identifier
+        });
+        if recorded {
+            continue;
+        }
+        let first = config.outputs.iter().position(|line| {
+            OutputLine::parse(line)
+                .is_some_and(|parsed| !parsed.is_wildcard() && output.matches(&parsed.target))
+        });
+        if let Some(index) = first {
+            config
+                .outputs
+                .insert(index, output::identity_line(&output.name, &identifier));
+            added += 1;
+        }
+    }
+    added
+}
+
+// An `output` command found outside the managed section
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unmanaged {
+    // The config lines it spans (more than one for `output NAME { ... }` blocks)
+    pub lines: std::ops::Range<usize>,
+    // The command as a single line, with block contents joined
+    pub command: String,
+}
+
+// Find the output commands outside the managed section
+pub fn unmanaged_outputs(config: &SwayConfig) -> Vec<Unmanaged> {
+    let managed = config.display_start..=config.display_end;
+    output_commands(&config.lines, |index| managed.contains(&index))
+}
+
+// The output commands in `lines`, leaving out the lines `skip` picks
+fn output_commands(lines: &[String], skip: impl Fn(usize) -> bool) -> Vec<Unmanaged> {
+    let mut found = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if skip(index) {
+            index += 1;
+            continue;
+        }
+        let line = lines[index].trim();
+        if !line.starts_with("output ") {
+            index += 1;
+            continue;
+        }
+        match line.strip_suffix('{') {
+            // `output NAME {` followed by one subcommand per line until `}`
+            Some(head) => {
+                let mut command = head.trim_end().to_string();
+                let mut end = index + 1;
+                while end < lines.len() && lines[end].trim() != "}" {
+                    let inner = lines[end].trim();
+                    if !inner.is_empty() && !inner.starts_with('#') {
+                        command.push(' ');
+                        command.push_str(inner);
+                    }
+                    end += 1;
+                }
+                found.push(Unmanaged {
+                    lines: index..(end + 1).min(lines.len()),
+                    command,
+                });
+                index = end + 1;
+            }
+            None => {
+                found.push(Unmanaged {
+                    lines: index..index + 1,
+                    command: line.to_string(),
+                });
+                index += 1;
+            }
+        }
+    }
+    found
+}
+
+// The config with the unmanaged commands moved into the managed section as a
+// new profile. The originals are removed, or commented out when `remove` is false.
+pub fn adopt(
+    config: &SwayConfig,
+    configs: &[DisplayConfig],
+    unmanaged: &[Unmanaged],
+    profile: DisplayConfig,
+    remove: bool,
+) -> Vec<String> {
+    let mut updated = configs.to_vec();
+    updated.push(profile);
+    let section = config.managed_section(&updated);
+
+    let mut new_lines = Vec::new();
+    for (index, line) in config.lines.iter().enumerate() {
+        if index > config.display_start && index < config.display_end {
+            continue;
+        }
+        if unmanaged.iter().any(|u| u.lines.contains(&index)) {
+            if !remove {
+                new_lines.push(format!("# {}", line));
+            }
+            continue;
+        }
+        new_lines.push(line.clone());
+        if index == config.display_start {
+            new_lines.extend(section.iter().cloned());
+        }
+    }
+    new_lines
+}
+
+// Where nwg-displays saves its layout: the output commands, and the workspace
+// assignments from its Workspaces tab
+pub const NWG_OUTPUTS: &str = "~/.config/sway/outputs";
+pub const NWG_WORKSPACES: &str = "~/.config/sway/workspaces";
+
+// The lines of a profile holding what nwg-displays saved: its output commands
+// (`output NAME { ... }` blocks joined into one line), then its
+// `workspace N output NAME` assignments
+pub fn from_nwg_displays(outputs: &str, workspaces: &str) -> Vec<String> {
+    let output_lines: Vec<String> = outputs.lines().map(str::to_string).collect();
+    let mut lines: Vec<String> = output_commands(&output_lines, |_| false)
+        .into_iter()
+        // nwg-displays pads some values, e.g. `mode  1920x1080@60.000Hz`
+        .map(|found| {
+            found
+                .command
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    lines.extend(
+        workspaces
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("workspace "))
+            .map(str::to_string),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(name: &str, model: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            make: "Make".to_string(),
+            model: model.to_string(),
+            serial: String::new(),
+            active: true,
+            width: 1920,
+            height: 1080,
+            refresh: 60000,
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            transform: "normal".to_string(),
+            modes: Vec::new(),
+            physical_width: 0,
+            physical_height: 0,
+        }
+    }
+
+    fn profile(lines: &[&str]) -> DisplayConfig {
+        DisplayConfig {
+            description: "Desk".to_string(),
+            outputs: lines.iter().map(|line| line.to_string()).collect(),
+            status: "Disabled".to_string(),
+            metadata: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn connectors_become_descriptions() {
+        let mut configs = [profile(&[
+            "output * bg #000000 solid_color",
+            "output DP-1 enable pos 0 0",
+            "workspace 1 output DP-1",
+            "output DP-1 scale 2",
+        ])];
+        let unresolved = to_descriptions(&mut configs, &[connected("DP-1", "Monitor")]);
+        assert!(unresolved.is_empty());
+        assert_eq!(
+            configs[0].outputs,
+            [
+                "output * bg #000000 solid_color",
+                "?identity DP-1 \"Make Monitor Unknown\"",
+                "output \"Make Monitor Unknown\" enable pos 0 0",
+                "workspace 1 output DP-1",
+                "output \"Make Monitor Unknown\" scale 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_connectors_that_are_not_connected() {
+        let mut configs = [
+            profile(&[
+                "output HDMI-A-1 enable",
+                "output \"Other Monitor 1\" enable",
+            ]),
+            profile(&["output HDMI-A-1 disable"]),
+        ];
+        let unresolved = to_descriptions(&mut configs, &[connected("DP-1", "Monitor")]);
+        assert_eq!(unresolved, ["HDMI-A-1"]);
+        assert_eq!(configs[1].outputs, ["output HDMI-A-1 disable"]);
+    }
+
+    #[test]
+    fn identities_go_before_the_first_line_for_an_output() {
+        let mut config = profile(&[
+            "output * scale 1",
+            "output eDP-1 disable",
+            "output \"Make Monitor Unknown\" enable",
+            "output eDP-1 scale 2",
+        ]);
+        let outputs = [
+            connected("eDP-1", "Panel"),
+            connected("DP-3", "Monitor"),
+            connected("HDMI-A-1", "TV"),
+        ];
+        assert_eq!(add_identities(&mut config, &outputs), 2);
+        assert_eq!(
+            config.outputs,
+            [
+                "output * scale 1",
+                "?identity eDP-1 \"Make Panel Unknown\"",
+                "output eDP-1 disable",
+                "?identity DP-3 \"Make Monitor Unknown\"",
+                "output \"Make Monitor Unknown\" enable",
+                "output eDP-1 scale 2",
+            ]
+        );
+        assert_eq!(add_identities(&mut config, &outputs), 0);
+    }
+
+    fn sway_config() -> SwayConfig {
+        let lines = [
+            "output eDP-1 scale 2",
+            "output DP-1 {",
+            "    mode 2560x1440",
+            "    # left of the laptop",
+            "    pos 0 0",
+            "}",
+            "# Display Start",
+            "# Description = Laptop, Status = Enabled",
+            "output eDP-1 enable",
+            "# Display End",
+            "output * bg #000000 solid_color",
+        ];
+        SwayConfig {
+            path: "config".into(),
+            lines: lines.map(String::from).to_vec(),
+            display_start: 6,
+            display_end: 9,
+            lossy: false,
+        }
+    }
+
+    #[test]
+    fn finds_output_commands_outside_the_section() {
+        let commands: Vec<(std::ops::Range<usize>, String)> = unmanaged_outputs(&sway_config())
+            .into_iter()
+            .map(|u| (u.lines, u.command))
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                (0..1, "output eDP-1 scale 2".to_string()),
+                (1..6, "output DP-1 mode 2560x1440 pos 0 0".to_string()),
+                (10..11, "output * bg #000000 solid_color".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn adopting_moves_the_commands_into_a_profile() {
+        let config = sway_config();
+        let unmanaged = &unmanaged_outputs(&config)[..2];
+        let adopted = profile(&["output eDP-1 scale 2", "output DP-1 mode 2560x1440 pos 0 0"]);
+        let configs = [DisplayConfig {
+            description: "Laptop".to_string(),
+            outputs: vec!["output eDP-1 enable".to_string()],
+            status: "Enabled".to_string(),
+            metadata: Vec::new(),
+        }];
+        let removed = adopt(&config, &configs, unmanaged, adopted.clone(), true);
+        assert_eq!(removed[0], "# Display Start");
+        assert!(removed.contains(&"# output DP-1 mode 2560x1440 pos 0 0".to_string()));
+        assert!(!removed.contains(&"output eDP-1 scale 2".to_string()));
+        assert_eq!(
+            removed[removed.len() - 2..],
+            ["# Display End", "output * bg #000000 solid_color"]
+        );
+
+        let commented = adopt(&config, &configs, unmanaged, adopted, false);
+        assert_eq!(
+            commented[..3],
+            [
+                "# output eDP-1 scale 2",
+                "# output DP-1 {",
+                "#     mode 2560x1440"
+            ]
+        );
+        assert_eq!(commented.len(), removed.len() + 6);
+    }
+
+    #[test]
+    fn reads_the_layout_nwg_displays_saved() {
+        let outputs = "# Generated by nwg-displays on 2026-01-01
+output \"eDP-1\" {
+    mode  2256x1504@59.999Hz
+    pos 0 0
+    scale 1.5
+}
+output DP-1 disable
+";
+        let workspaces = "workspace 1 output eDP-1\n  workspace 2 output DP-1\nbindsym x nop\n";
+        assert_eq!(
+            from_nwg_displays(outputs, workspaces),
+            [
+                "output \"eDP-1\" mode 2256x1504@59.999Hz pos 0 0 scale 1.5",
+                "output DP-1 disable",
+                "workspace 1 output eDP-1",
+                "workspace 2 output DP-1",
+            ]
+        );
+    }
+}
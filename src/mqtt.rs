@@ -0,0 +1,136 @@
+// MQTT integration through the mosquitto command line clients: the active
+// profile is published (retained) to `<topic>/state`, and the --watch daemon
+// switches to any profile name sent to `<topic>/set`
+
+use crate::config::{self, SwayConfig};
+use crate::effects;
+use crate::switcher;
+use crate::tr;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{self, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+
+// Broker connection and topic prefix, from the `mqtt_*` settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Broker {
+    pub host: String,
+    pub port: u16,
+    pub topic: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Broker {
+    pub fn new(host: &str) -> Broker {
+        Broker {
+            host: host.to_string(),
+            port: 1883,
+            topic: "sway-display-switcher".to_string(),
+            username: None,
+            password: None,
+        }
+    }
+
+    // Connection arguments shared by mosquitto_pub and mosquitto_sub
+    fn connection_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "-h".to_string(),
+            self.host.clone(),
+            "-p".to_string(),
+            self.port.to_string(),
+        ];
+        if let Some(username) = &self.username {
+            args.extend(["-u".to_string(), username.clone()]);
+        }
+        if let Some(password) = &self.password {
+            args.extend(["-P".to_string(), password.clone()]);
+        }
+        args
+    }
+}
+
+static BROKER: OnceLock<Option<Broker>> = OnceLock::new();
+
+// Use this broker from now on; None turns the integration off
+pub fn set_broker(broker: Option<Broker>) {
+    let _ = BROKER.set(broker);
+}
+
+fn broker() -> Option<&'static Broker> {
+    BROKER.get().and_then(Option::as_ref)
+}
+
+// Publish the active profile as the retained state, in the background
+pub fn publish_state(profile: &str) {
+    let Some(broker) = broker() else {
+        return;
+    };
+    let mut command = vec!["mosquitto_pub".to_string()];
+    command.extend(broker.connection_args());
+    command.extend([
+        "-r".to_string(),
+        "-t".to_string(),
+        format!("{}/state", broker.topic),
+        "-m".to_string(),
+        profile.to_string(),
+    ]);
+    if !effects::spawn_detached(&command) {
+        eprintln!("{}", tr!("warning", tr!("mqtt-publish-failed")));
+    }
+}
+
+// Listen on `<topic>/set` in the background and switch to each profile named
+// there (a number, description or alias), as `switch` would
+pub fn follow_commands(config_path: &Path) {
+    let Some(broker) = broker() else {
+        return;
+    };
+    let mut child = match process::Command::new("mosquitto_sub")
+        .args(broker.connection_args())
+        .args(["-t", &format!("{}/set", broker.topic)])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("{}", tr!("warning", tr!("mqtt-not-listening", e)));
+            return;
+        }
+    };
+    let Some(stdout) = child.stdout.take() else {
+        return;
+    };
+    let config_path = config_path.to_path_buf();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            switch_to(&config_path, line.trim());
+        }
+        let _ = child.wait();
+        eprintln!("{}", tr!("warning", tr!("mqtt-ended")));
+    });
+}
+
+// Switch to the profile an MQTT command named
+fn switch_to(config_path: &Path, query: &str) {
+    let Ok(config) = SwayConfig::load(config_path) else {
+        return;
+    };
+    let display_configs = config.display_configs();
+    let Some(index) = config::find_config(&display_configs, query) else {
+        eprintln!("{}", tr!("mqtt-unknown-profile", query));
+        return;
+    };
+    if switcher::already_active(&display_configs[index], false) {
+        return;
+    }
+    println!(
+        "{}",
+        tr!("mqtt-switching", display_configs[index].description)
+    );
+    if let Err(e) = switcher::switch_persistent(&config, &display_configs, index, "mqtt") {
+        eprintln!("{}", tr!("switch-failed", e));
+    }
+}
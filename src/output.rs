@@ -0,0 +1,639 @@
+// Parsing of `output <name> <subcommand> ...` lines held in profiles
+
+use crate::layout::Rect;
+use crate::sway::{Mode, Output};
+use crate::tr;
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Range;
+
+// Every subcommand Sway accepts after `output <name>`
+pub const SUBCOMMANDS: &[&str] = &[
+    "mode",
+    "res",
+    "resolution",
+    "modeline",
+    "position",
+    "pos",
+    "scale",
+    "scale_filter",
+    "subpixel",
+    "background",
+    "bg",
+    "transform",
+    "enable",
+    "disable",
+    "toggle",
+    "power",
+    "dpms",
+    "max_render_time",
+    "adaptive_sync",
+    "render_bit_depth",
+    "color_profile",
+    "allow_tearing",
+    "hdr",
+    "unplug",
+];
+
+// The Sway release (major, minor) that added each subcommand newer than 1.4.
+// `dpms` still works after `power` took its place in 1.7.
+const INTRODUCED: &[(&str, (u64, u64))] = &[
+    ("adaptive_sync", (1, 5)),
+    ("max_render_time", (1, 5)),
+    ("power", (1, 7)),
+    ("render_bit_depth", (1, 7)),
+    ("unplug", (1, 8)),
+    ("allow_tearing", (1, 9)),
+    ("color_profile", (1, 10)),
+    ("hdr", (1, 11)),
+];
+
+// The release a subcommand needs, or None when every supported Sway has it
+pub fn introduced_in(key: &str) -> Option<(u64, u64)> {
+    INTRODUCED
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, version)| *version)
+}
+
+// Canonical name of an output subcommand, folding Sway's aliases together
+pub fn canonical_key(key: &str) -> &str {
+    match key {
+        "res" | "resolution" | "mode" => "mode",
+        "position" | "pos" => "pos",
+        "background" | "bg" => "bg",
+        "dpms" => "power",
+        other => other,
+    }
+}
+
+pub fn is_subcommand(word: &str) -> bool {
+    SUBCOMMANDS.contains(&word)
+}
+
+// Split the words following a subcommand into leading flags and the number of
+// values it takes. Unknown subcommands take everything up to the next known one.
+fn value_layout(key: &str, rest: &[String]) -> (usize, usize) {
+    let flags = match canonical_key(key) {
+        "mode" | "color_profile" => rest.iter().take_while(|w| w.starts_with("--")).count(),
+        _ => 0,
+    };
+    let rest = &rest[flags..];
+    let second_is = |words: &[&str]| rest.get(1).is_some_and(|w| words.contains(&w.as_str()));
+    let count = match canonical_key(key) {
+        "pos" => 2,
+        "modeline" => 11,
+        "enable" | "disable" | "toggle" | "unplug" => 0,
+        "bg" if second_is(&["solid_color"]) => 2,
+        "bg" if rest.get(2).is_some_and(|w| w.starts_with('#')) => 3,
+        "bg" => 2,
+        "transform" if second_is(&["clockwise", "anticlockwise"]) => 2,
+        "color_profile" if rest.first().is_some_and(|w| w == "icc") => 2,
+        _ if !is_subcommand(key) => rest.iter().take_while(|w| !is_subcommand(w)).count(),
+        _ => 1,
+    };
+    (flags, count.min(rest.len()))
+}
+
+// One subcommand of an output line with its flags and values. The key is
+// written in its canonical spelling.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Attribute {
+    #[serde(serialize_with = "serialize_key")]
+    pub key: String,
+    pub flags: Vec<String>,
+    pub values: Vec<String>,
+}
+
+impl Attribute {
+    pub fn canonical_key(&self) -> &str {
+        canonical_key(&self.key)
+    }
+
+    pub fn is_known(&self) -> bool {
+        is_subcommand(&self.key)
+    }
+}
+
+fn serialize_key<S: Serializer>(key: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(canonical_key(key))
+}
+
+// Group the words of an output line into subcommands
+fn parse_attributes(args: &[String]) -> Vec<Attribute> {
+    let mut attributes = Vec::new();
+    let mut index = 0;
+    while index < args.len() {
+        let key = &args[index];
+        let (flags, count) = value_layout(key, &args[index + 1..]);
+        let values_start = index + 1 + flags;
+        attributes.push(Attribute {
+            key: key.clone(),
+            flags: args[index + 1..values_start].to_vec(),
+            values: args[values_start..values_start + count].to_vec(),
+        });
+        index = values_start + count;
+    }
+    attributes
+}
+
+// Parse a `WIDTHxHEIGHT[@RATE[Hz]]` mode string
+pub fn parse_mode(text: &str) -> Option<Mode> {
+    let (size, rate) = match text.split_once('@') {
+        Some((size, rate)) => (size, Some(rate)),
+        None => (text, None),
+    };
+    let (width, height) = size.split_once('x')?;
+    let refresh = match rate {
+        Some(rate) => {
+            let hz: f64 = rate.trim_end_matches("Hz").parse().ok()?;
+            (hz * 1000.0).round() as i64
+        }
+        None => 0,
+    };
+    Some(Mode {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        refresh,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLine {
+    pub target: String,
+    pub args: Vec<String>,
+    pub attributes: Vec<Attribute>,
+}
+
+impl OutputLine {
+    // Parse a line, returning None when it is not an output command
+    pub fn parse(line: &str) -> Option<OutputLine> {
+        let mut tokens = tokenize(line).into_iter();
+        if tokens.next()? != "output" {
+            return None;
+        }
+        let target = tokens.next()?;
+        let args: Vec<String> = tokens.collect();
+        Some(OutputLine {
+            target,
+            attributes: parse_attributes(&args),
+            args,
+        })
+    }
+
+    // The values following a subcommand, e.g. ["0", "0"] for `pos 0 0`; the
+    // last occurrence wins, as it does in Sway
+    pub fn attribute(&self, key: &str) -> Option<&[String]> {
+        let key = canonical_key(key);
+        self.attributes
+            .iter()
+            .rev()
+            .find(|attribute| attribute.canonical_key() == key)
+            .map(|attribute| attribute.values.as_slice())
+    }
+
+    pub fn mode(&self) -> Option<Mode> {
+        self.attribute("mode")
+            .and_then(|values| values.first())
+            .and_then(|mode| parse_mode(mode))
+    }
+
+    pub fn scale(&self) -> Option<f64> {
+        self.attribute("scale")
+            .and_then(|values| values.first())
+            .and_then(|scale| scale.parse().ok())
+    }
+
+    pub fn position(&self) -> Option<(i64, i64)> {
+        let values = self.attribute("pos")?;
+        Some((values.first()?.parse().ok()?, values.get(1)?.parse().ok()?))
+    }
+
+    pub fn transform(&self) -> Option<&str> {
+        self.attribute("transform")
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    // The rectangle this line gives its output, filling gaps from the live state
+    pub fn rect(&self, live: Option<&Output>) -> Option<Rect> {
+        let mode = self.mode().or_else(|| live.map(Output::current_mode))?;
+        let scale = self.scale().or(live.map(|o| o.scale)).unwrap_or(1.0);
+        let (x, y) = self
+            .position()
+            .or(live.map(|o| (o.x, o.y)))
+            .unwrap_or((0, 0));
+        let transform = self
+            .transform()
+            .or(live.map(|o| o.transform.as_str()))
+            .unwrap_or("normal");
+        let rotated = transform.contains("90") || transform.contains("270");
+        Some(Rect::logical(x, y, mode.width, mode.height, scale, rotated))
+    }
+
+    pub fn is_wildcard(&self) -> bool {
+        self.target == "*"
+    }
+
+    // Whether this line turns the output off
+    pub fn is_disabled(&self) -> bool {
+        self.attributes.iter().any(|attribute| {
+            attribute.key == "disable"
+                || (attribute.canonical_key() == "power"
+                    && attribute.values.first().is_some_and(|v| v == "off"))
+        })
+    }
+}
+
+// A condition in front of a profile line: `?if-present NAME: command` or
+// `?if-absent NAME: command`, NAME being a connector or output description
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Present(String),
+    Absent(String),
+}
+
+impl Condition {
+    pub fn holds(&self, connected: &[Output]) -> bool {
+        let connected = |name: &str| {
+            connected
+                .iter()
+                .any(|o| o.name == name || o.identifier() == name)
+        };
+        match self {
+            Condition::Present(name) => connected(name),
+            Condition::Absent(name) => !connected(name),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Condition::Present(name) => write!(f, "?if-present {}:", quote(name)),
+            Condition::Absent(name) => write!(f, "?if-absent {}:", quote(name)),
+        }
+    }
+}
+
+// A `--set OUTPUT.KEY=VALUE` change to one setting of a profile's line for
+// an output. KEY is an output subcommand or `refresh`, which keeps the size of
+// the line's mode; `pos` takes `X,Y`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Override {
+    pub output: String,
+    pub key: String,
+    pub values: Vec<String>,
+}
+
+impl Override {
+    pub fn parse(text: &str) -> Result<Override, String> {
+        let invalid = || tr!("override-form", text);
+        let (setting, value) = text.split_once('=').ok_or_else(invalid)?;
+        // Descriptions can contain dots, keys cannot
+        let (output, key) = setting.rsplit_once('.').ok_or_else(invalid)?;
+        let values: Vec<String> = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .collect();
+        if output.is_empty() || values.is_empty() {
+            return Err(invalid());
+        }
+        let settable = key == "refresh"
+            || (is_subcommand(key)
+                && !matches!(key, "enable" | "disable" | "toggle" | "unplug" | "modeline"));
+        if !settable {
+            return Err(tr!("override-key", key));
+        }
+        Ok(Override {
+            output: output.to_string(),
+            key: key.to_string(),
+            values,
+        })
+    }
+
+    // The line with this override applied. `refresh` needs a mode to change,
+    // from the line or else from `live`.
+    pub fn apply(&self, line: &str, live: Option<&Output>) -> Result<String, String> {
+        if self.key != "refresh" {
+            return Ok(set_attribute(line, &self.key, &self.values));
+        }
+        let rate: f64 = self.values[0]
+            .trim_end_matches("Hz")
+            .parse()
+            .map_err(|_| tr!("not-a-rate", self.values[0]))?;
+        let mut mode = OutputLine::parse(line)
+            .and_then(|parsed| parsed.mode())
+            .or(live.map(Output::current_mode))
+            .ok_or_else(|| tr!("no-mode-for-rate", self.output))?;
+        mode.refresh = (rate * 1000.0).round() as i64;
+        Ok(set_attribute(line, "mode", &[mode.to_string()]))
+    }
+}
+
+impl fmt::Display for Override {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}={}", self.output, self.key, self.values.join(","))
+    }
+}
+
+// Split a conditional line into its condition and the command it guards;
+// None for an ordinary line
+pub fn split_condition(line: &str) -> Option<(Condition, &str)> {
+    let line = line.trim_start();
+    let (present, rest) = match line.strip_prefix("?if-present ") {
+        Some(rest) => (true, rest),
+        None => (false, line.strip_prefix("?if-absent ")?),
+    };
+    let (name, command) = rest.split_once(':')?;
+    let name = name.trim().trim_matches('"').to_string();
+    if name.is_empty() {
+        return None;
+    }
+    let condition = if present {
+        Condition::Present(name)
+    } else {
+        Condition::Absent(name)
+    };
+    Some((condition, command.trim_start()))
+}
+
+// The connector and description an `?identity DP-1 "Make Model Serial"` line
+// records for an output; None for other lines
+pub fn parse_identity(line: &str) -> Option<(String, String)> {
+    let rest = line.trim_start().strip_prefix("?identity ")?;
+    match tokenize(rest).as_slice() {
+        [connector, description] => Some((connector.clone(), description.clone())),
+        _ => None,
+    }
+}
+
+pub fn identity_line(connector: &str, description: &str) -> String {
+    format!("?identity {} \"{}\"", connector, description)
+}
+
+// A line kept in a profile as a comment because it is not a Sway command (see
+// `config::parse_configs`); it is never sent to Sway
+pub fn is_comment(line: &str) -> bool {
+    line.starts_with('#')
+}
+
+// Split a command into words, keeping quoted strings together
+pub fn tokenize(line: &str) -> Vec<String> {
+    tokenize_spans(line)
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect()
+}
+
+// Like tokenize, but also returns the byte range each word occupies in the line
+pub fn tokenize_spans(line: &str) -> Vec<(String, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                start.get_or_insert(i);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(begin) = start.take() {
+                    tokens.push((std::mem::take(&mut current), begin..i));
+                }
+            }
+            c => {
+                current.push(c);
+                start.get_or_insert(i);
+            }
+        }
+    }
+    if let Some(begin) = start {
+        tokens.push((current, begin..line.len()));
+    }
+    tokens
+}
+
+// Set a subcommand's values in an output line, replacing any existing
+// occurrence in place (keeping its flags) or appending it at the end
+pub fn set_attribute(line: &str, key: &str, values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| quote(value)).collect();
+    let tokens = tokenize_spans(line);
+    let words: Vec<String> = tokens.iter().map(|(word, _)| word.clone()).collect();
+    let canonical = canonical_key(key);
+
+    // Walk the subcommands the same way parse_attributes does
+    let mut index = 2;
+    while index < words.len() {
+        let (flags, count) = value_layout(&words[index], &words[index + 1..]);
+        let values_start = index + 1 + flags;
+        if canonical_key(&words[index]) == canonical {
+            let prefix_end = if flags > 0 {
+                tokens[values_start - 1].1.end
+            } else {
+                tokens[index].1.end
+            };
+            let suffix_start = if count > 0 {
+                tokens[values_start + count - 1].1.end
+            } else {
+                prefix_end
+            };
+            return format!(
+                "{} {}{}",
+                &line[..prefix_end],
+                values.join(" "),
+                &line[suffix_start..]
+            );
+        }
+        index = values_start + count;
+    }
+    format!("{} {} {}", line.trim_end(), key, values.join(" "))
+}
+
+// Subcommands that override each other, for normalizing
+pub fn key_group(key: &str) -> &str {
+    match canonical_key(key) {
+        "enable" | "disable" => "enable",
+        "modeline" => "mode",
+        other => other,
+    }
+}
+
+// Where a subcommand goes in a normalized line
+fn key_rank(key: &str) -> usize {
+    match key_group(key) {
+        "enable" => 0,
+        "mode" => 1,
+        "pos" => 2,
+        "scale" => 3,
+        "transform" => 4,
+        _ => 5,
+    }
+}
+
+// The canonical spelling of a line: single spaces, then for output lines the
+// enable state first, followed by mode, position, scale and transform and the
+// remaining subcommands in their original order. A repeated subcommand keeps
+// only its last occurrence, the one Sway applies, so the meaning is unchanged.
+pub fn normalize_line(line: &str) -> String {
+    if let Some((condition, command)) = split_condition(line) {
+        return format!("{} {}", condition, normalize_line(command));
+    }
+    let Some(parsed) = OutputLine::parse(line) else {
+        return line.trim().to_string();
+    };
+    let attributes = &parsed.attributes;
+    let mut kept: Vec<&Attribute> = attributes
+        .iter()
+        .enumerate()
+        .filter(|(index, attribute)| {
+            !attribute.is_known()
+                || !attributes[index + 1..].iter().any(|later| {
+                    later.is_known() && key_group(&later.key) == key_group(&attribute.key)
+                })
+        })
+        .map(|(_, attribute)| attribute)
+        .collect();
+    kept.sort_by_key(|attribute| key_rank(&attribute.key));
+
+    let mut words = vec!["output".to_string(), quote(&parsed.target)];
+    for attribute in kept {
+        words.push(attribute.key.clone());
+        words.extend(attribute.flags.iter().cloned());
+        words.extend(attribute.values.iter().map(|value| quote(value)));
+    }
+    words.join(" ")
+}
+
+// The lines of a profile in a stable order: each run of lines for specific
+// outputs sorted by output name (see `compare_names`), lines for the same output
+// keeping their order.
+// Wildcard lines and other commands stay where they are, as moving a line past
+// them could change what overrides what.
+pub fn sort_lines(lines: &[String]) -> Vec<String> {
+    let target = |line: &str| {
+        if let Some((connector, _)) = parse_identity(line) {
+            return Some(connector);
+        }
+        let command = split_condition(line).map_or(line, |(_, command)| command);
+        OutputLine::parse(command)
+            .filter(|parsed| !parsed.is_wildcard())
+            .map(|parsed| parsed.target)
+    };
+    let mut sorted = Vec::new();
+    let mut run: Vec<(String, &String)> = Vec::new();
+    for line in lines {
+        match target(line) {
+            Some(target) => run.push((target, line)),
+            None => {
+                run.sort_by(|a, b| compare_names(&a.0, &b.0));
+                sorted.extend(run.drain(..).map(|(_, line)| line.clone()));
+                sorted.push(line.clone());
+            }
+        }
+    }
+    run.sort_by(|a, b| compare_names(&a.0, &b.0));
+    sorted.extend(run.into_iter().map(|(_, line)| line.clone()));
+    sorted
+}
+
+// Compare output names with runs of digits taken as numbers, so that `DP-2`
+// comes before `DP-10`
+fn compare_names(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        let ordering = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (x, rest_a) = split_number(a);
+            let (y, rest_b) = split_number(b);
+            (a, b) = (rest_a, rest_b);
+            let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+            x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+        } else {
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+            x.cmp(&y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+// The leading digits of `text` and what follows them
+fn split_number(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+// Quote a word for an output line when it contains whitespace
+pub fn quote(word: &str) -> String {
+    if word.contains(char::is_whitespace) {
+        format!("\"{}\"", word)
+    } else {
+        word.to_string()
+    }
+}
+
+// Rewrite the target of an output line, leaving the rest of the line untouched
+pub fn replace_target(line: &str, new_target: &str) -> Option<String> {
+    let tokens = tokenize_spans(line);
+    if tokens.first()?.0 != "output" {
+        return None;
+    }
+    let span = tokens.get(1)?.1.clone();
+    let quoted = if new_target.contains(char::is_whitespace) {
+        format!("\"{}\"", new_target)
+    } else {
+        new_target.to_string()
+    };
+    Some(format!(
+        "{}{}{}",
+        &line[..span.start],
+        quoted,
+        &line[span.end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_in_names_compare_by_value() {
+        assert_eq!(compare_names("DP-2", "DP-10"), Ordering::Less);
+        assert_eq!(compare_names("HDMI-A-1", "DP-10"), Ordering::Greater);
+        assert_eq!(compare_names("DP-1", "DP-1-1"), Ordering::Less);
+        assert_eq!(compare_names("DP-02", "DP-2"), Ordering::Equal);
+    }
+
+    #[test]
+    fn sorts_lines_by_output_name() {
+        let lines = [
+            "output DP-10 enable",
+            "output DP-2 enable",
+            "output DP-10 scale 2",
+            "output * bg #000000 solid_color",
+            "output DP-1 enable",
+            "output eDP-1 disable",
+        ]
+        .map(String::from);
+        assert_eq!(
+            sort_lines(&lines),
+            [
+                "output DP-2 enable",
+                "output DP-10 enable",
+                "output DP-10 scale 2",
+                "output * bg #000000 solid_color",
+                "output DP-1 enable",
+                "output eDP-1 disable",
+            ]
+        );
+    }
+}
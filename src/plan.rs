@@ -0,0 +1,159 @@
+use crate::config::{self, DisplayConfig, SwayConfig};
+use crate::diff;
+use crate::error::{self, Error};
+use crate::state;
+use crate::sway;
+use crate::tr;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+enum PlanSection {
+    Header,
+    Commands,
+    Config,
+}
+
+// A previewed switch: the Sway commands to run and the config file to write
+pub struct Plan {
+    pub description: String,
+    pub checksum: u64,
+    pub commands: Vec<String>,
+    pub new_config: Option<Vec<String>>,
+}
+
+impl Plan {
+    pub fn build(
+        config: &SwayConfig,
+        display_configs: &[DisplayConfig],
+        selected_index: usize,
+        transient: bool,
+    ) -> Plan {
+        // The commands and the file as a switch made now would have them
+        let connected = sway::get_outputs().unwrap_or_default();
+        let selected = &display_configs[selected_index];
+        let new_config = if transient {
+            None
+        } else {
+            let mut updated = config::activate(display_configs, selected_index);
+            updated[selected_index] = updated[selected_index].reidentified(&connected);
+            Some(config.with_display_configs(&updated))
+        };
+        Plan {
+            description: selected.description.clone(),
+            checksum: config::checksum(&config.lines),
+            commands: sway::output_commands(selected, &connected),
+            new_config,
+        }
+    }
+
+    pub fn print(&self, config: &SwayConfig) {
+        println!("{}", tr!("plan-for", self.description));
+        println!("\n{}", tr!("plan-commands"));
+        for command in &self.commands {
+            println!("  swaymsg {}", command);
+        }
+        match &self.new_config {
+            Some(new_lines) => {
+                let changes = diff::format_diff(&config.lines, new_lines, 2);
+                if changes.is_empty() {
+                    println!("\n{}", tr!("plan-no-changes", config.path.display()));
+                } else {
+                    println!("\n{}", tr!("plan-changes", config.path.display()));
+                    for line in changes {
+                        println!("{}", line);
+                    }
+                }
+            }
+            None => println!("\n{}", tr!("plan-transient")),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = plan_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = format!(
+            "profile = {}\nchecksum = {}\n[commands]\n",
+            self.description, self.checksum
+        );
+        for command in &self.commands {
+            contents.push_str(command);
+            contents.push('\n');
+        }
+        if let Some(new_lines) = &self.new_config {
+            contents.push_str("[config]\n");
+            for line in new_lines {
+                contents.push_str(line);
+                contents.push('\n');
+            }
+        }
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn load() -> io::Result<Plan> {
+        let contents = fs::read_to_string(plan_path())?;
+        let mut plan = Plan {
+            description: String::new(),
+            checksum: 0,
+            commands: Vec::new(),
+            new_config: None,
+        };
+        let mut section = PlanSection::Header;
+        for line in contents.lines() {
+            match section {
+                PlanSection::Config => plan
+                    .new_config
+                    .get_or_insert_with(Vec::new)
+                    .push(line.to_string()),
+                _ if line == "[config]" => {
+                    section = PlanSection::Config;
+                    plan.new_config = Some(Vec::new());
+                }
+                PlanSection::Header if line == "[commands]" => section = PlanSection::Commands,
+                PlanSection::Commands => plan.commands.push(line.to_string()),
+                PlanSection::Header => {
+                    if let Some((key, value)) = line.split_once(" = ") {
+                        match key {
+                            "profile" => plan.description = value.to_string(),
+                            "checksum" => plan.checksum = value.parse().unwrap_or(0),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    // Refuse a plan made for the config as it was before a later change
+    pub fn check(&self, config: &SwayConfig) -> error::Result<()> {
+        if config::checksum(&config.lines) != self.checksum {
+            return Err(Error::Failed(tr!("plan-stale", config.path.display())));
+        }
+        Ok(())
+    }
+
+    // Execute the plan, refusing if the config changed since it was shown. The
+    // guards and side effects of a switch are switcher::apply_plan's.
+    pub fn apply(&self, config: &SwayConfig) -> error::Result<()> {
+        self.check(config)?;
+        if let Some(new_lines) = &self.new_config {
+            config.check_rewritable()?;
+            config::write_config(&config.path, new_lines)
+                .map_err(|e| Error::file("write", &config.path, e))?;
+        }
+        let success = sway::run_commands(&self.commands);
+        let _ = fs::remove_file(plan_path());
+        if !success {
+            return Err(Error::Sway(tr!("apply-failed")));
+        }
+        Ok(())
+    }
+}
+
+fn plan_path() -> PathBuf {
+    state::state_dir().join("plan")
+}
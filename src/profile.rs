@@ -0,0 +1,189 @@
+//! Canonical machine representation of profiles.
+//!
+//! Every JSON document that holds a profile uses these types: `list --json`,
+//! `show --json`, `export --format json` and `get` write them and
+//! `import json` reads them back, as does [`DisplayConfig`]'s serde
+//! implementation. The switch log
+//! and the events socket refer to profiles by name only. `SCHEMA_VERSION` is
+//! bumped whenever a field is removed or changes meaning; new optional fields
+//! may be added without a bump.
+
+use crate::config::DisplayConfig;
+use crate::output::{Attribute, OutputLine};
+use crate::tr;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const SCHEMA_VERSION: i64 = 1;
+
+// A single line of a profile with its parsed output target. Only `command` is
+// read; the other fields are derived from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "OutputCommand")]
+pub struct ProfileOutput {
+    pub target: Option<String>,
+    pub enabled: bool,
+    pub attributes: Vec<Attribute>,
+    pub command: String,
+}
+
+#[derive(Deserialize)]
+struct OutputCommand {
+    command: String,
+}
+
+impl From<OutputCommand> for ProfileOutput {
+    fn from(output: OutputCommand) -> ProfileOutput {
+        ProfileOutput::from_command(&output.command)
+    }
+}
+
+impl ProfileOutput {
+    // Derive the parsed fields from the command so they cannot disagree with it
+    pub fn from_command(command: &str) -> ProfileOutput {
+        let parsed = OutputLine::parse(command);
+        ProfileOutput {
+            enabled: parsed.as_ref().is_none_or(|p| !p.is_disabled()),
+            attributes: parsed
+                .as_ref()
+                .map(|p| p.attributes.clone())
+                .unwrap_or_default(),
+            target: parsed.map(|p| p.target),
+            command: command.to_string(),
+        }
+    }
+}
+
+// A profile as exposed to external tools. `enabled` follows `status`, and is
+// not read back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ProfileInput")]
+pub struct Profile {
+    pub schema_version: i64,
+    pub name: String,
+    pub status: String,
+    pub enabled: bool,
+    #[serde(with = "metadata")]
+    pub metadata: Vec<(String, String)>,
+    pub outputs: Vec<ProfileOutput>,
+}
+
+// A profile as read: only `name` is required
+#[derive(Deserialize)]
+struct ProfileInput {
+    schema_version: Option<i64>,
+    name: String,
+    #[serde(default = "disabled")]
+    status: String,
+    #[serde(default, with = "metadata")]
+    metadata: Vec<(String, String)>,
+    #[serde(default)]
+    outputs: Vec<ProfileOutput>,
+}
+
+fn disabled() -> String {
+    "Disabled".to_string()
+}
+
+impl TryFrom<ProfileInput> for Profile {
+    type Error = String;
+
+    fn try_from(input: ProfileInput) -> Result<Profile, String> {
+        if let Some(version) = input.schema_version.filter(|&v| v > SCHEMA_VERSION) {
+            return Err(tr!("unsupported-schema", version, SCHEMA_VERSION));
+        }
+        Ok(Profile {
+            schema_version: SCHEMA_VERSION,
+            enabled: input.status.eq_ignore_ascii_case("Enabled"),
+            name: input.name,
+            status: input.status,
+            metadata: input.metadata,
+            outputs: input.outputs,
+        })
+    }
+}
+
+// Header metadata as a JSON object, keeping the order of its keys
+mod metadata {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        metadata: &[(String, String)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(metadata.iter().map(|(key, value)| (key, value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, String)>, D::Error> {
+        serde_json::Map::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(text) => Ok((key, text)),
+                _ => Err(D::Error::custom(tr!("metadata-not-string", key))),
+            })
+            .collect()
+    }
+}
+
+impl Profile {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl From<&DisplayConfig> for Profile {
+    fn from(config: &DisplayConfig) -> Profile {
+        let outputs = config
+            .outputs
+            .iter()
+            .map(|line| ProfileOutput::from_command(line))
+            .collect();
+        Profile {
+            schema_version: SCHEMA_VERSION,
+            name: config.description.clone(),
+            status: config.status.clone(),
+            enabled: config.is_enabled(),
+            metadata: config.metadata.clone(),
+            outputs,
+        }
+    }
+}
+
+impl From<DisplayConfig> for Profile {
+    fn from(config: DisplayConfig) -> Profile {
+        Profile::from(&config)
+    }
+}
+
+impl From<&Profile> for DisplayConfig {
+    fn from(profile: &Profile) -> DisplayConfig {
+        DisplayConfig {
+            description: profile.name.clone(),
+            status: profile.status.clone(),
+            outputs: profile.outputs.iter().map(|o| o.command.clone()).collect(),
+            metadata: profile.metadata.clone(),
+        }
+    }
+}
+
+impl From<Profile> for DisplayConfig {
+    fn from(profile: Profile) -> DisplayConfig {
+        DisplayConfig::from(&profile)
+    }
+}
+
+// The document printed by `list --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfilesDocument {
+    pub schema_version: i64,
+    pub profiles: Vec<Profile>,
+}
+
+pub fn profiles_document(configs: &[DisplayConfig]) -> ProfilesDocument {
+    ProfilesDocument {
+        schema_version: SCHEMA_VERSION,
+        profiles: configs.iter().map(Profile::from).collect(),
+    }
+}
@@ -0,0 +1,65 @@
+// Line-based prompts for interactive commands
+
+use std::io::{self, Write};
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::tr;
+
+// Print a prompt and read one trimmed line; end of input means the person quit
+fn read_answer(prompt: &str) -> error::Result<String> {
+    print!("{}: ", prompt);
+    io::stdout().flush().ok();
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+        println!();
+        return Err(Error::Cancelled);
+    }
+    Ok(input.trim().to_string())
+}
+
+// Ask a question, returning the answer or the default on an empty line
+pub fn ask(question: &str, default: &str) -> error::Result<String> {
+    let answer = if default.is_empty() {
+        read_answer(question)?
+    } else {
+        read_answer(&format!("{} [{}]", question, default))?
+    };
+    if answer.is_empty() {
+        Ok(default.to_string())
+    } else {
+        Ok(answer)
+    }
+}
+
+// Ask a yes/no question, accepting English answers as well as the first
+// letter of the translated yes and no
+pub fn confirm(question: &str, default: bool) -> error::Result<bool> {
+    let (yes, no) = (tr!("yes"), tr!("no"));
+    let hint = if default {
+        format!("{}/{}", yes.to_uppercase(), no)
+    } else {
+        format!("{}/{}", yes, no.to_uppercase())
+    };
+    loop {
+        let answer = read_answer(&format!("{} [{}]", question, hint))?.to_lowercase();
+        match answer.as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ if answer.starts_with(&yes) => return Ok(true),
+            _ if answer.starts_with(&no) => return Ok(false),
+            _ => println!("{}", tr!("answer-yes-no")),
+        }
+    }
+}
+
+// Ask for a number between 1 and `max`, returning a 0-based index
+#[cfg(feature = "interactive")]
+pub fn choose(question: &str, max: usize, default: usize) -> error::Result<usize> {
+    loop {
+        let answer = ask(question, &(default + 1).to_string())?;
+        match answer.parse::<usize>() {
+            Ok(choice) if choice > 0 && choice <= max => return Ok(choice - 1),
+            _ => println!("{}", tr!("number-range", max)),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// Single-field queries for `get`, so scripts can read one value without jq
+
+use crate::config::{self, DisplayConfig};
+use crate::matching;
+use crate::profile::Profile;
+use crate::state::State;
+use crate::sway::Output;
+use serde_json::{json, Value};
+
+// Every field `get` can reach, as dotted paths into one document. Profiles are
+// the same objects `list --json` prints. Fields that depend on Sway are null
+// when it cannot be queried.
+pub fn document(configs: &[DisplayConfig], connected: Option<&[Output]>, state: &State) -> Value {
+    let best = connected
+        .and_then(|connected| matching::best_match(configs, connected))
+        .map(|index| Profile::from(&configs[index]));
+    let connected = connected.map(|connected| {
+        json!({
+            "count": connected.len(),
+            "names": connected.iter().map(|o| &o.name).collect::<Vec<_>>(),
+        })
+    });
+    json!({
+        "current": config::enabled_config(configs).map(Profile::from),
+        "previous": state.previous_profile,
+        "best": best,
+        "profiles": {
+            "count": configs.len(),
+            "names": configs.iter().map(|c| &c.description).collect::<Vec<_>>(),
+        },
+        "connected": connected,
+    })
+}
+
+// Follow a dotted path such as `current.metadata.Hotkey`. Object keys match
+// case-insensitively. On a list a number picks an item, counting from 1, and
+// a key is followed in every item, e.g. `current.outputs.target`.
+pub fn lookup(document: &Value, path: &str) -> Option<Value> {
+    path.split('.')
+        .try_fold(document.clone(), |value, key| step(&value, key))
+}
+
+fn step(value: &Value, key: &str) -> Option<Value> {
+    match value {
+        Value::Object(entries) => entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.clone()),
+        Value::Array(items) => match key.parse::<usize>() {
+            Ok(n) => items.get(n.checked_sub(1)?).cloned(),
+            Err(_) => Some(Value::Array(
+                items.iter().filter_map(|item| step(item, key)).collect(),
+            )),
+        },
+        _ => None,
+    }
+}
+
+// A value as plain text: strings unquoted, arrays one item per line and
+// objects as JSON. Null has no text.
+pub fn raw(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Array(items) => Some(items.iter().filter_map(raw).collect::<Vec<_>>().join("\n")),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docked() -> DisplayConfig {
+        DisplayConfig {
+            description: "Docked".to_string(),
+            outputs: vec![
+                "output eDP-1 disable".to_string(),
+                "output DP-1 enable".to_string(),
+            ],
+            status: "Enabled".to_string(),
+            metadata: vec![("Hotkey".to_string(), "$mod+F1".to_string())],
+        }
+    }
+
+    fn get(path: &str) -> Option<String> {
+        let document = document(&[docked()], None, &State::default());
+        lookup(&document, path).and_then(|value| raw(&value))
+    }
+
+    #[test]
+    fn current_is_the_listed_profile() {
+        let document = document(&[docked()], None, &State::default());
+        let listed = serde_json::to_value(crate::profile::profiles_document(&[docked()])).unwrap();
+        assert_eq!(lookup(&document, "current"), lookup(&listed, "profiles.1"));
+    }
+
+    #[test]
+    fn fields_of_every_item() {
+        assert_eq!(
+            get("current.outputs.target").as_deref(),
+            Some("eDP-1\nDP-1")
+        );
+        assert_eq!(get("current.outputs.2.enabled").as_deref(), Some("true"));
+        assert_eq!(get("CURRENT.metadata.hotkey").as_deref(), Some("$mod+F1"));
+        assert_eq!(get("profiles.names.1").as_deref(), Some("Docked"));
+    }
+
+    #[test]
+    fn missing_fields_have_no_value() {
+        assert_eq!(get("best.name"), None);
+        assert_eq!(get("current.outputs.0"), None);
+        assert_eq!(get("current.name.first"), None);
+    }
+}
@@ -0,0 +1,132 @@
+// Managing the Sway session of another machine over SSH (`--host user@machine`):
+// its config is copied into the state directory, read and rewritten there as
+// usual and copied back after every write, and swaymsg runs on the host
+
+use crate::state;
+use crate::tr;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::sync::OnceLock;
+
+// The SSH destination chosen for this invocation; None means this machine
+static HOST: OnceLock<Option<String>> = OnceLock::new();
+
+// Where the config lives on the host, as a shell word expanded there
+static REMOTE_PATH: OnceLock<String> = OnceLock::new();
+
+// The user config, or the system one (`--system`), on the host
+const USER_CONFIG: &str = "\"${XDG_CONFIG_HOME:-$HOME/.config}/sway/config\"";
+const SYSTEM_CONFIG: &str = "/etc/sway/config";
+
+// Direct every following config transfer and swaymsg call at `host`
+pub fn set_host(host: Option<String>, system: bool) {
+    let _ = HOST.set(host);
+    let _ = REMOTE_PATH.set(if system { SYSTEM_CONFIG } else { USER_CONFIG }.to_string());
+}
+
+pub fn host() -> Option<&'static str> {
+    HOST.get().and_then(Option::as_deref)
+}
+
+// Quote a word for the host's POSIX shell
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+// An ssh command running `script` on the host. Connections are shared through
+// a control socket, so the many swaymsg calls of a switch log in only once,
+// and stdin is not forwarded unless the caller sets it.
+fn ssh(host: &str, script: &str) -> process::Command {
+    let control = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+        .join("sway-display-switcher-ssh-%C");
+    let mut command = process::Command::new("ssh");
+    command
+        .arg("-o")
+        .arg("ControlMaster=auto")
+        .arg("-o")
+        .arg(format!("ControlPath={}", control.display()))
+        .arg("-o")
+        .arg("ControlPersist=60")
+        .args([host, "--", script])
+        .stdin(Stdio::null());
+    command
+}
+
+// swaymsg with `args`, here or on the host. An SSH session does not inherit
+// SWAYSOCK from the desktop, so on the host it is looked up in the runtime
+// directory when not set.
+pub fn swaymsg<I, S>(args: I) -> process::Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let Some(host) = host() else {
+        let mut command = process::Command::new("swaymsg");
+        command.args(args.into_iter().map(|arg| arg.as_ref().to_string()));
+        return command;
+    };
+    let words: Vec<String> = args
+        .into_iter()
+        .map(|arg| shell_quote(arg.as_ref()))
+        .collect();
+    let script = format!(
+        "SWAYSOCK=\"${{SWAYSOCK:-$(ls \"${{XDG_RUNTIME_DIR:-/run/user/$(id -u)}}\"/sway-ipc.*.sock 2>/dev/null | head -n 1)}}\" exec swaymsg {}",
+        words.join(" ")
+    );
+    ssh(host, &script)
+}
+
+// The local copy of the host's config
+pub fn mirror_path() -> PathBuf {
+    state::state_dir().join("remote-config")
+}
+
+// Whether `path` is the local copy of a host's config, which has to be sent
+// back after writing it
+pub fn is_mirror(path: &Path) -> bool {
+    host().is_some() && path == mirror_path()
+}
+
+fn remote_path() -> &'static str {
+    REMOTE_PATH.get().map_or(USER_CONFIG, String::as_str)
+}
+
+// Copy the host's config into mirror_path() and return that path
+pub fn fetch() -> Result<PathBuf, String> {
+    let host = host().ok_or_else(|| tr!("no-host"))?;
+    let reply = ssh(host, &format!("cat {}", remote_path()))
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| tr!("ssh-failed", e))?;
+    if !reply.status.success() {
+        return Err(tr!("remote-read-failed", host));
+    }
+    let path = mirror_path();
+    fs::create_dir_all(state::state_dir())
+        .and_then(|_| fs::write(&path, &reply.stdout))
+        .map_err(|e| tr!("write-failed", path.display(), e))?;
+    Ok(path)
+}
+
+// Copy the local config back to the host, replacing its config in one rename
+// once the new contents have fully arrived
+pub fn upload(path: &Path) -> io::Result<()> {
+    let Some(host) = host() else {
+        return Ok(());
+    };
+    let script = format!(
+        "f={}; cat > \"$f.sway-display-switcher\" && mv \"$f.sway-display-switcher\" \"$f\"",
+        remote_path()
+    );
+    let status = ssh(host, &script).stdin(File::open(path)?).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(tr!("remote-write-failed", host)))
+    }
+}
@@ -0,0 +1,125 @@
+// The part each connected output plays on this machine: the built-in panel,
+// the external displays and the largest of them, and the one to treat as main
+
+use crate::sway::Output;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    // A built-in panel (eDP, LVDS or DSI connector)
+    Internal,
+    // Any output that is not built in
+    External,
+    // The external output with the largest screen
+    LargestExternal,
+    // The output to put the main workspace on: the largest external output,
+    // else the built-in panel, else the first output
+    Primary,
+}
+
+pub const ROLES: [Role; 4] = [
+    Role::Internal,
+    Role::External,
+    Role::LargestExternal,
+    Role::Primary,
+];
+
+impl Role {
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Internal => "internal",
+            Role::External => "external",
+            Role::LargestExternal => "largest-external",
+            Role::Primary => "primary",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Role> {
+        ROLES.into_iter().find(|role| role.name() == name)
+    }
+}
+
+// Whether a connector belongs to a built-in panel
+pub fn is_internal(name: &str) -> bool {
+    name.starts_with("eDP") || name.starts_with("LVDS") || name.starts_with("DSI")
+}
+
+// The built-in panel, when there is one
+pub fn internal(connected: &[Output]) -> Option<&Output> {
+    connected.iter().find(|output| is_internal(&output.name))
+}
+
+pub fn externals(connected: &[Output]) -> Vec<&Output> {
+    connected
+        .iter()
+        .filter(|output| !is_internal(&output.name))
+        .collect()
+}
+
+// The external output with the largest screen by its EDID size. Projectors
+// and many TVs report no size, so outputs without one come after those with
+// one and are compared by their largest mode; earlier outputs win ties.
+pub fn largest_external(connected: &[Output]) -> Option<&Output> {
+    let size = |output: &Output| {
+        let known = output.physical_width > 10 && output.physical_height > 10;
+        let area = output.physical_width * output.physical_height;
+        let pixels = output
+            .modes
+            .iter()
+            .map(|mode| mode.width * mode.height)
+            .max()
+            .unwrap_or(output.width * output.height);
+        (known, if known { area } else { 0 }, pixels)
+    };
+    externals(connected)
+        .into_iter()
+        .rev()
+        .max_by_key(|output| size(output))
+}
+
+pub fn primary(connected: &[Output]) -> Option<&Output> {
+    largest_external(connected)
+        .or_else(|| internal(connected))
+        .or_else(|| connected.first())
+}
+
+// The outputs playing `role`
+pub fn find(role: Role, connected: &[Output]) -> Vec<&Output> {
+    match role {
+        Role::Internal => connected
+            .iter()
+            .filter(|output| is_internal(&output.name))
+            .collect(),
+        Role::External => externals(connected),
+        Role::LargestExternal => largest_external(connected).into_iter().collect(),
+        Role::Primary => primary(connected).into_iter().collect(),
+    }
+}
+
+// Every role `output` plays among the connected outputs
+pub fn roles_of(output: &Output, connected: &[Output]) -> Vec<Role> {
+    ROLES
+        .into_iter()
+        .filter(|&role| {
+            find(role, connected)
+                .iter()
+                .any(|other| other.name == output.name)
+        })
+        .collect()
+}
+
+// The roles and the connector names playing them, for `detect --json`
+pub fn document(connected: &[Output]) -> Value {
+    Value::Object(
+        ROLES
+            .into_iter()
+            .map(|role| {
+                let names = find(role, connected)
+                    .iter()
+                    .map(|output| output.name.clone().into())
+                    .collect();
+                (role.name().to_string(), Value::Array(names))
+            })
+            .collect(),
+    )
+}
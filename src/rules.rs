@@ -0,0 +1,421 @@
+// Daemon rules: events such as an output being plugged in or the lid closing,
+// or a time of day, mapped to the profile to switch to. Rules live in the
+// settings file as `on <event> = <profile>` and `at <time> = <profile>` lines.
+
+use crate::sway::Output;
+use std::fmt;
+use std::fs;
+use std::process::Command;
+
+// Something the daemon can react to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    // An output was connected; with a pattern, only one whose connector is the
+    // pattern or whose description contains it (ignoring case)
+    Added(Option<String>),
+    // An output was disconnected, likewise optionally filtered
+    Removed(Option<String>),
+    LidClosed,
+    LidOpened,
+    // A GPU appeared or went away, such as an eGPU being attached
+    GpuAdded,
+    GpuRemoved,
+    // The clock reached a time of day (minutes after midnight) on one of the
+    // days, Monday being day 1
+    At { days: Vec<u32>, minute: u32 },
+}
+
+// Day names and groups accepted in `at` rules
+const DAY_NAMES: &[(&str, &[u32])] = &[
+    ("daily", &[1, 2, 3, 4, 5, 6, 7]),
+    ("weekdays", &[1, 2, 3, 4, 5]),
+    ("weekends", &[6, 7]),
+    ("mon", &[1]),
+    ("tue", &[2]),
+    ("wed", &[3]),
+    ("thu", &[4]),
+    ("fri", &[5]),
+    ("sat", &[6]),
+    ("sun", &[7]),
+];
+
+impl Event {
+    // Whether the event is a time of day rather than a change of the hardware
+    pub fn is_scheduled(&self) -> bool {
+        matches!(self, Event::At { .. })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Added(None) => write!(f, "on added"),
+            Event::Added(Some(pattern)) => write!(f, "on added \"{}\"", pattern),
+            Event::Removed(None) => write!(f, "on removed"),
+            Event::Removed(Some(pattern)) => write!(f, "on removed \"{}\"", pattern),
+            Event::LidClosed => write!(f, "on lid closed"),
+            Event::LidOpened => write!(f, "on lid open"),
+            Event::GpuAdded => write!(f, "on gpu added"),
+            Event::GpuRemoved => write!(f, "on gpu removed"),
+            Event::At { days, minute } => {
+                let days = DAY_NAMES
+                    .iter()
+                    .find(|(_, group)| group == days)
+                    .map(|(name, _)| name.to_string())
+                    .unwrap_or_else(|| {
+                        days.iter()
+                            .filter_map(|day| DAY_NAMES.get(*day as usize + 2))
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    });
+                write!(f, "at {} {:02}:{:02}", days, minute / 60, minute % 60)
+            }
+        }
+    }
+}
+
+// What to switch to when a rule fires
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    // A profile by number, description or alias
+    Profile(String),
+    // Whichever profile best fits the connected outputs
+    BestMatch,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub event: Event,
+    pub action: Action,
+}
+
+impl Rule {
+    // Parse the key and value of an `on <event> = <profile>` settings line,
+    // e.g. `on added "Dell Inc. DELL U2720Q" = TV` or `on lid closed = Clamshell`,
+    // or of an `at [<days>] <HH:MM> = <profile>` line such as
+    // `at weekdays 09:00 = Work`
+    pub fn parse(key: &str, value: &str) -> Option<Rule> {
+        if let Some(time) = key.strip_prefix("at ") {
+            let event = parse_schedule(time)?;
+            return Some(Rule {
+                event,
+                action: parse_action(value)?,
+            });
+        }
+        let event = key.strip_prefix("on")?;
+        if !event.starts_with(char::is_whitespace) || value.is_empty() {
+            return None;
+        }
+        let (kind, rest) = event
+            .trim()
+            .split_once(char::is_whitespace)
+            .map_or((event.trim(), ""), |(kind, rest)| (kind, rest.trim()));
+        let pattern = (!rest.is_empty()).then(|| rest.trim_matches('"').to_string());
+        let event = match (kind, rest) {
+            ("added", _) => Event::Added(pattern),
+            ("removed", _) => Event::Removed(pattern),
+            ("lid", "closed") => Event::LidClosed,
+            ("lid", "open") => Event::LidOpened,
+            ("gpu", "added") => Event::GpuAdded,
+            ("gpu", "removed") => Event::GpuRemoved,
+            _ => return None,
+        };
+        Some(Rule {
+            event,
+            action: parse_action(value)?,
+        })
+    }
+
+    // Whether the rule's event happened between two lists of connected outputs
+    // or lid states. Rules for the lid only fire when its state changed.
+    pub fn fires(
+        &self,
+        before: &[Output],
+        after: &[Output],
+        lid_before: Option<bool>,
+        lid_after: Option<bool>,
+    ) -> bool {
+        let lid_changed = lid_before.is_some() && lid_before != lid_after;
+        match &self.event {
+            Event::Added(pattern) => changed(after, before)
+                .iter()
+                .any(|output| matches_pattern(output, pattern.as_deref())),
+            Event::Removed(pattern) => changed(before, after)
+                .iter()
+                .any(|output| matches_pattern(output, pattern.as_deref())),
+            Event::LidClosed => lid_changed && lid_after == Some(true),
+            Event::LidOpened => lid_changed && lid_after == Some(false),
+            // Checked by the daemon against the GPUs, see `gpu_rule`
+            Event::GpuAdded | Event::GpuRemoved | Event::At { .. } => false,
+        }
+    }
+}
+
+// The first rule that fires, rules being tried in file order
+pub fn first_fired<'a>(
+    rules: &'a [Rule],
+    before: &[Output],
+    after: &[Output],
+    lid_before: Option<bool>,
+    lid_after: Option<bool>,
+) -> Option<&'a Rule> {
+    rules
+        .iter()
+        .find(|rule| rule.fires(before, after, lid_before, lid_after))
+}
+
+// The rule for a GPU appearing or going away. Connector names shift when that
+// happens, so without a rule of its own the best match is switched to.
+pub fn gpu_rule(rules: &[Rule], added: bool) -> Rule {
+    let event = if added {
+        Event::GpuAdded
+    } else {
+        Event::GpuRemoved
+    };
+    rules
+        .iter()
+        .find(|rule| rule.event == event)
+        .cloned()
+        .unwrap_or(Rule {
+            event,
+            action: Action::BestMatch,
+        })
+}
+
+// The `at` rules due at a time of day (minutes after midnight) on a day of
+// the week, Monday being 1, in file order
+pub fn due(rules: &[Rule], day: u32, minute: u32) -> Vec<&Rule> {
+    rules
+        .iter()
+        .filter(|rule| match &rule.event {
+            Event::At { days, minute: at } => *at == minute && days.contains(&day),
+            _ => false,
+        })
+        .collect()
+}
+
+// The profile a rule's value names, or `best`
+fn parse_action(value: &str) -> Option<Action> {
+    if value.is_empty() {
+        None
+    } else if value.eq_ignore_ascii_case("best") {
+        Some(Action::BestMatch)
+    } else {
+        Some(Action::Profile(value.to_string()))
+    }
+}
+
+// `[<days>] <HH:MM>`, the days being `daily` (the default), `weekdays`,
+// `weekends` or a comma-separated list such as `mon,wed,fri`
+fn parse_schedule(text: &str) -> Option<Event> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (days, time) = match words.as_slice() {
+        [time] => ("daily", *time),
+        [days, time] => (*days, *time),
+        _ => return None,
+    };
+    let mut parsed = Vec::new();
+    for name in days.to_ascii_lowercase().split(',') {
+        let (_, group) = DAY_NAMES.iter().find(|(day, _)| *day == name)?;
+        parsed.extend(group.iter().copied());
+    }
+    parsed.sort_unstable();
+    parsed.dedup();
+    let (hours, minutes) = time.split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(Event::At {
+        days: parsed,
+        minute: hours * 60 + minutes,
+    })
+}
+
+// The local day of the week (Monday being 1) and minutes after midnight, from
+// date(1) so that the time zone and daylight saving time are honored
+pub fn local_time() -> Option<(u32, u32)> {
+    let output = Command::new("date").arg("+%u %H %M").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut fields = text.split_whitespace().map(|field| field.parse::<u32>());
+    let (day, hours, minutes) = (
+        fields.next()?.ok()?,
+        fields.next()?.ok()?,
+        fields.next()?.ok()?,
+    );
+    Some((day, hours * 60 + minutes))
+}
+
+// Outputs in `from` that are missing from `to`
+fn changed<'a>(from: &'a [Output], to: &[Output]) -> Vec<&'a Output> {
+    from.iter()
+        .filter(|output| {
+            !to.iter()
+                .any(|other| other.name == output.name && other.identifier() == output.identifier())
+        })
+        .collect()
+}
+
+fn matches_pattern(output: &Output, pattern: Option<&str>) -> bool {
+    pattern.is_none_or(|pattern| {
+        output.name == pattern
+            || output
+                .identifier()
+                .to_lowercase()
+                .contains(&pattern.to_lowercase())
+    })
+}
+
+// Whether the laptop lid is closed, from ACPI; None on machines without one
+pub fn lid_closed() -> Option<bool> {
+    let entries = fs::read_dir("/proc/acpi/button/lid").ok()?;
+    entries
+        .filter_map(Result::ok)
+        .find_map(|entry| fs::read_to_string(entry.path().join("state")).ok())
+        .map(|state| state.contains("closed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(name: &str, make: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            make: make.to_string(),
+            model: "Model".to_string(),
+            serial: "1".to_string(),
+            active: true,
+            width: 1920,
+            height: 1080,
+            refresh: 60000,
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            transform: "normal".to_string(),
+            modes: Vec::new(),
+            physical_width: 0,
+            physical_height: 0,
+        }
+    }
+
+    fn rule(key: &str, value: &str) -> Rule {
+        Rule::parse(key, value).unwrap()
+    }
+
+    #[test]
+    fn parses_on_rules() {
+        assert_eq!(
+            rule("on added \"Dell Inc. U2720Q\"", "TV"),
+            Rule {
+                event: Event::Added(Some("Dell Inc. U2720Q".to_string())),
+                action: Action::Profile("TV".to_string()),
+            }
+        );
+        assert_eq!(rule("on lid closed", "best").action, Action::BestMatch);
+        assert_eq!(rule("on  gpu removed", "Laptop").event, Event::GpuRemoved);
+        assert_eq!(Rule::parse("on lid ajar", "Laptop"), None);
+        assert_eq!(Rule::parse("onadded", "Laptop"), None);
+        assert_eq!(Rule::parse("on added", ""), None);
+    }
+
+    #[test]
+    fn the_first_rule_that_fires_wins() {
+        let rules = [
+            rule("on removed", "Laptop"),
+            rule("on added dell", "Desk"),
+            rule("on added", "Mirror"),
+        ];
+        let laptop = [connected("eDP-1", "BOE")];
+        let desk = [connected("eDP-1", "BOE"), connected("DP-1", "Dell")];
+        let tv = [connected("eDP-1", "BOE"), connected("HDMI-A-1", "Sony")];
+        let fired = |before: &[Output], after: &[Output]| {
+            first_fired(&rules, before, after, None, None).map(|rule| rule.action.clone())
+        };
+        assert_eq!(
+            fired(&laptop, &desk),
+            Some(Action::Profile("Desk".to_string()))
+        );
+        assert_eq!(
+            fired(&laptop, &tv),
+            Some(Action::Profile("Mirror".to_string()))
+        );
+        assert_eq!(
+            fired(&desk, &laptop),
+            Some(Action::Profile("Laptop".to_string()))
+        );
+        assert_eq!(fired(&desk, &desk), None);
+    }
+
+    #[test]
+    fn lid_rules_fire_when_the_lid_changes() {
+        let rules = [rule("on lid closed", "Clamshell")];
+        assert!(first_fired(&rules, &[], &[], Some(false), Some(true)).is_some());
+        assert!(first_fired(&rules, &[], &[], Some(true), Some(true)).is_none());
+        assert!(first_fired(&rules, &[], &[], None, Some(true)).is_none());
+    }
+
+    #[test]
+    fn gpu_changes_switch_to_the_best_match_by_default() {
+        assert_eq!(gpu_rule(&[], true).action, Action::BestMatch);
+        let rules = [rule("on gpu added", "Docked")];
+        assert_eq!(
+            gpu_rule(&rules, true).action,
+            Action::Profile("Docked".to_string())
+        );
+        assert_eq!(gpu_rule(&rules, false).event, Event::GpuRemoved);
+    }
+
+    #[test]
+    fn parses_at_rules() {
+        assert_eq!(
+            rule("at weekdays 09:00", "Work").event,
+            Event::At {
+                days: vec![1, 2, 3, 4, 5],
+                minute: 9 * 60,
+            }
+        );
+        assert_eq!(
+            rule("at Fri,mon,fri 18:30", "best").event,
+            Event::At {
+                days: vec![1, 5],
+                minute: 18 * 60 + 30,
+            }
+        );
+        assert_eq!(
+            rule("at 7:05", "Laptop").event.to_string(),
+            "at daily 07:05"
+        );
+        assert_eq!(Rule::parse("at 24:00", "Laptop"), None);
+        assert_eq!(Rule::parse("at someday 09:00", "Laptop"), None);
+        assert_eq!(Rule::parse("at mon 09:00 extra", "Laptop"), None);
+    }
+
+    #[test]
+    fn at_rules_print_as_written() {
+        for key in ["at weekends 10:00", "at mon,wed 08:15", "at daily 00:00"] {
+            assert_eq!(rule(key, "Laptop").event.to_string(), key);
+        }
+    }
+
+    #[test]
+    fn due_rules_match_the_day_and_minute() {
+        let rules = [
+            rule("at weekdays 09:00", "Work"),
+            rule("on added", "Desk"),
+            rule("at daily 09:00", "Morning"),
+            rule("at sat 09:00", "Weekend"),
+        ];
+        let names = |day, minute| {
+            due(&rules, day, minute)
+                .iter()
+                .map(|rule| rule.action.clone())
+                .collect::<Vec<_>>()
+        };
+        let profile = |name: &str| Action::Profile(name.to_string());
+        assert_eq!(names(1, 9 * 60), [profile("Work"), profile("Morning")]);
+        assert_eq!(names(6, 9 * 60), [profile("Morning"), profile("Weekend")]);
+        assert!(names(1, 9 * 60 + 1).is_empty());
+    }
+}
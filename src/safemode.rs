@@ -0,0 +1,135 @@
+// Falling back to safer modes when an output (typically a projector) does not
+// take the one a profile asks for, and remembering what worked per EDID
+
+use crate::config::DisplayConfig;
+use crate::output;
+use crate::state;
+use crate::sway::{self, Mode, Output};
+use crate::tr;
+use std::fs;
+use std::path::PathBuf;
+
+// Modes tried in turn when the requested one is rejected
+const SAFE_MODES: &[Mode] = &[
+    Mode {
+        width: 1920,
+        height: 1080,
+        refresh: 60000,
+    },
+    Mode {
+        width: 1280,
+        height: 720,
+        refresh: 60000,
+    },
+];
+
+// What checking the applied modes found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // Every output runs the mode its line asks for
+    Fine,
+    // At least one output needed a fallback mode, and got one
+    Recovered,
+    // An output took neither its mode nor any fallback
+    Failed,
+}
+
+// Modes that worked after a rejection, one line per output:
+// `<requested> <fallback> <identifier>`
+fn path() -> PathBuf {
+    state::state_dir().join("safe-modes")
+}
+
+fn load() -> Vec<(Mode, Mode, String)> {
+    fs::read_to_string(path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ' ');
+            let requested = output::parse_mode(fields.next()?)?;
+            let fallback = output::parse_mode(fields.next()?)?;
+            Some((requested, fallback, fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+// Remember that `fallback` works where `requested` did not, replacing what
+// was recorded for that output before
+fn record(output: &Output, requested: Mode, fallback: Mode) {
+    let identifier = output.identifier();
+    let mut records = load();
+    records.retain(|(mode, _, id)| !(*id == identifier && *mode == requested));
+    records.push((requested, fallback, identifier));
+    let contents: String = records
+        .iter()
+        .map(|(requested, fallback, id)| format!("{} {} {}\n", requested, fallback, id))
+        .collect();
+    let path = path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = fs::write(&path, contents) {
+        let failure = tr!("write-failed", path.display(), e);
+        eprintln!("{}", tr!("warning", failure));
+    }
+}
+
+// The fallback recorded for an output and requested mode, if any
+fn recorded(output: &Output, requested: Mode) -> Option<Mode> {
+    let identifier = output.identifier();
+    load()
+        .into_iter()
+        .find(|(mode, _, id)| *id == identifier && *mode == requested)
+        .map(|(_, fallback, _)| fallback)
+}
+
+// Check every output a profile gives a mode against what Sway reports and,
+// where the mode did not take, try the mode recorded for that EDID and then
+// the safe modes, largest first, never going above the requested size
+pub fn enforce(profile: &DisplayConfig) -> Outcome {
+    let Some(connected) = sway::get_outputs() else {
+        return Outcome::Fine;
+    };
+    let resolved = profile.resolved(&connected);
+    let mut outcome = Outcome::Fine;
+    // Each output is checked once, with the mode its last line gives it,
+    // whether that line names it or is an `output *` line
+    for output in &connected {
+        let Some(settings) = resolved.settings_for(&output.name, &connected) else {
+            continue;
+        };
+        let Some(requested) = settings.mode() else {
+            continue;
+        };
+        if settings.is_disabled() || output.shows_mode(&requested) {
+            continue;
+        }
+        let fits = |mode: &&Mode| {
+            mode.width * mode.height <= requested.width * requested.height && **mode != requested
+        };
+        let candidates: Vec<Mode> = recorded(output, requested)
+            .into_iter()
+            .chain(SAFE_MODES.iter().filter(fits).copied())
+            .collect();
+        let working = candidates.into_iter().find(|mode| {
+            sway::try_command(&format!("output \"{}\" enable mode {}", output.name, mode))
+                && sway::get_outputs()
+                    .and_then(|now| now.into_iter().find(|o| o.name == output.name))
+                    .is_some_and(|now| now.shows_mode(mode))
+        });
+        match working {
+            Some(mode) => {
+                println!("{}", tr!("safe-mode-used", output.name, requested, mode));
+                record(output, requested, mode);
+                if outcome == Outcome::Fine {
+                    outcome = Outcome::Recovered;
+                }
+            }
+            None => {
+                eprintln!("{}", tr!("safe-mode-none", output.name, requested));
+                outcome = Outcome::Failed;
+            }
+        }
+    }
+    outcome
+}
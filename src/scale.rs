@@ -0,0 +1,30 @@
+// DPI computation and scale suggestions from EDID physical dimensions
+
+use crate::roles;
+use crate::sway::{Mode, Output};
+
+// Pixel density a scale of 1 is designed for, for desktop monitors and for
+// laptop panels, which are viewed from closer up
+const DESKTOP_DPI: f64 = 96.0;
+const LAPTOP_DPI: f64 = 120.0;
+
+// Horizontal pixel density of an output in a given mode, when its size is known
+pub fn dpi(output: &Output, mode: &Mode) -> Option<f64> {
+    // Projectors and many TVs report no size or a placeholder aspect ratio in cm
+    if output.physical_width <= 10 || output.physical_height <= 10 {
+        return None;
+    }
+    Some(mode.width as f64 / (output.physical_width as f64 / 25.4))
+}
+
+// A scale that brings an output close to its target density, in quarter steps
+pub fn suggest(output: &Output, mode: &Mode) -> Option<f64> {
+    let dpi = dpi(output, mode)?;
+    let target = if roles::is_internal(&output.name) {
+        LAPTOP_DPI
+    } else {
+        DESKTOP_DPI
+    };
+    let scale = ((dpi / target) * 4.0).round() / 4.0;
+    Some(scale.clamp(1.0, 3.0))
+}
@@ -0,0 +1,214 @@
+// The managed section read into the blocks it is made of, so it can be written
+// back without losing anything. Each line is classified into a token, and a
+// two-state machine (before the first profile header, inside a profile) groups
+// them: text before the first header, then one block per profile holding its
+// header line and the lines below it exactly as they were read. Rendering the
+// parsed profiles unchanged gives the input back line for line; a profile that
+// was changed is written in the canonical style, the others keep their spacing,
+// indentation, blank lines and comments.
+
+use crate::config::{self, DisplayConfig, CHECKSUM_PREFIX};
+
+// What one line of the section is
+enum Token {
+    // The checksum comment, which is regenerated on every write
+    Checksum,
+    Header(DisplayConfig),
+    Body,
+}
+
+fn tokenize(line: &str) -> Token {
+    if line.starts_with(CHECKSUM_PREFIX) {
+        Token::Checksum
+    } else if let Some(header) = config::parse_header(line) {
+        Token::Header(header)
+    } else {
+        Token::Body
+    }
+}
+
+enum State {
+    // Before the first header: lines belong to no profile
+    Preamble,
+    Profile(Block),
+}
+
+// A profile as written: its header line, the lines up to the next header
+// (blank lines and comments included), and what they parse to
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub config: DisplayConfig,
+    header: String,
+    body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Section {
+    // Lines before the first header, kept where they are
+    pub preamble: Vec<String>,
+    pub blocks: Vec<Block>,
+}
+
+impl Section {
+    pub fn parse<'a, I>(lines: I) -> Section
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut section = Section::default();
+        let mut state = State::Preamble;
+        for line in lines {
+            state = match (state, tokenize(line)) {
+                (state, Token::Checksum) => state,
+                (State::Preamble, Token::Header(config)) => {
+                    State::Profile(Block::new(config, line))
+                }
+                (State::Preamble, Token::Body) => {
+                    section.preamble.push(line.clone());
+                    State::Preamble
+                }
+                (State::Profile(block), Token::Header(config)) => {
+                    section.blocks.push(block);
+                    State::Profile(Block::new(config, line))
+                }
+                (State::Profile(mut block), Token::Body) => {
+                    block.push(line);
+                    State::Profile(block)
+                }
+            };
+        }
+        if let State::Profile(block) = state {
+            section.blocks.push(block);
+        }
+        section
+    }
+
+    // The profiles, in the order they appear
+    pub fn configs(&self) -> Vec<DisplayConfig> {
+        self.blocks
+            .iter()
+            .map(|block| block.config.clone())
+            .collect()
+    }
+
+    // The section lines for `configs`, without the checksum comment. Each
+    // profile takes the place of the first unused block with its description:
+    // the block's header line is kept while the header says the same, and its
+    // other lines while they give the same output lines, commented the same
+    // way. Anything else is rendered afresh.
+    pub fn render(&self, configs: &[DisplayConfig]) -> Vec<String> {
+        let mut lines = self.preamble.clone();
+        let mut used = vec![false; self.blocks.len()];
+        let separated = self.separated();
+        // Whether the previous profile came from a block that had another
+        // after it, whose own trailing lines are then the separator as read
+        let mut separator_kept = false;
+        for (index, config) in configs.iter().enumerate() {
+            // Otherwise a blank line between profiles, if the section is laid
+            // out with them
+            if index > 0
+                && !separator_kept
+                && separated
+                && lines.last().is_some_and(|line| !line.trim().is_empty())
+            {
+                lines.push(String::new());
+            }
+            let original = (0..self.blocks.len())
+                .find(|&i| !used[i] && self.blocks[i].config.description == config.description);
+            let Some(original) = original else {
+                lines.push(config::render_header(config));
+                lines.extend(config::render_outputs(config));
+                separator_kept = false;
+                continue;
+            };
+            used[original] = true;
+            separator_kept = original + 1 < self.blocks.len();
+            let block = &self.blocks[original];
+
+            if block.same_header(config) {
+                lines.push(block.header.clone());
+            } else {
+                lines.push(config::render_header(config));
+            }
+            let fresh = config::render_outputs(config);
+            if block.same_body(config, &fresh) {
+                lines.extend(block.body.iter().cloned());
+            } else {
+                lines.extend(fresh);
+                lines.extend(block.trailing_blanks().iter().cloned());
+            }
+        }
+        // The last profile ends the way the last one read did, not with the
+        // separator its block had for a profile no longer after it
+        if let (true, Some(last)) = (separator_kept, self.blocks.last()) {
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                lines.pop();
+            }
+            lines.extend(last.trailing_blanks().iter().cloned());
+        }
+        lines
+    }
+
+    // Whether profiles are set apart by blank lines: as the ones read are, and
+    // as in the canonical style when fewer than two were read to tell by
+    fn separated(&self) -> bool {
+        match self.blocks.split_last() {
+            Some((_, rest)) if !rest.is_empty() => {
+                rest.iter().any(|block| !block.trailing_blanks().is_empty())
+            }
+            _ => true,
+        }
+    }
+
+    // The section lines for `configs` in the canonical style, keeping only the
+    // lines before the first header as they are
+    pub fn render_canonical(&self, configs: &[DisplayConfig]) -> Vec<String> {
+        let mut lines = self.preamble.clone();
+        lines.extend(config::render_section(configs));
+        lines
+    }
+}
+
+impl Block {
+    fn new(config: DisplayConfig, header: &str) -> Block {
+        Block {
+            config,
+            header: header.to_string(),
+            body: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        if let Some(entry) = config::profile_line(line) {
+            self.config.outputs.push(entry);
+        }
+        self.body.push(line.to_string());
+    }
+
+    fn same_header(&self, config: &DisplayConfig) -> bool {
+        self.config.description == config.description
+            && self.config.status == config.status
+            && self.config.metadata == config.metadata
+    }
+
+    // Whether the lines as written still say what `fresh` would: the same
+    // output lines, each commented out or not like its fresh rendering
+    fn same_body(&self, config: &DisplayConfig, fresh: &[String]) -> bool {
+        self.config.outputs == config.outputs
+            && self
+                .body
+                .iter()
+                .filter(|line| config::profile_line(line).is_some())
+                .map(|line| line.trim_start().starts_with('#'))
+                .eq(fresh.iter().map(|line| line.starts_with('#')))
+    }
+
+    // The blank lines that end the block, separating it from the next one
+    fn trailing_blanks(&self) -> &[String] {
+        let kept = self
+            .body
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |index| index + 1);
+        &self.body[kept..]
+    }
+}
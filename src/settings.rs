@@ -0,0 +1,172 @@
+// Preferences for the program itself, read from
+// `$XDG_CONFIG_HOME/sway-display-switcher/config` as `key = value` lines
+
+use crate::matching::{MatchMode, Policy, TieBreak};
+use crate::mqtt::Broker;
+use crate::rules::Rule;
+use crate::tr;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub matching: Policy,
+    // How long output events must stay quiet before --watch acts on them
+    pub settle_delay: Duration,
+    // How long --watch waits after a resume from suspend before checking the
+    // outputs, as docks take a while to bring them back
+    pub resume_delay: Duration,
+    // `on <event> = <profile>` and `at <time> = <profile>` rules for --watch,
+    // in file order
+    pub rules: Vec<Rule>,
+    // URLs each profile change is POSTed to as JSON; `webhook =` may repeat
+    pub webhooks: Vec<String>,
+    // Where to publish the active profile and take switch commands from
+    pub mqtt: Option<Broker>,
+    // Put windows back where they were when returning to a profile
+    pub remember_windows: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            matching: Policy::default(),
+            settle_delay: Duration::from_millis(500),
+            resume_delay: Duration::from_secs(2),
+            rules: Vec::new(),
+            webhooks: Vec::new(),
+            mqtt: None,
+            remember_windows: false,
+        }
+    }
+}
+
+// Location of the settings file
+pub fn path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("sway-display-switcher")
+        .join("config")
+}
+
+// Load the settings file. A missing file gives the defaults; unknown keys and
+// invalid values are warned about and ignored.
+pub fn load() -> Settings {
+    let mut settings = Settings::default();
+    let Ok(contents) = fs::read_to_string(path()) else {
+        return settings;
+    };
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let applied = line
+            .split_once('=')
+            .is_some_and(|(key, value)| settings.set(key.trim(), value.trim()));
+        if !applied {
+            eprintln!(
+                "{}",
+                tr!(
+                    "warning",
+                    tr!("setting-ignored", path().display(), number + 1, line)
+                )
+            );
+        }
+    }
+    settings
+}
+
+impl Settings {
+    // Apply one `key = value` line, reporting whether it was understood
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let matching = &mut self.matching;
+        match key {
+            "match_mode" => match value {
+                "subset" => matching.mode = MatchMode::Subset,
+                "exact" => matching.mode = MatchMode::Exact,
+                _ => return false,
+            },
+            "tie_break" => match value {
+                "file" => matching.tie_break = TieBreak::File,
+                "priority" => matching.tie_break = TieBreak::Priority,
+                _ => return false,
+            },
+            "description_weight" | "connector_weight" | "unconfigured_penalty" => {
+                let Ok(number) = value.parse() else {
+                    return false;
+                };
+                match key {
+                    "description_weight" => matching.description_weight = number,
+                    "connector_weight" => matching.connector_weight = number,
+                    _ => matching.unconfigured_penalty = number,
+                }
+            }
+            "settle_ms" | "resume_delay_ms" => match value.parse() {
+                Ok(ms) if key == "settle_ms" => self.settle_delay = Duration::from_millis(ms),
+                Ok(ms) => self.resume_delay = Duration::from_millis(ms),
+                Err(_) => return false,
+            },
+            "remember_windows" => match value {
+                "yes" | "true" | "on" | "1" => self.remember_windows = true,
+                "no" | "false" | "off" | "0" => self.remember_windows = false,
+                _ => return false,
+            },
+            "webhook" if !value.is_empty() => self.webhooks.push(value.to_string()),
+            "mqtt_host" if !value.is_empty() => match &mut self.mqtt {
+                Some(broker) => broker.host = value.to_string(),
+                None => self.mqtt = Some(Broker::new(value)),
+            },
+            "mqtt_port" | "mqtt_topic" | "mqtt_username" | "mqtt_password" => {
+                // The broker is configured by mqtt_host, which must come first
+                let Some(broker) = &mut self.mqtt else {
+                    return false;
+                };
+                match key {
+                    "mqtt_port" => match value.parse() {
+                        Ok(port) => broker.port = port,
+                        Err(_) => return false,
+                    },
+                    "mqtt_topic" => broker.topic = value.trim_end_matches('/').to_string(),
+                    "mqtt_username" => broker.username = Some(value.to_string()),
+                    _ => broker.password = Some(value.to_string()),
+                }
+            }
+            _ if key.starts_with("on") || key.starts_with("at") => match Rule::parse(key, value) {
+                Some(rule) => self.rules.push(rule),
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_the_matching_policy() {
+        let mut settings = Settings::default();
+        assert!(settings.set("match_mode", "exact"));
+        assert!(settings.set("description_weight", "5"));
+        assert!(settings.set("connector_weight", "1"));
+        assert!(settings.set("tie_break", "file"));
+        let policy = settings.matching;
+        assert_eq!(policy.mode, MatchMode::Exact);
+        assert_eq!((policy.description_weight, policy.connector_weight), (5, 1));
+        assert_eq!(policy.unconfigured_penalty, 1);
+        assert_eq!(policy.tie_break, TieBreak::File);
+    }
+
+    #[test]
+    fn refuses_invalid_policy_values() {
+        let mut settings = Settings::default();
+        assert!(!settings.set("match_mode", "loose"));
+        assert!(!settings.set("connector_weight", "two"));
+        assert!(!settings.set("tie_break", "name"));
+        assert_eq!(settings.matching, Policy::default());
+    }
+}
@@ -0,0 +1,203 @@
+// Small persistent state shared across invocations, kept under XDG_STATE_HOME
+
+use crate::tr;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A switch that should be undone unless confirmed before the deadline
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingRevert {
+    pub profile: String,
+    pub deadline: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct State {
+    pub last_profile: Option<String>,
+    pub previous_profile: Option<String>,
+    pub last_switch: Option<u64>,
+    pub pending_revert: Option<PendingRevert>,
+    // When each profile was last applied, by description
+    pub usage: Vec<(String, u64)>,
+    // What the outputs were last set to without saving it to the config (a
+    // transient switch, `test`, `present`, ...): a name and its output lines.
+    // The watch daemon holds to it instead of the Enabled profile.
+    pub live_profile: Option<String>,
+    pub live_outputs: Vec<String>,
+}
+
+impl State {
+    // Load the state file, falling back to an empty state if it is missing or unreadable
+    pub fn load() -> State {
+        let mut state = State::default();
+        let Ok(contents) = fs::read_to_string(state_path()) else {
+            return state;
+        };
+        let mut revert_profile = None;
+        let mut revert_deadline = None;
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once(" = ") else {
+                continue;
+            };
+            let value = value.to_string();
+            match key {
+                "last_profile" => state.last_profile = Some(value),
+                "previous_profile" => state.previous_profile = Some(value),
+                "last_switch" => state.last_switch = value.parse().ok(),
+                "pending_revert_profile" => revert_profile = Some(value),
+                "pending_revert_deadline" => revert_deadline = value.parse().ok(),
+                "live_profile" => state.live_profile = Some(value),
+                "live_output" => state.live_outputs.push(value),
+                "used" => {
+                    if let Some((time, profile)) = value.split_once(' ') {
+                        if let Ok(time) = time.parse() {
+                            state.usage.push((profile.to_string(), time));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let (Some(profile), Some(deadline)) = (revert_profile, revert_deadline) {
+            state.pending_revert = Some(PendingRevert { profile, deadline });
+        }
+        state
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = state_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = String::new();
+        if let Some(profile) = &self.last_profile {
+            contents.push_str(&format!("last_profile = {}\n", profile));
+        }
+        if let Some(profile) = &self.previous_profile {
+            contents.push_str(&format!("previous_profile = {}\n", profile));
+        }
+        if let Some(time) = self.last_switch {
+            contents.push_str(&format!("last_switch = {}\n", time));
+        }
+        if let Some(revert) = &self.pending_revert {
+            contents.push_str(&format!("pending_revert_profile = {}\n", revert.profile));
+            contents.push_str(&format!("pending_revert_deadline = {}\n", revert.deadline));
+        }
+        for (profile, time) in &self.usage {
+            contents.push_str(&format!("used = {} {}\n", time, profile));
+        }
+        if let Some(profile) = &self.live_profile {
+            contents.push_str(&format!("live_profile = {}\n", profile));
+            for line in &self.live_outputs {
+                contents.push_str(&format!("live_output = {}\n", line));
+            }
+        }
+        fs::write(path, contents)
+    }
+
+    // Remember that a profile was just applied. A switch waiting to be
+    // confirmed is settled by it: it is not reverted over a later choice.
+    pub fn record_switch(&mut self, profile: &str) {
+        self.pending_revert = None;
+        if self.last_profile.as_deref() != Some(profile) {
+            self.previous_profile = self.last_profile.take();
+        }
+        self.last_profile = Some(profile.to_string());
+        self.last_switch = Some(now());
+        self.usage.retain(|(used, _)| used != profile);
+        self.usage.push((profile.to_string(), now()));
+    }
+
+    // When a profile was last applied, if ever
+    pub fn last_used(&self, profile: &str) -> Option<u64> {
+        self.usage
+            .iter()
+            .find(|(used, _)| used == profile)
+            .map(|(_, time)| *time)
+    }
+}
+
+// Keep the switch waiting to be confirmed, returning what it would have been
+// reverted to; with a `deadline`, only the switch armed with it
+pub fn confirm_pending(deadline: Option<u64>) -> Option<PendingRevert> {
+    let mut state = State::load();
+    let pending = state
+        .pending_revert
+        .take()
+        .filter(|revert| deadline.is_none_or(|deadline| revert.deadline == deadline))?;
+    if let Err(e) = state.save() {
+        eprintln!("{}", tr!("warning", tr!("state-write-failed", e)));
+    }
+    Some(pending)
+}
+
+// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// The seat whose state this invocation reads and writes; None is the default seat
+static SEAT: OnceLock<Option<String>> = OnceLock::new();
+
+// Keep state for a named seat separate from the default one
+pub fn set_seat(seat: Option<String>) {
+    let _ = SEAT.set(seat);
+}
+
+// The seat selected with set_seat, if any
+pub fn seat() -> Option<&'static str> {
+    SEAT.get().and_then(Option::as_deref)
+}
+
+// Directory holding this seat's state files
+pub fn state_dir() -> PathBuf {
+    let base = dirs::state_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("sway-display-switcher");
+    match seat() {
+        Some(seat) => base.join("seats").join(seat),
+        None => base,
+    }
+}
+
+fn state_path() -> PathBuf {
+    state_dir().join("state")
+}
+
+// Record a switch in the state file, warning rather than failing if it cannot be written
+pub fn record_switch(profile: &str) {
+    update(|state| state.record_switch(profile));
+}
+
+// Record outputs set up over IPC only, as `description` with these lines
+pub fn record_live(description: &str, outputs: &[String]) {
+    update(|state| {
+        state.live_profile = Some(description.to_string());
+        state.live_outputs = outputs.to_vec();
+    });
+}
+
+// Forget the outputs recorded by record_live, once the Enabled profile is
+// what they should show again
+pub fn clear_live() {
+    if State::load().live_profile.is_some() {
+        update(|state| {
+            state.live_profile = None;
+            state.live_outputs.clear();
+        });
+    }
+}
+
+fn update(change: impl FnOnce(&mut State)) {
+    let mut state = State::load();
+    change(&mut state);
+    if let Err(e) = state.save() {
+        eprintln!("{}", tr!("warning", tr!("state-write-failed", e)));
+    }
+}
@@ -0,0 +1,376 @@
+use crate::config::DisplayConfig;
+use crate::output::{self, OutputLine};
+use crate::remote;
+use crate::tr;
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process;
+use std::sync::OnceLock;
+
+// The Sway IPC socket chosen for this invocation; None means swaymsg's default
+static SOCKET: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// Direct every following swaymsg call at a specific Sway instance
+pub fn set_socket(socket: Option<PathBuf>) {
+    let _ = SOCKET.set(socket);
+}
+
+pub fn socket() -> Option<&'static PathBuf> {
+    SOCKET.get().and_then(Option::as_ref)
+}
+
+// A swaymsg command addressed to the selected Sway instance, on this machine
+// or the remote host
+fn swaymsg(args: &[&str]) -> process::Command {
+    let mut words: Vec<String> = Vec::new();
+    if let Some(socket) = socket() {
+        words.push("-s".to_string());
+        words.push(socket.to_string_lossy().into_owned());
+    }
+    words.extend(args.iter().map(|arg| arg.to_string()));
+    remote::swaymsg(words)
+}
+
+// Find the IPC sockets of running Sway instances in XDG_RUNTIME_DIR, ignoring
+// stale ones left behind by crashed sessions
+pub fn list_sockets() -> Vec<PathBuf> {
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    let Ok(entries) = fs::read_dir(runtime_dir) else {
+        return Vec::new();
+    };
+    let mut sockets: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("sway-ipc.") && name.ends_with(".sock"))
+        })
+        .filter(|path| UnixStream::connect(path).is_ok())
+        .collect();
+    sockets.sort();
+    sockets
+}
+
+// Run a single command through swaymsg, reporting whether Sway accepted it.
+// swaymsg's reply is kept quiet unless Sway rejects the command, so callers
+// such as `restore` print nothing on success.
+pub fn run_command(command: &str) -> bool {
+    let Ok(reply) = swaymsg(&[command]).output() else {
+        return false;
+    };
+    if !reply.status.success() {
+        report_rejection(&reply);
+    }
+    reply.status.success()
+}
+
+// Run a command that is allowed to fail, such as a rule for windows that may
+// not exist, without printing anything either way
+pub fn try_command(command: &str) -> bool {
+    swaymsg(&[command])
+        .output()
+        .is_ok_and(|reply| reply.status.success())
+}
+
+// Pass on why Sway refused a request: swaymsg says so on standard error, or
+// in the reply on standard output
+fn report_rejection(reply: &process::Output) {
+    let stderr = String::from_utf8_lossy(&reply.stderr);
+    let stdout = String::from_utf8_lossy(&reply.stdout);
+    let reason = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if !reason.is_empty() {
+        eprintln!("{}", reason);
+    }
+}
+
+// Send each output line of a config straight to Sway without touching the
+// file. Conditional and `?identity` lines are resolved against the outputs
+// connected now.
+pub fn apply_outputs(config: &DisplayConfig) -> bool {
+    let connected = get_outputs().unwrap_or_default();
+    run_commands(&output_commands(config, &connected))
+}
+
+// The commands apply_outputs sends for a config: conditions resolved and
+// outputs re-identified against `connected`, each line in its runtime form
+pub fn output_commands(config: &DisplayConfig, connected: &[Output]) -> Vec<String> {
+    let resolved;
+    let config = if config.has_conditions() || !config.identities().is_empty() {
+        resolved = config.resolved(connected);
+        &resolved
+    } else {
+        config
+    };
+    config
+        .outputs
+        .iter()
+        .filter(|line| !output::is_comment(line))
+        .flat_map(|line| runtime_commands(line, connected))
+        .collect()
+}
+
+// Run each command, going on past rejected ones; whether Sway took them all
+pub fn run_commands(commands: &[String]) -> bool {
+    let mut success = true;
+    for command in commands {
+        if !run_command(command) {
+            eprintln!("{}", tr!("command-rejected", command));
+            success = false;
+        }
+    }
+    success
+}
+
+// The commands sending one profile line over IPC. At runtime Sway keeps an
+// `output *` line as a default it merges into every output configured later,
+// including by the next profile switched to, so the line is sent once for each
+// connected output instead, in its place among the other lines.
+fn runtime_commands(line: &str, connected: &[Output]) -> Vec<String> {
+    let line = runtime_form(line);
+    let wildcard = OutputLine::parse(&line).is_some_and(|parsed| parsed.is_wildcard());
+    if !wildcard || connected.is_empty() {
+        return vec![line];
+    }
+    connected
+        .iter()
+        .filter_map(|output| output::replace_target(&line, &output.name))
+        .collect()
+}
+
+// Sides `gaps` can be given for, in the config file and over IPC
+const GAP_KINDS: &[&str] = &[
+    "inner",
+    "outer",
+    "horizontal",
+    "vertical",
+    "top",
+    "right",
+    "bottom",
+    "left",
+];
+
+// A profile line as Sway accepts it at runtime. Most commands read the same in
+// the config file and over IPC, but the config form `gaps inner 20` has to
+// become `gaps inner all set 20` to change the gaps of every workspace.
+fn runtime_form(line: &str) -> String {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["gaps", kind, amount] if GAP_KINDS.contains(kind) => {
+            format!("gaps {} all set {}", kind, amount)
+        }
+        _ => line.to_string(),
+    }
+}
+
+// Reload Sway configuration, waiting for Sway to finish so that commands sent
+// afterwards are not overridden by it. Returns whether Sway took the config.
+pub fn reload() -> bool {
+    match swaymsg(&["reload"]).output() {
+        Ok(reply) if reply.status.success() => {
+            println!("{}", tr!("reloaded"));
+            true
+        }
+        Ok(reply) => {
+            report_rejection(&reply);
+            eprintln!("{}", tr!("reload-failed"));
+            false
+        }
+        Err(e) => {
+            eprintln!("{}", tr!("reload-failed-because", e));
+            false
+        }
+    }
+}
+
+// A video mode; refresh is in millihertz as reported by Sway
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Mode {
+    pub width: i64,
+    pub height: i64,
+    pub refresh: i64,
+}
+
+// Formatted the way `output <name> mode` expects it
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if self.refresh > 0 {
+            write!(f, "@{}Hz", self.refresh as f64 / 1000.0)?;
+        }
+        Ok(())
+    }
+}
+
+// Live state of an output as reported by get_outputs
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "OutputReply")]
+pub struct Output {
+    pub name: String,
+    pub make: String,
+    pub model: String,
+    pub serial: String,
+    pub active: bool,
+    pub width: i64,
+    pub height: i64,
+    pub refresh: i64,
+    pub x: i64,
+    pub y: i64,
+    pub scale: f64,
+    pub transform: String,
+    pub modes: Vec<Mode>,
+    // Physical size of the panel in millimetres from the EDID (0 when unknown)
+    pub physical_width: i64,
+    pub physical_height: i64,
+}
+
+impl Output {
+    pub fn current_mode(&self) -> Mode {
+        Mode {
+            width: self.width,
+            height: self.height,
+            refresh: self.refresh,
+        }
+    }
+}
+
+// An output as get_outputs reports it. Sway leaves some fields out, or null,
+// for a disabled output; those read as empty.
+#[derive(Deserialize)]
+struct OutputReply {
+    name: Option<String>,
+    make: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+    active: Option<bool>,
+    current_mode: Option<Mode>,
+    rect: Option<Position>,
+    scale: Option<f64>,
+    transform: Option<String>,
+    #[serde(default)]
+    modes: Vec<Mode>,
+    physical_width: Option<i64>,
+    physical_height: Option<i64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Position {
+    x: i64,
+    y: i64,
+}
+
+impl From<OutputReply> for Output {
+    fn from(reply: OutputReply) -> Output {
+        let mode = reply.current_mode.unwrap_or_default();
+        let rect = reply.rect.unwrap_or_default();
+        Output {
+            name: reply.name.unwrap_or_default(),
+            make: reply.make.unwrap_or_default(),
+            model: reply.model.unwrap_or_default(),
+            serial: reply.serial.unwrap_or_default(),
+            active: reply.active.unwrap_or(false),
+            width: mode.width,
+            height: mode.height,
+            refresh: mode.refresh,
+            x: rect.x,
+            y: rect.y,
+            scale: reply.scale.unwrap_or(1.0),
+            transform: reply.transform.unwrap_or_default(),
+            modes: reply.modes,
+            physical_width: reply.physical_width.unwrap_or(0),
+            physical_height: reply.physical_height.unwrap_or(0),
+        }
+    }
+}
+
+// The release of the running Sway
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    #[serde(default)]
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn at_least(self, (major, minor): (u64, u64)) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// Asked once per run; a Sway upgrade only takes effect when Sway restarts,
+// which ends a running daemon's session too
+static VERSION: OnceLock<Option<Version>> = OnceLock::new();
+
+// The version of the running Sway, None when it cannot be asked
+pub fn version() -> Option<Version> {
+    *VERSION.get_or_init(|| {
+        let reply = swaymsg(&["-r", "-t", "get_version"]).output().ok()?;
+        if !reply.status.success() {
+            return None;
+        }
+        serde_json::from_slice(&reply.stdout).ok()
+    })
+}
+
+// Whether the running Sway knows an output subcommand; taken for granted
+// when its version is unknown
+pub fn supports(key: &str) -> bool {
+    match (version(), output::introduced_in(key)) {
+        (Some(version), Some(since)) => version.at_least(since),
+        _ => true,
+    }
+}
+
+// `power`, or `dpms` for a Sway older than 1.7
+pub fn power_keyword() -> &'static str {
+    if supports("power") {
+        "power"
+    } else {
+        "dpms"
+    }
+}
+
+// Query the currently connected outputs
+pub fn get_outputs() -> Option<Vec<Output>> {
+    let reply = swaymsg(&["-r", "-t", "get_outputs"]).output().ok()?;
+    if !reply.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&reply.stdout).ok()
+}
+
+// Query Sway's layout tree: outputs, workspaces and windows
+pub fn get_tree() -> Option<serde_json::Value> {
+    let reply = swaymsg(&["-r", "-t", "get_tree"]).output().ok()?;
+    if !reply.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&reply.stdout).ok()
+}
+
+// Start a swaymsg subscription that prints one JSON event per line
+pub fn subscribe(events: &str) -> io::Result<process::Child> {
+    swaymsg(&["-r", "-m", "-t", "subscribe", events])
+        .stdout(process::Stdio::piped())
+        .spawn()
+}
@@ -0,0 +1,230 @@
+// Activating profiles; shared by the command line and any other frontend
+
+use crate::config::{self, DisplayConfig, SwayConfig};
+use crate::error::{self, Error};
+use crate::plan::Plan;
+use crate::safemode::{self, Outcome};
+use crate::tr;
+use crate::{audit, effects, lint, matching, output, state, sway, windows};
+use std::sync::OnceLock;
+
+static FORCE: OnceLock<bool> = OnceLock::new();
+
+// Apply profiles even when they would turn off every connected output (`--force`)
+pub fn set_force(force: bool) {
+    let _ = FORCE.set(force);
+}
+
+// Refuse a profile that would leave no connected output on, and with it no
+// screen to undo the switch from, unless forced
+fn guard_blackout(profile: &DisplayConfig) -> error::Result<()> {
+    if FORCE.get().copied().unwrap_or(false) {
+        return Ok(());
+    }
+    match sway::get_outputs() {
+        Some(connected) if matching::leaves_all_off(profile, &connected) => {
+            Err(Error::Failed(tr!("blackout", profile.description)))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Record in the switch log why a switch stopped, and hand the error on
+fn failed(pending: audit::Pending, error: Error) -> Error {
+    pending.finish(Some(error.to_string()));
+    error
+}
+
+// Point out lines the running Sway is too old for before they are applied
+fn warn_unsupported(profile: &DisplayConfig) {
+    let Some(version) = sway::version() else {
+        return;
+    };
+    for finding in lint::check_support(std::slice::from_ref(profile), version) {
+        let finding = format!("{}: {}.", finding.line, finding.message);
+        eprintln!("{}", tr!("warning", finding));
+    }
+}
+
+// Whether switching to a profile would change nothing: Sway's outputs already
+// match it and, unless `transient`, it is the Enabled profile in the file, so
+// rewriting the config and reloading Sway can be skipped
+pub fn already_active(profile: &DisplayConfig, transient: bool) -> bool {
+    (transient || profile.is_enabled())
+        && sway::get_outputs().is_some_and(|connected| matching::in_effect(profile, &connected))
+}
+
+// Apply a profile via IPC only, leaving the config file untouched. Fails
+// when Sway rejects a line. `trigger` says what asked for the switch, for the
+// switch log.
+pub fn switch_transient(profile: &DisplayConfig, trigger: &str) -> error::Result<()> {
+    guard_blackout(profile)?;
+    warn_unsupported(profile);
+    let previous = state::State::load().last_profile;
+    let pending = audit::start(trigger, &profile.description, previous.as_deref(), false);
+    // Recorded first, so that the watch daemon finds it when the outputs change
+    state::record_live(&profile.description, &profile.outputs);
+    let applied = sway::apply_outputs(profile);
+    // A rejected mode that a safe mode stood in for still counts as applied
+    let applied = match safemode::enforce(profile) {
+        Outcome::Fine => applied,
+        Outcome::Recovered => true,
+        Outcome::Failed => false,
+    };
+    if !applied {
+        return Err(failed(pending, Error::Sway(tr!("apply-failed"))));
+    }
+    state::record_switch(&profile.description);
+    effects::apply(profile);
+    windows::restore(profile);
+    pending.finish(None);
+    Ok(())
+}
+
+// Mark the selected profile Enabled in the config file and reload Sway
+pub fn switch_persistent(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    selected_index: usize,
+    trigger: &str,
+) -> error::Result<()> {
+    persist(config, display_configs, selected_index, None, trigger)
+}
+
+// Mark the selected profile Enabled in the config file, but only apply
+// `subset` (some of its lines, see DisplayConfig::restricted_to) over IPC
+// instead of reloading, so the outputs it leaves out are not touched
+pub fn switch_partial(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    selected_index: usize,
+    subset: &DisplayConfig,
+    trigger: &str,
+) -> error::Result<()> {
+    persist(
+        config,
+        display_configs,
+        selected_index,
+        Some(subset),
+        trigger,
+    )
+}
+
+fn persist(
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    selected_index: usize,
+    subset: Option<&DisplayConfig>,
+    trigger: &str,
+) -> error::Result<()> {
+    guard_blackout(subset.unwrap_or(&display_configs[selected_index]))?;
+    warn_unsupported(subset.unwrap_or(&display_configs[selected_index]));
+    let previous = config::enabled_config(display_configs).map(|c| c.description.as_str());
+    let pending = audit::start(
+        trigger,
+        &display_configs[selected_index].description,
+        previous,
+        true,
+    );
+
+    if let Some(leaving) = config::enabled_config(display_configs) {
+        windows::save(leaving);
+    }
+
+    // Update display_configs: set selected to Enabled, others to Disabled. The
+    // file holds the selected profile with its outputs on today's connectors,
+    // as Sway reads the lines literally.
+    let mut updated_display_configs = config::activate(display_configs, selected_index);
+    if let Some(connected) = sway::get_outputs() {
+        let selected = &mut updated_display_configs[selected_index];
+        *selected = selected.reidentified(&connected);
+    }
+    if let Err(e) = config.save(&updated_display_configs) {
+        return Err(failed(pending, e));
+    }
+    state::record_switch(&display_configs[selected_index].description);
+    // Outputs a partial switch leaves out stay as they are, not as the file has them
+    match subset {
+        Some(subset) => state::record_live(&subset.description, &subset.outputs),
+        None => state::clear_live(),
+    }
+
+    match subset {
+        Some(subset) => {
+            if !sway::apply_outputs(subset) {
+                return Err(failed(pending, Error::Sway(tr!("apply-failed"))));
+            }
+        }
+        None => {
+            if !sway::reload() {
+                let error = Error::Sway(tr!("reload-rejected"));
+                return Err(failed(pending, error));
+            }
+            // The file holds conditional lines only as comments
+            let profile = &display_configs[selected_index];
+            if profile.has_conditions() {
+                let mut conditional = profile.clone();
+                conditional
+                    .outputs
+                    .retain(|line| output::split_condition(line).is_some());
+                if !sway::apply_outputs(&conditional) {
+                    let error = Error::Sway(tr!("conditional-rejected"));
+                    return Err(failed(pending, error));
+                }
+            }
+        }
+    }
+    if safemode::enforce(subset.unwrap_or(&display_configs[selected_index])) == Outcome::Failed {
+        let error = Error::Sway(tr!("safe-mode-failed"));
+        return Err(failed(pending, error));
+    }
+    effects::apply(&display_configs[selected_index]);
+    windows::restore(&display_configs[selected_index]);
+    pending.finish(None);
+    Ok(())
+}
+
+// Execute a plan made by `plan` with the same guards and side effects as a
+// switch: the profile has to leave an output on, the switch is logged, and
+// its effects and windows follow
+pub fn apply_plan(
+    plan: &Plan,
+    config: &SwayConfig,
+    display_configs: &[DisplayConfig],
+    trigger: &str,
+) -> error::Result<()> {
+    plan.check(config)?;
+    let profile = config::find_config(display_configs, &plan.description)
+        .map(|index| &display_configs[index])
+        .ok_or_else(|| Error::NoSuchProfile(plan.description.clone()))?;
+    guard_blackout(profile)?;
+    warn_unsupported(profile);
+    let persistent = plan.new_config.is_some();
+    let enabled = config::enabled_config(display_configs);
+    let previous = if persistent {
+        enabled.map(|c| c.description.clone())
+    } else {
+        state::State::load().last_profile
+    };
+    let pending = audit::start(trigger, &plan.description, previous.as_deref(), persistent);
+    if let (true, Some(leaving)) = (persistent, enabled) {
+        windows::save(leaving);
+    }
+    if persistent {
+        state::clear_live();
+    } else {
+        state::record_live(&profile.description, &profile.outputs);
+    }
+    if let Err(e) = plan.apply(config) {
+        return Err(failed(pending, e));
+    }
+    if safemode::enforce(profile) == Outcome::Failed {
+        let error = Error::Sway(tr!("safe-mode-failed"));
+        return Err(failed(pending, error));
+    }
+    state::record_switch(&plan.description);
+    effects::apply(profile);
+    windows::restore(profile);
+    pending.finish(None);
+    Ok(())
+}
@@ -0,0 +1,177 @@
+// A GTK4/libadwaita window listing the profiles with a preview of their
+// layout, for switching with a click instead of a terminal
+
+use adw::prelude::*;
+use gtk::{cairo, glib};
+use std::path::Path;
+use sway_display_switcher::config::{DisplayConfig, SwayConfig};
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::layout::{self, Rect};
+use sway_display_switcher::{matching, sway, switcher, tr};
+
+const APP_ID: &str = "io.github.jefrecantuledesma.SwayDisplaySwitcher";
+
+// Size of the layout preview at the start of each row
+const PREVIEW_WIDTH: i32 = 96;
+const PREVIEW_HEIGHT: i32 = 64;
+
+// Show the window until it is closed
+pub fn run(config_path: &Path) -> error::Result<()> {
+    let app = adw::Application::builder().application_id(APP_ID).build();
+    let path = config_path.to_path_buf();
+    app.connect_activate(move |app| build(app, &path));
+    // The command line was parsed already; GTK is not to read it again
+    if app.run_with_args::<&str>(&[]) != glib::ExitCode::SUCCESS {
+        return Err(Error::Failed(tr!("window-failed")));
+    }
+    Ok(())
+}
+
+fn build(app: &adw::Application, config_path: &Path) {
+    // Running `window` again raises the open one
+    if let Some(window) = app.active_window() {
+        window.present();
+        return;
+    }
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .valign(gtk::Align::Start)
+        .build();
+    let clamp = adw::Clamp::builder()
+        .maximum_size(600)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .child(&list)
+        .build();
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .vexpand(true)
+        .child(&clamp)
+        .build();
+    let toasts = adw::ToastOverlay::new();
+    toasts.set_child(Some(&scrolled));
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    content.append(&adw::HeaderBar::new());
+    content.append(&toasts);
+
+    fill(&list, &toasts, config_path);
+    adw::ApplicationWindow::builder()
+        .application(app)
+        .title(tr!("dialog-title"))
+        .default_width(480)
+        .default_height(560)
+        .content(&content)
+        .build()
+        .present();
+}
+
+// Put a row in `list` for each profile in the config file as it is now,
+// replacing the rows there
+fn fill(list: &gtk::ListBox, toasts: &adw::ToastOverlay, config_path: &Path) {
+    while let Some(row) = list.first_child() {
+        list.remove(&row);
+    }
+    let config = match SwayConfig::load(config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            toasts.add_toast(adw::Toast::new(&e.to_string()));
+            return;
+        }
+    };
+    let connected = sway::get_outputs().unwrap_or_default();
+    for profile in config.display_configs() {
+        let rects = layout::profile_rects(&profile, &connected);
+        let outputs: Vec<&str> = rects.iter().map(|(name, _)| name.as_str()).collect();
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&profile.description))
+            .subtitle(glib::markup_escape_text(&outputs.join(", ")))
+            .build();
+        row.add_prefix(&preview(rects));
+
+        let button = gtk::Button::builder().valign(gtk::Align::Center).build();
+        // An Enabled profile whose outputs were changed since can be applied again
+        if profile.is_enabled() && matching::in_effect(&profile, &connected) {
+            button.set_label(&tr!("window-active"));
+            button.set_sensitive(false);
+        } else {
+            button.set_label(&tr!("window-switch"));
+            button.add_css_class("suggested-action");
+        }
+        let path = config_path.to_path_buf();
+        button.connect_clicked(glib::clone!(
+            #[weak]
+            list,
+            #[weak]
+            toasts,
+            move |_| {
+                let message = match switch(&path, &profile) {
+                    Ok(()) => tr!("window-switched", profile.description),
+                    Err(e) => e.to_string(),
+                };
+                toasts.add_toast(adw::Toast::new(&message));
+                fill(&list, &toasts, &path);
+            }
+        ));
+        row.add_suffix(&button);
+        row.set_activatable_widget(Some(&button));
+        list.append(&row);
+    }
+}
+
+// Switch to `profile` as `switch` does, with the config read again in case it
+// changed since the window was filled
+fn switch(config_path: &Path, profile: &DisplayConfig) -> error::Result<()> {
+    let config = SwayConfig::load(config_path)?;
+    let display_configs = config.display_configs();
+    let index = display_configs
+        .iter()
+        .position(|other| other.description == profile.description)
+        .ok_or_else(|| Error::NoSuchProfile(profile.description.clone()))?;
+    if switcher::already_active(&display_configs[index], false) {
+        return Err(Error::Unchanged(tr!("already-active", profile.description)));
+    }
+    switcher::switch_persistent(&config, &display_configs, index, "window")
+}
+
+// The profile's enabled outputs drawn as rectangles, scaled to fit
+fn preview(rects: Vec<(String, Rect)>) -> gtk::DrawingArea {
+    let area = gtk::DrawingArea::builder()
+        .content_width(PREVIEW_WIDTH)
+        .content_height(PREVIEW_HEIGHT)
+        .valign(gtk::Align::Center)
+        .build();
+    area.set_draw_func(move |_, cr, width, height| draw(cr, &rects, width, height));
+    area
+}
+
+fn draw(cr: &cairo::Context, rects: &[(String, Rect)], width: i32, height: i32) {
+    let left = rects.iter().map(|(_, r)| r.x).min().unwrap_or(0);
+    let top = rects.iter().map(|(_, r)| r.y).min().unwrap_or(0);
+    let right = rects.iter().map(|(_, r)| r.x + r.width).max().unwrap_or(0);
+    let bottom = rects.iter().map(|(_, r)| r.y + r.height).max().unwrap_or(0);
+    if right <= left || bottom <= top {
+        return;
+    }
+    let margin = 2.0;
+    let scale = ((f64::from(width) - 2.0 * margin) / (right - left) as f64)
+        .min((f64::from(height) - 2.0 * margin) / (bottom - top) as f64);
+    // Centered in the area
+    let x0 = (f64::from(width) - (right - left) as f64 * scale) / 2.0;
+    let y0 = (f64::from(height) - (bottom - top) as f64 * scale) / 2.0;
+    cr.set_line_width(1.0);
+    for (_, rect) in rects {
+        cr.rectangle(
+            x0 + (rect.x - left) as f64 * scale + 0.5,
+            y0 + (rect.y - top) as f64 * scale + 0.5,
+            rect.width as f64 * scale - 1.0,
+            rect.height as f64 * scale - 1.0,
+        );
+        cr.set_source_rgba(0.21, 0.52, 0.89, 0.35);
+        let _ = cr.fill_preserve();
+        cr.set_source_rgba(0.21, 0.52, 0.89, 1.0);
+        let _ = cr.stroke();
+    }
+}
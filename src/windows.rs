@@ -0,0 +1,224 @@
+// Where windows and workspaces were while a profile was in effect, so they can
+// be put back when the profile is applied again (`remember_windows = yes`)
+
+use crate::config::DisplayConfig;
+use crate::output;
+use crate::state;
+use crate::tr;
+use crate::{matching, sway};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+static REMEMBER: OnceLock<bool> = OnceLock::new();
+
+// Save and restore window placement from now on
+pub fn set_remember(remember: bool) {
+    let _ = REMEMBER.set(remember);
+}
+
+pub fn remembering() -> bool {
+    REMEMBER.get().copied().unwrap_or(false)
+}
+
+// A window by its Sway container ID, which stays the same for as long as the
+// window is open
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Window {
+    id: i64,
+    #[serde(default)]
+    workspace: String,
+    // x, y, width and height of a floating window
+    floating: Option<[i64; 4]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Workspace {
+    name: String,
+    output: String,
+}
+
+// The workspaces on each output and the windows on each workspace
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Placement {
+    workspaces: Vec<Workspace>,
+    windows: Vec<Window>,
+}
+
+// The saved placements, one per profile description
+fn path() -> PathBuf {
+    state::state_dir().join("windows")
+}
+
+fn load() -> Vec<(String, Placement)> {
+    let Ok(contents) = fs::read_to_string(path()) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<serde_json::Map<String, Value>>(&contents) {
+        Ok(entries) => entries
+            .into_iter()
+            .filter_map(|(profile, value)| Some((profile, serde_json::from_value(value).ok()?)))
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// The current placement from Sway's tree, leaving out the scratchpad
+fn current() -> Option<Placement> {
+    let tree = sway::get_tree()?;
+    let mut placement = Placement::default();
+    for output in children(&tree) {
+        let output_name = name(output);
+        if output_name.starts_with("__") {
+            continue;
+        }
+        for workspace in children(output) {
+            placement.workspaces.push(Workspace {
+                name: name(workspace).to_string(),
+                output: output_name.to_string(),
+            });
+            collect_windows(workspace, name(workspace), false, &mut placement.windows);
+        }
+    }
+    Some(placement)
+}
+
+fn name(node: &Value) -> &str {
+    node.get("name").and_then(Value::as_str).unwrap_or("")
+}
+
+// Tiled and floating children of a tree node
+fn children(node: &Value) -> impl Iterator<Item = &Value> {
+    ["nodes", "floating_nodes"].into_iter().flat_map(|key| {
+        node.get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+    })
+}
+
+fn collect_windows(node: &Value, workspace: &str, floating: bool, windows: &mut Vec<Window>) {
+    for (key, floating) in [("nodes", floating), ("floating_nodes", true)] {
+        for child in node
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            // Windows have a pid; containers only hold other nodes
+            if child.get("pid").is_none() {
+                collect_windows(child, workspace, floating, windows);
+                continue;
+            }
+            let rect = |field| {
+                child
+                    .get("rect")
+                    .and_then(|rect| rect.get(field))
+                    .and_then(Value::as_i64)
+                    .unwrap_or(0)
+            };
+            windows.push(Window {
+                id: child.get("id").and_then(Value::as_i64).unwrap_or(0),
+                workspace: workspace.to_string(),
+                floating: floating.then(|| [rect("x"), rect("y"), rect("width"), rect("height")]),
+            });
+        }
+    }
+}
+
+// Remember where the windows are for `profile`, as long as its outputs are
+// still in effect; once they have changed (a dock already unplugged), Sway has
+// moved the windows itself and the placement saved earlier is kept
+pub fn save(profile: &DisplayConfig) {
+    if !remembering() {
+        return;
+    }
+    let in_effect =
+        sway::get_outputs().is_some_and(|connected| matching::in_effect(profile, &connected));
+    let Some(placement) = current().filter(|_| in_effect) else {
+        return;
+    };
+    let mut saved = load();
+    saved.retain(|(name, _)| *name != profile.description);
+    saved.push((profile.description.clone(), placement));
+    let document: serde_json::Map<String, Value> = saved
+        .iter()
+        .filter_map(|(name, placement)| Some((name.clone(), serde_json::to_value(placement).ok()?)))
+        .collect();
+    let path = path();
+    let written = fs::create_dir_all(state::state_dir())
+        .and_then(|_| fs::write(&path, Value::Object(document).to_string()));
+    if let Err(e) = written {
+        eprintln!("{}", tr!("warning", tr!("write-failed", path.display(), e)));
+    }
+}
+
+// Move the windows that are still open back to the workspaces they were on
+// while `profile` was last in effect, those workspaces back to their outputs
+// and floating windows back to where they were
+pub fn restore(profile: &DisplayConfig) {
+    if !remembering() {
+        return;
+    }
+    let Some((_, saved)) = load()
+        .into_iter()
+        .find(|(name, _)| *name == profile.description)
+    else {
+        return;
+    };
+    let Some(now) = current() else {
+        return;
+    };
+    let connected = sway::get_outputs().unwrap_or_default();
+    let open: Vec<&Window> = saved
+        .windows
+        .iter()
+        .filter(|window| now.windows.iter().any(|w| w.id == window.id))
+        .collect();
+
+    for window in &open {
+        let moved = now
+            .windows
+            .iter()
+            .any(|w| w.id == window.id && w.workspace != window.workspace);
+        if moved {
+            sway::try_command(&format!(
+                "[con_id={}] move container to workspace {}",
+                window.id,
+                output::quote(&window.workspace)
+            ));
+        }
+    }
+    // A workspace is moved through one of its windows, so the command does not
+    // change which workspace is focused
+    for Workspace { name, output } in &saved.workspaces {
+        let moved = now
+            .workspaces
+            .iter()
+            .any(|now_on| now_on.name == *name && now_on.output != *output);
+        if !moved || !connected.iter().any(|o| o.name == *output) {
+            continue;
+        }
+        if let Some(window) = open.iter().find(|window| window.workspace == *name) {
+            sway::try_command(&format!(
+                "[con_id={}] move workspace to output {}",
+                window.id,
+                output::quote(output)
+            ));
+        }
+    }
+    for window in &open {
+        if now.windows.iter().any(|w| w == *window) {
+            continue;
+        }
+        if let Some([x, y, width, height]) = window.floating {
+            sway::try_command(&format!(
+                "[con_id={}] resize set {} px {} px, move absolute position {} px {} px",
+                window.id, width, height, x, y
+            ));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+// Interactive creation of a new profile from the connected outputs
+
+use crate::prompt;
+use sway_display_switcher::config::DisplayConfig;
+use sway_display_switcher::error::{self, Error};
+use sway_display_switcher::layout::{self, Rect, Side};
+use sway_display_switcher::sway::{self, Mode, Output};
+use sway_display_switcher::tr;
+use sway_display_switcher::{migrate, output, roles, scale};
+
+// An output the user chose to enable, with its place in the layout
+struct Placed {
+    name: String,
+    mode: Mode,
+    scale: f64,
+    rect: Rect,
+}
+
+// With `auto_scale`, the suggested scale is used without asking
+pub fn run(auto_scale: bool) -> error::Result<DisplayConfig> {
+    let connected = sway::get_outputs().ok_or_else(|| Error::Sway(tr!("outputs-query-failed")))?;
+    if connected.is_empty() {
+        return Err(Error::Failed(tr!("no-outputs")));
+    }
+
+    let mut placed: Vec<Placed> = Vec::new();
+    let mut disabled = Vec::new();
+    for output in &connected {
+        let roles: Vec<&str> = roles::roles_of(output, &connected)
+            .into_iter()
+            .map(roles::Role::name)
+            .collect();
+        println!(
+            "\n{} ({}) [{}]",
+            output.name,
+            output.identifier(),
+            roles.join(", ")
+        );
+        if prompt::confirm(&tr!("enable-output"), true)? {
+            configure_output(output, &mut placed, auto_scale)?;
+        } else {
+            disabled.push(output.name.clone());
+        }
+    }
+
+    if placed.is_empty() {
+        println!("\n{}", tr!("all-disabled"));
+    }
+
+    // Shift the layout so that its top-left corner is at 0,0
+    let min_x = placed.iter().map(|p| p.rect.x).min().unwrap_or(0);
+    let min_y = placed.iter().map(|p| p.rect.y).min().unwrap_or(0);
+    for output in placed.iter_mut() {
+        output.rect.x -= min_x;
+        output.rect.y -= min_y;
+    }
+
+    let mut lines: Vec<String> = placed.iter().map(enabled_line).collect();
+    lines.extend(
+        disabled
+            .iter()
+            .map(|name| format!("output {} disable", name)),
+    );
+
+    // Preview the layout before saving
+    println!("\n{}", tr!("layout-preview"));
+    for output in &placed {
+        let rect = &output.rect;
+        let placed_at = tr!("layout-at", rect.width, rect.height, rect.x, rect.y);
+        println!("  {:<12} {}", output.name, placed_at);
+    }
+    println!("\n{}", tr!("profile-lines"));
+    for line in &lines {
+        println!("  {}", line);
+    }
+
+    let description = loop {
+        let name = prompt::ask(&format!("\n{}", tr!("profile-name")), "")?;
+        // Commas would break the header line
+        if !name.is_empty() && !name.contains(',') {
+            break name;
+        }
+        println!("{}", tr!("name-invalid"));
+    };
+
+    let mut profile = DisplayConfig {
+        description,
+        status: "Disabled".to_string(),
+        outputs: lines,
+        metadata: Vec::new(),
+    };
+    migrate::add_identities(&mut profile, &connected);
+    Ok(profile)
+}
+
+// Ask how one more output fits into an existing profile and return the line
+// to add for it, placed relative to the profile's enabled outputs
+pub fn output_line(
+    config: &DisplayConfig,
+    output: &Output,
+    connected: &[Output],
+    auto_scale: bool,
+) -> error::Result<String> {
+    println!("\n{} ({})", output.name, output.identifier());
+    if !prompt::confirm(&tr!("enable-output"), true)? {
+        return Ok(format!("output {} disable", output.name));
+    }
+    let mut placed: Vec<Placed> = layout::profile_rects(config, connected)
+        .into_iter()
+        .map(|(name, rect)| Placed {
+            name,
+            mode: output.current_mode(),
+            scale: 1.0,
+            rect,
+        })
+        .collect();
+    configure_output(output, &mut placed, auto_scale)?;
+    Ok(placed.last().map(enabled_line).unwrap_or_default())
+}
+
+fn enabled_line(placed: &Placed) -> String {
+    format!(
+        "output {} enable mode {} pos {} {} scale {}",
+        output::quote(&placed.name),
+        placed.mode,
+        placed.rect.x,
+        placed.rect.y,
+        format_scale(placed.scale)
+    )
+}
+
+// Ask for mode, scale and position of one output and add it to the layout
+fn configure_output(
+    output: &Output,
+    placed: &mut Vec<Placed>,
+    auto_scale: bool,
+) -> error::Result<()> {
+    let mode = if output.modes.is_empty() {
+        output.current_mode()
+    } else {
+        println!("{}", tr!("available-modes"));
+        for (i, mode) in output.modes.iter().enumerate() {
+            println!("  {}. {}", i + 1, mode);
+        }
+        let current = output
+            .modes
+            .iter()
+            .position(|m| *m == output.current_mode())
+            .unwrap_or(0);
+        output.modes[prompt::choose(&tr!("mode"), output.modes.len(), current)?]
+    };
+
+    let suggested = scale::suggest(output, &mode);
+    if let (Some(dpi), Some(suggested)) = (scale::dpi(output, &mode), suggested) {
+        println!(
+            "{}",
+            tr!(
+                "dpi-suggestion",
+                format!("{:.0}", dpi),
+                format_scale(suggested)
+            )
+        );
+    }
+    let default_scale = suggested.unwrap_or(output.scale);
+    let scale = if auto_scale {
+        default_scale
+    } else {
+        loop {
+            let answer = prompt::ask(&tr!("scale"), &format_scale(default_scale))?;
+            match answer.parse::<f64>() {
+                Ok(scale) if scale > 0.0 => break scale,
+                _ => println!("{}", tr!("scale-invalid")),
+            }
+        }
+    };
+
+    let mut rect = Rect::logical(0, 0, mode.width, mode.height, scale, false);
+    if !placed.is_empty() {
+        let names: Vec<&str> = placed.iter().map(|p| p.name.as_str()).collect();
+        let anchor = if placed.len() == 1 {
+            0
+        } else {
+            println!("{}", tr!("place-relative"));
+            for (i, name) in names.iter().enumerate() {
+                println!("  {}. {}", i + 1, name);
+            }
+            prompt::choose(&tr!("output"), placed.len(), placed.len() - 1)?
+        };
+        let side = loop {
+            let answer = prompt::ask(&tr!("position-relative", names[anchor]), "right")?;
+            match Side::parse(&answer) {
+                Some(side) => break side,
+                None => println!("{}", tr!("side-invalid")),
+            }
+        };
+        (rect.x, rect.y) = rect.placed(&placed[anchor].rect, side);
+    }
+
+    placed.push(Placed {
+        name: output.name.clone(),
+        mode,
+        scale,
+        rect,
+    });
+    Ok(())
+}
+
+fn format_scale(scale: f64) -> String {
+    let text = format!("{:.3}", scale);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
@@ -0,0 +1,134 @@
+// Rewriting the managed section leaves the rest of the file as it was read,
+// down to the bytes and the final newline, or leaves the file alone. Headers
+// are read however they were typed, and descriptions survive the rewrite.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use sway_display_switcher::config::SwayConfig;
+
+// A config file of its own for each test, with backups kept beside them
+fn config_file(test: &str, contents: &[u8]) -> PathBuf {
+    let dir = env::temp_dir().join(format!("sway-display-switcher-test-{}", process::id()));
+    env::set_var("XDG_STATE_HOME", dir.join("state"));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(test);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+const SECTION: &str = "# Display Start
+# Description = Laptop, Status = Enabled
+output eDP-1 enable
+
+# Description = Desk, Status = Disabled
+# output DP-1 enable
+# Display End";
+
+// Switch the Enabled profile, which rewrites the section
+fn switch_to_desk(path: &Path) -> Result<(), String> {
+    let config = SwayConfig::load(path).map_err(|e| e.to_string())?;
+    let mut configs = config.display_configs();
+    configs[0].status = "Disabled".to_string();
+    configs[1].status = "Enabled".to_string();
+    config.save(&configs).map_err(|e| e.to_string())
+}
+
+#[test]
+fn keeps_what_surrounds_the_section() {
+    let before = "set $mod Mod4\n# café\n";
+    let after = "\nbindsym $mod+Return exec foot";
+    let path = config_file(
+        "surrounds",
+        format!("{}{}\n{}", before, SECTION, after).as_bytes(),
+    );
+    switch_to_desk(&path).unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    assert!(written.starts_with(&format!("{}# Display Start\n", before)));
+    assert!(written.ends_with(&format!("# Display End\n{}", after)));
+    assert!(written.contains("# Description = Desk, Status = Enabled\noutput DP-1 enable\n"));
+}
+
+#[test]
+fn keeps_a_missing_final_newline() {
+    let path = config_file("no-newline", SECTION.as_bytes());
+    switch_to_desk(&path).unwrap();
+    let written = fs::read(&path).unwrap();
+    assert!(written.ends_with(b"# Display End"));
+}
+
+#[test]
+fn keeps_the_final_newline() {
+    let path = config_file("newline", format!("{}\n", SECTION).as_bytes());
+    switch_to_desk(&path).unwrap();
+    let written = fs::read(&path).unwrap();
+    assert!(written.ends_with(b"# Display End\n"));
+    assert!(!written.ends_with(b"\n\n"));
+}
+
+#[test]
+fn keeps_crlf_line_endings() {
+    let path = config_file(
+        "crlf",
+        format!("{}\r\n", SECTION.replace('\n', "\r\n")).as_bytes(),
+    );
+    switch_to_desk(&path).unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    assert!(written.ends_with("# Display End\r\n"));
+    assert_eq!(
+        written.matches('\n').count(),
+        written.matches("\r\n").count()
+    );
+}
+
+#[test]
+fn leaves_a_file_that_is_not_utf8_alone() {
+    let mut contents = b"# caf\xe9 in Latin-1\n".to_vec();
+    contents.extend_from_slice(SECTION.as_bytes());
+    contents.push(b'\n');
+    let path = config_file("latin1", &contents);
+    let error = switch_to_desk(&path).unwrap_err();
+    assert!(error.contains("not valid UTF-8"), "{}", error);
+    assert_eq!(fs::read(&path).unwrap(), contents);
+}
+
+#[test]
+fn reads_hand_written_headers() {
+    let section = "# Display Start
+##\tDescription=Desk, left ,Status = Enabled
+output DP-1 enable
+  ###  Description\t=\tCafé → Beamer 🎥 ,\tStatus=Disabled, Alias = ü
+#\toutput HDMI-A-1 enable\t
+# Display End
+";
+    let path = config_file("hand-written", section.as_bytes());
+    let configs = SwayConfig::load(&path).unwrap().display_configs();
+    assert_eq!(configs.len(), 2);
+    assert_eq!(configs[0].description, "Desk, left");
+    assert_eq!(configs[0].status, "Enabled");
+    assert_eq!(configs[1].description, "Café → Beamer 🎥");
+    assert_eq!(configs[1].status, "Disabled");
+    assert_eq!(configs[1].outputs, ["output HDMI-A-1 enable"]);
+}
+
+#[test]
+fn keeps_unicode_descriptions_when_rewritten() {
+    let section = "# Display Start
+#\tDescription = Laptop 💻, Status = Enabled
+output eDP-1 enable
+
+## Description = Café → Beamer, Status = Disabled
+# output HDMI-A-1 enable
+# Display End
+";
+    let path = config_file("unicode", section.as_bytes());
+    switch_to_desk(&path).unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    assert!(written.contains("# Description = Laptop 💻, Status = Disabled\n"));
+    assert!(written
+        .contains("# Description = Café → Beamer, Status = Enabled\noutput HDMI-A-1 enable\n"));
+    let configs = SwayConfig::load(&path).unwrap().display_configs();
+    assert_eq!(configs[0].description, "Laptop 💻");
+    assert_eq!(configs[1].description, "Café → Beamer");
+}
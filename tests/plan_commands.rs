@@ -0,0 +1,83 @@
+// The Sway commands a plan records for a profile, which have to be the ones a
+// switch sends for the same connected outputs
+
+use sway_display_switcher::config::DisplayConfig;
+use sway_display_switcher::sway::{self, Output};
+
+fn connected(name: &str) -> Output {
+    Output {
+        name: name.to_string(),
+        make: "Make".to_string(),
+        model: name.to_string(),
+        serial: String::new(),
+        active: true,
+        width: 1920,
+        height: 1080,
+        refresh: 60000,
+        x: 0,
+        y: 0,
+        scale: 1.0,
+        transform: "normal".to_string(),
+        modes: Vec::new(),
+        physical_width: 0,
+        physical_height: 0,
+    }
+}
+
+fn profile(lines: &[&str]) -> DisplayConfig {
+    DisplayConfig {
+        description: "Desk".to_string(),
+        status: "Disabled".to_string(),
+        outputs: lines.iter().map(|line| line.to_string()).collect(),
+        metadata: Vec::new(),
+    }
+}
+
+#[test]
+fn conditions_are_resolved() {
+    let desk = profile(&[
+        "?if-present HDMI-A-1: output eDP-1 disable",
+        "?if-absent HDMI-A-1: output eDP-1 enable",
+        "output HDMI-A-1 enable",
+    ]);
+    let outputs = [connected("eDP-1"), connected("HDMI-A-1")];
+    assert_eq!(
+        sway::output_commands(&desk, &outputs),
+        ["output eDP-1 disable", "output HDMI-A-1 enable"]
+    );
+    assert_eq!(
+        sway::output_commands(&desk, &outputs[..1]),
+        ["output eDP-1 enable", "output HDMI-A-1 enable"]
+    );
+}
+
+#[test]
+fn wildcard_goes_to_each_output() {
+    let desk = profile(&["output * bg #000000 solid_color", "output HDMI-A-1 enable"]);
+    let outputs = [connected("eDP-1"), connected("HDMI-A-1")];
+    assert_eq!(
+        sway::output_commands(&desk, &outputs),
+        [
+            "output eDP-1 bg #000000 solid_color",
+            "output HDMI-A-1 bg #000000 solid_color",
+            "output HDMI-A-1 enable",
+        ]
+    );
+}
+
+#[test]
+fn gaps_take_their_runtime_form() {
+    let desk = profile(&[
+        "output HDMI-A-1 enable",
+        "gaps inner 10",
+        "gaps outer all set 4",
+    ]);
+    assert_eq!(
+        sway::output_commands(&desk, &[connected("HDMI-A-1")]),
+        [
+            "output HDMI-A-1 enable",
+            "gaps inner all set 10",
+            "gaps outer all set 4",
+        ]
+    );
+}
@@ -0,0 +1,163 @@
+// Parsing the managed section and rendering the profiles back unchanged has to
+// give the same lines: profiles with and without blank lines between them, and
+// comments before the first profile and inside profiles
+
+use proptest::prelude::*;
+use sway_display_switcher::config::DisplayConfig;
+use sway_display_switcher::section::Section;
+
+// One profile as written: whether it is Enabled, its lines below the header
+// and the blank lines after it
+#[derive(Debug, Clone)]
+struct Written {
+    enabled: bool,
+    body: Vec<String>,
+    blanks: usize,
+}
+
+fn body_line(enabled: bool) -> impl Strategy<Value = String> {
+    let output = (1..4u8, 0..3u16, prop::bool::ANY).prop_map(move |(port, x, indented)| {
+        let line = format!(
+            "output DP-{} enable mode 1920x1080 pos {} 0 scale 1",
+            port,
+            x * 1920
+        );
+        let line = if enabled { line } else { format!("# {}", line) };
+        if indented {
+            format!("    {}", line)
+        } else {
+            line
+        }
+    });
+    prop_oneof![
+        4 => output,
+        1 => "[a-z]{3,8}( [a-z]{3,8}){0,3}".prop_map(|remark| format!("# {}", remark)),
+        1 => Just(String::new()),
+    ]
+}
+
+fn profile() -> impl Strategy<Value = Written> {
+    prop::bool::ANY.prop_flat_map(|enabled| {
+        (prop::collection::vec(body_line(enabled), 0..5), 0..3usize).prop_map(
+            move |(mut body, blanks)| {
+                // Blank lines at the end of a body are the separator
+                while body.last().is_some_and(String::is_empty) {
+                    body.pop();
+                }
+                Written {
+                    enabled,
+                    body,
+                    blanks,
+                }
+            },
+        )
+    })
+}
+
+fn preamble() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec(
+        "[a-z]{3,8}( [a-z]{3,8}){0,3}".prop_map(|remark| format!("# {}", remark)),
+        0..3,
+    )
+}
+
+// The section lines for the written profiles; the last one has no blank lines
+// after it, as the `# Display End` marker follows
+fn section_lines(preamble: &[String], profiles: &[Written], separate: bool) -> Vec<String> {
+    let mut lines = preamble.to_vec();
+    for (index, profile) in profiles.iter().enumerate() {
+        let status = if profile.enabled {
+            "Enabled"
+        } else {
+            "Disabled"
+        };
+        lines.push(format!(
+            "# Description = Profile {}, Status = {}",
+            index, status
+        ));
+        lines.extend(profile.body.iter().cloned());
+        if separate && index + 1 < profiles.len() {
+            lines.extend((0..profile.blanks.max(1)).map(|_| String::new()));
+        }
+    }
+    lines
+}
+
+fn round_trip(lines: &[String]) -> Vec<String> {
+    let section = Section::parse(lines);
+    section.render(&section.configs())
+}
+
+fn new_profile() -> DisplayConfig {
+    DisplayConfig {
+        description: "Added".to_string(),
+        status: "Disabled".to_string(),
+        outputs: vec!["output HDMI-A-1 enable".to_string()],
+        metadata: Vec::new(),
+    }
+}
+
+proptest! {
+    #[test]
+    fn renders_what_it_parsed(
+        preamble in preamble(),
+        profiles in prop::collection::vec(profile(), 0..5),
+        separate in prop::bool::ANY,
+    ) {
+        let lines = section_lines(&preamble, &profiles, separate);
+        prop_assert_eq!(round_trip(&lines), lines);
+    }
+
+    #[test]
+    fn added_profile_follows_the_layout(
+        preamble in preamble(),
+        profiles in prop::collection::vec(profile(), 2..5),
+        separate in prop::bool::ANY,
+    ) {
+        let lines = section_lines(&preamble, &profiles, separate);
+        let section = Section::parse(&lines);
+        let mut configs = section.configs();
+        configs.push(new_profile());
+        let rendered = section.render(&configs);
+
+        let mut expected = lines.clone();
+        if separate {
+            expected.push(String::new());
+        }
+        expected.push("# Description = Added, Status = Disabled".to_string());
+        expected.push("# output HDMI-A-1 enable".to_string());
+        prop_assert_eq!(rendered, expected);
+    }
+}
+
+#[test]
+fn adds_no_blank_line_between_adjacent_profiles() {
+    let lines: Vec<String> = [
+        "# Description = Laptop, Status = Enabled",
+        "output eDP-1 enable",
+        "# Description = Desk, Status = Disabled",
+        "# output DP-1 enable",
+    ]
+    .iter()
+    .map(|line| line.to_string())
+    .collect();
+    assert_eq!(round_trip(&lines), lines);
+}
+
+#[test]
+fn removed_profile_leaves_no_separator_behind() {
+    let lines: Vec<String> = [
+        "# Description = Laptop, Status = Enabled",
+        "output eDP-1 enable",
+        "",
+        "# Description = Desk, Status = Disabled",
+        "# output DP-1 enable",
+    ]
+    .iter()
+    .map(|line| line.to_string())
+    .collect();
+    let section = Section::parse(&lines);
+    let mut configs = section.configs();
+    configs.pop();
+    assert_eq!(section.render(&configs), &lines[..2]);
+}